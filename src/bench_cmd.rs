@@ -0,0 +1,204 @@
+use crate::cmd_grammar::Rule;
+use crate::csdr_cmd::eval_cmd::EvalCmd;
+use crate::csdr_cmd::AnyCmd;
+use crate::grc::builder::GrcBuilder;
+use crate::grc::converter::Grc2FutureSdr;
+use crate::grc::{BlockInstance, Grc};
+use crate::stats::{BlockStats, FlowgraphStats, StatsFormat};
+use anyhow::{Context, Result};
+use futures::executor::block_on;
+use futuresdr::runtime::Runtime;
+use pest::iterators::Pair;
+use serde::Serialize;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Number of samples generated when neither `--samples` nor `--seconds` is given
+pub const DEFAULT_BENCH_SAMPLES: u64 = 10_000_000;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BenchOptions {
+    pub samples: Option<u64>,
+    pub seconds: Option<f64>,
+    pub json: bool,
+}
+
+pub trait BenchCmd<'i> {
+    fn bench_options(&self) -> Result<BenchOptions>;
+    fn bench_grc(&self) -> Result<Grc>;
+}
+
+impl<'i> BenchCmd<'i> for Pair<'i, Rule> {
+    fn bench_options(&self) -> Result<BenchOptions> {
+        let mut options = BenchOptions::default();
+        for opt in self.clone().into_inner() {
+            match opt.as_rule() {
+                Rule::bench_samples_opt => {
+                    let value = opt.into_inner().next().context("number of samples")?;
+                    options.samples = Some(value.eval()? as u64);
+                }
+                Rule::bench_seconds_opt => {
+                    let value = opt.into_inner().next().context("number of seconds")?;
+                    options.seconds = Some(value.eval()? as f64);
+                }
+                Rule::bench_json_opt => options.json = true,
+                _ => {}
+            }
+        }
+        Ok(options)
+    }
+
+    fn bench_grc(&self) -> Result<Grc> {
+        let mut grc_builder = GrcBuilder::new();
+        for sub_cmd in self.clone().into_inner() {
+            match sub_cmd.as_rule() {
+                Rule::bench_samples_opt | Rule::bench_seconds_opt | Rule::bench_json_opt => {}
+                _ => grc_builder = AnyCmd::parse(&sub_cmd, grc_builder)?,
+            }
+        }
+        grc_builder.ensure_sink()?;
+        grc_builder.build()
+    }
+}
+
+/// Turn a pipeline into a benchmark (`fsdr-cli bench`): stdin is replaced by
+/// an in-memory noise source of the same item type, stdout by a null sink,
+/// and the source is limited to `samples` items if given.
+pub fn prepare_bench(grc: Grc, samples: Option<u64>) -> Result<Grc> {
+    let mut grc = grc;
+    let mut sources = Vec::new();
+    for blk in grc.blocks.iter_mut() {
+        let item_type = blk.parameter_or("type", "float").to_string();
+        if blk.parameter("file").is_some_and(|file| file == "-") {
+            match &blk.id[..] {
+                "blocks_file_source" => {
+                    *blk = BlockInstance::new(&blk.name, "analog_fastnoise_source_x")
+                        .with("type", &item_type)
+                        .with("noise_type", "analog.GR_UNIFORM")
+                        .with("amp", "1.0");
+                    sources.push((blk.name.clone(), item_type));
+                }
+                "blocks_file_sink" => {
+                    *blk =
+                        BlockInstance::new(&blk.name, "blocks_null_sink").with("type", &item_type);
+                }
+                _ => {}
+            }
+        }
+    }
+    if let Some(samples) = samples {
+        for (source, item_type) in sources {
+            let head_name = format!("{source}_head");
+            grc.blocks.push(
+                BlockInstance::new(&head_name, "blocks_head")
+                    .with("type", &item_type)
+                    .with("num_items", &samples.to_string()),
+            );
+            for connection in grc.connections.iter_mut() {
+                if connection[0] == source {
+                    connection[0] = head_name.clone();
+                }
+            }
+            grc.connections.push([
+                source.clone(),
+                "0".to_string(),
+                head_name.clone(),
+                "0".to_string(),
+            ]);
+        }
+    }
+    Ok(grc)
+}
+
+/// Result of a benchmark run
+#[derive(Debug, Serialize)]
+pub struct BenchReport {
+    /// Seconds
+    pub elapsed: f64,
+    /// Samples generated by the source(s)
+    pub samples: u64,
+    pub samples_per_second: f64,
+    pub blocks: Vec<BlockStats>,
+}
+
+impl BenchReport {
+    fn new(sources: &[String], stats: &FlowgraphStats, elapsed: Duration) -> BenchReport {
+        let blocks = stats.blocks(elapsed);
+        let samples = blocks
+            .iter()
+            .filter(|blk| sources.contains(&blk.name))
+            .map(|blk| blk.produced)
+            .sum();
+        let elapsed = elapsed.as_secs_f64();
+        BenchReport {
+            elapsed,
+            samples,
+            samples_per_second: samples as f64 / elapsed.max(f64::EPSILON),
+            blocks,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    pub fn to_table(&self) -> String {
+        let mut table = format!(
+            "{:<32} {:<28} {:>12} {:>14}\n",
+            "block", "type", "items", "items/s"
+        );
+        for blk in self.blocks.iter() {
+            let items = blk.produced.max(blk.consumed);
+            table.push_str(&format!(
+                "{:<32} {:<28} {:>12} {:>14.0}\n",
+                blk.name, blk.block_type, items, blk.throughput
+            ));
+        }
+        table.push_str(&format!(
+            "overall: {} samples in {:.3}s, {:.0} samples/s\n",
+            self.samples, self.elapsed, self.samples_per_second
+        ));
+        table
+    }
+}
+
+/// Run the pipeline prepared by [`prepare_bench`] and measure its throughput.
+pub fn run_bench(grc: Grc, options: &BenchOptions) -> Result<BenchReport> {
+    let samples = match (options.samples, options.seconds) {
+        (None, None) => Some(DEFAULT_BENCH_SAMPLES),
+        (samples, _) => samples,
+    };
+    let grc = prepare_bench(grc, samples)?;
+    let mut converter = Grc2FutureSdr::new();
+    converter.with_stats(StatsFormat::Table);
+    let sources: Vec<String> = grc
+        .blocks
+        .iter()
+        .filter(|blk| blk.id == "analog_fastnoise_source_x")
+        .map(|blk| blk.name.clone())
+        .collect();
+    let fg = converter.convert_grc(grc)?;
+    let stats = converter.take_stats().context("stats are enabled")?;
+
+    let runtime = Runtime::new();
+    let start = Instant::now();
+    let (task, handle) = runtime.start_sync(fg)?;
+    let (done, finished) = mpsc::channel::<()>();
+    let timer = options.seconds.map(|seconds| {
+        let mut handle = handle.clone();
+        let duration = Duration::from_secs_f64(seconds);
+        thread::spawn(move || {
+            if let Err(RecvTimeoutError::Timeout) = finished.recv_timeout(duration) {
+                let _ = block_on(handle.terminate());
+            }
+        })
+    });
+    block_on(task)?;
+    let elapsed = start.elapsed();
+    let _ = done.send(());
+    if let Some(timer) = timer {
+        let _ = timer.join();
+    }
+    Ok(BenchReport::new(&sources, &stats, elapsed))
+}
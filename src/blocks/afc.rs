@@ -0,0 +1,140 @@
+use anyhow::Result;
+use futuresdr::num_complex::Complex32;
+use futuresdr::prelude::*;
+use std::f64::consts::TAU;
+use std::io::Write;
+
+/// How an [`Afc`] reports its estimate on stderr
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AfcReport {
+    None,
+    /// Every `interval` samples, as a frequency normalized to the sample rate
+    Normalized {
+        interval: u64,
+    },
+    /// Every second of signal, in Hz
+    Hertz {
+        sample_rate: f64,
+    },
+}
+
+/// State of an [`Afc`]
+struct Estimator {
+    /// Weight of a new sample in the average
+    alpha: f32,
+    /// Average of the product of each sample by the conjugate of the previous one
+    product: Complex32,
+    /// Phase of `product`, ie the offset in radians per sample
+    offset: f64,
+    previous: Complex32,
+    /// Phase of the correction, in radians
+    phase: f64,
+    report: AfcReport,
+    samples: u64,
+}
+
+impl Estimator {
+    fn correct(&mut self, x: Complex32) -> Complex32 {
+        // Like the mean of the output of a quadrature demodulator, but weighted
+        // by the power of the signal, so that phase jumps of PSK do not bias it
+        self.product += (x * self.previous.conj() - self.product) * self.alpha;
+        self.previous = x;
+        self.offset = self.product.arg() as f64;
+        self.phase = (self.phase - self.offset).rem_euclid(TAU);
+        self.samples += 1;
+        let interval = match self.report {
+            AfcReport::None => None,
+            AfcReport::Normalized { interval } => Some(interval),
+            AfcReport::Hertz { sample_rate } => Some(sample_rate.round().max(1.0) as u64),
+        };
+        if interval.is_some_and(|interval| self.samples % interval == 0) {
+            self.report();
+        }
+        x * Complex32::from_polar(1.0, self.phase as f32)
+    }
+
+    fn report(&self) {
+        let offset = self.offset / TAU;
+        let line = match self.report {
+            AfcReport::Hertz { sample_rate } => {
+                format!("afc: offset {:+.1} Hz\n", offset * sample_rate)
+            }
+            _ => format!("afc: offset {offset:+.6}\n"),
+        };
+        let _ = std::io::stderr().write_all(line.as_bytes());
+    }
+}
+
+/// Automatic frequency correction: the carrier offset is estimated as the mean
+/// instantaneous frequency of the signal (ie the mean of the output of a
+/// quadrature demodulator), which is then shifted back to 0.
+/// It suits FSK signals whose symbols are balanced and PSK signals.
+#[derive(Block)]
+pub struct Afc<
+    I: CpuBufferReader<Item = Complex32> = DefaultCpuReader<Complex32>,
+    O: CpuBufferWriter<Item = Complex32> = DefaultCpuWriter<Complex32>,
+> {
+    estimator: Estimator,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<I, O> Afc<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    /// `averaging` is the time constant of the estimate, in samples.
+    pub fn new(averaging: f64, report: AfcReport) -> Self {
+        Self {
+            estimator: Estimator {
+                alpha: 1.0 / averaging.max(1.0) as f32,
+                product: Complex32::default(),
+                offset: 0.0,
+                previous: Complex32::default(),
+                phase: 0.0,
+                report,
+                samples: 0,
+            },
+            input: I::default(),
+            output: O::default(),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for Afc<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let m;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            ilen = i.len();
+            m = std::cmp::min(ilen, o.len());
+            for (x, y) in i.iter().zip(o.iter_mut()).take(m) {
+                *y = self.estimator.correct(*x);
+            }
+        }
+
+        self.input.consume(m);
+        self.output.produce(m);
+
+        if self.input.finished() && m == ilen {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
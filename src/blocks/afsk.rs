@@ -0,0 +1,307 @@
+use super::ax25::strip_kiss_command;
+use super::hdlc::{flag_bits, stuffed_bits, NrziEncoder};
+use anyhow::{bail, Result};
+use futuresdr::num_complex::Complex64;
+use futuresdr::prelude::*;
+use futuresdr::runtime::Pmt;
+use std::collections::VecDeque;
+use std::f64::consts::TAU;
+
+/// Gain of the clock recovery, pulling the symbol transitions half way between two symbols
+const CLOCK_GAIN: f64 = 0.2;
+
+/// Amplitude of the generated tones, leaving room for `fmmod_fc` not to deviate
+/// by more than a quarter of the sample rate
+const AMPLITUDE: f32 = 0.5;
+
+fn check_parameters(sample_rate: f64, baudrate: f64, mark: f64, space: f64) -> Result<()> {
+    if baudrate <= 0.0 || sample_rate < 2.0 * baudrate {
+        bail!("afsk: at least 2 samples per symbol are needed");
+    }
+    for tone in [mark, space] {
+        if tone <= 0.0 || tone >= sample_rate / 2.0 {
+            bail!("afsk: tone {tone} Hz out of (0, {}) Hz", sample_rate / 2.0);
+        }
+    }
+    Ok(())
+}
+
+/// Correlation of the last samples with a tone: the sum over a symbol of the
+/// signal brought to baseband, ie a matched filter of the tone.
+struct ToneCorrelator {
+    /// Angular frequency, in radians per sample
+    omega: f64,
+    phase: f64,
+    history: Vec<Complex64>,
+    index: usize,
+    sum: Complex64,
+}
+
+impl ToneCorrelator {
+    fn new(frequency: f64, sample_rate: f64, len: usize) -> ToneCorrelator {
+        ToneCorrelator {
+            omega: TAU * frequency / sample_rate,
+            phase: 0.0,
+            history: vec![Complex64::default(); len],
+            index: 0,
+            sum: Complex64::default(),
+        }
+    }
+
+    /// Energy of the tone over the last symbol
+    fn push(&mut self, x: f32) -> f64 {
+        let baseband = Complex64::from_polar(x as f64, -self.phase);
+        self.phase = (self.phase + self.omega) % TAU;
+        self.sum += baseband - self.history[self.index];
+        self.history[self.index] = baseband;
+        self.index = (self.index + 1) % self.history.len();
+        self.sum.norm_sqr()
+    }
+}
+
+/// State of an [`AfskDemodulator`]
+struct Demodulator {
+    mark: ToneCorrelator,
+    space: ToneCorrelator,
+    /// Position in the current symbol, a symbol being output when it reaches 1
+    clock: f64,
+    /// Symbols per sample
+    step: f64,
+    previous: f32,
+}
+
+impl Demodulator {
+    fn push(&mut self, x: f32) -> Option<f32> {
+        let mark = self.mark.push(x);
+        let space = self.space.push(x);
+        let total = mark + space;
+        let discriminated = if total > 0.0 {
+            ((mark - space) / total) as f32
+        } else {
+            0.0
+        };
+        self.clock += self.step;
+        // The correlators span a symbol, so they are balanced in the middle of one
+        if (discriminated >= 0.0) != (self.previous >= 0.0) {
+            self.clock += CLOCK_GAIN * (0.5 - self.clock);
+        }
+        self.previous = discriminated;
+        if self.clock >= 1.0 {
+            self.clock -= 1.0;
+            Some(discriminated)
+        } else {
+            None
+        }
+    }
+}
+
+/// Audio FSK demodulator, like gr-satellites' `afsk_demodulator`: the energies of the
+/// mark and space tones over a symbol are compared, the comparison being
+/// sampled once per symbol by a clock recovered from its zero crossings.
+/// The soft symbols output are in [-1, 1], positive for the mark tone.
+#[derive(Block)]
+pub struct AfskDemodulator<
+    I: CpuBufferReader<Item = f32> = DefaultCpuReader<f32>,
+    O: CpuBufferWriter<Item = f32> = DefaultCpuWriter<f32>,
+> {
+    demodulator: Demodulator,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<I, O> AfskDemodulator<I, O>
+where
+    I: CpuBufferReader<Item = f32>,
+    O: CpuBufferWriter<Item = f32>,
+{
+    /// Bell 202 is 1200 baud with a 1200 Hz mark and a 2200 Hz space
+    pub fn new(sample_rate: f64, baudrate: f64, mark: f64, space: f64) -> Result<Self> {
+        check_parameters(sample_rate, baudrate, mark, space)?;
+        let len = (sample_rate / baudrate).round() as usize;
+        Ok(Self {
+            demodulator: Demodulator {
+                mark: ToneCorrelator::new(mark, sample_rate, len),
+                space: ToneCorrelator::new(space, sample_rate, len),
+                clock: 0.0,
+                step: baudrate / sample_rate,
+                previous: 0.0,
+            },
+            input: I::default(),
+            output: O::default(),
+        })
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for AfskDemodulator<I, O>
+where
+    I: CpuBufferReader<Item = f32>,
+    O: CpuBufferWriter<Item = f32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let mut consumed = 0;
+        let mut produced = 0;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            ilen = i.len();
+            while consumed < ilen && produced < o.len() {
+                if let Some(symbol) = self.demodulator.push(i[consumed]) {
+                    o[produced] = symbol;
+                    produced += 1;
+                }
+                consumed += 1;
+            }
+        }
+
+        self.input.consume(consumed);
+        self.output.produce(produced);
+
+        if self.input.finished() && consumed == ilen {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// AFSK modulator of AX.25 frames: each PDU received, without its KISS command if
+/// any, is HDLC framed between preamble and tail flags, NRZI encoded and output as
+/// phase continuous audio tones, the mark one for a high level.
+#[derive(Block)]
+#[message_inputs(in_port)]
+pub struct AfskModulator<O: CpuBufferWriter<Item = f32> = DefaultCpuWriter<f32>> {
+    /// Number of flags before and after each frame
+    preamble: usize,
+    tail: usize,
+    nrzi: NrziEncoder,
+    /// Levels not yet sent
+    pending: VecDeque<u8>,
+    /// Angular frequencies of the mark and space tones, in radians per sample
+    mark: f64,
+    space: f64,
+    phase: f64,
+    /// Position in the current symbol, the next level being sent when it reaches 1
+    clock: f64,
+    /// Symbols per sample
+    step: f64,
+    level: Option<u8>,
+    /// No more PDUs will be received
+    finished: bool,
+    #[output]
+    output: O,
+}
+
+impl<O: CpuBufferWriter<Item = f32>> AfskModulator<O> {
+    /// Bell 202 is 1200 baud with a 1200 Hz mark and a 2200 Hz space
+    pub fn new(
+        sample_rate: f64,
+        baudrate: f64,
+        mark: f64,
+        space: f64,
+        preamble: usize,
+        tail: usize,
+    ) -> Result<Self> {
+        check_parameters(sample_rate, baudrate, mark, space)?;
+        Ok(Self {
+            preamble: preamble.max(1),
+            tail: tail.max(1),
+            nrzi: NrziEncoder::default(),
+            pending: VecDeque::new(),
+            mark: TAU * mark / sample_rate,
+            space: TAU * space / sample_rate,
+            phase: 0.0,
+            clock: 0.0,
+            step: baudrate / sample_rate,
+            level: None,
+            finished: false,
+            output: O::default(),
+        })
+    }
+
+    fn push_frame(&mut self, data: &[u8]) {
+        let flags = |count| (0..count).flat_map(|_| flag_bits());
+        let bits: Vec<u8> = flags(self.preamble)
+            .chain(stuffed_bits(data))
+            .chain(flags(self.tail))
+            .collect();
+        for bit in bits {
+            let level = self.nrzi.encode(bit);
+            self.pending.push_back(level);
+        }
+    }
+
+    async fn in_port(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match p {
+            Pmt::Blob(data) => {
+                self.push_frame(strip_kiss_command(&data));
+                io.call_again = true;
+                Ok(Pmt::Ok)
+            }
+            Pmt::Finished => {
+                self.finished = true;
+                io.call_again = true;
+                Ok(Pmt::Ok)
+            }
+            _ => Ok(Pmt::InvalidValue),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<O: CpuBufferWriter<Item = f32>> Kernel for AfskModulator<O> {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let mut produced = 0;
+        {
+            let o = self.output.slice();
+            while produced < o.len() {
+                let level = match self.level {
+                    Some(level) => level,
+                    None => match self.pending.pop_front() {
+                        Some(level) => *self.level.insert(level),
+                        None => break,
+                    },
+                };
+                o[produced] = AMPLITUDE * self.phase.cos() as f32;
+                produced += 1;
+                let omega = if level == 1 { self.mark } else { self.space };
+                self.phase = (self.phase + omega) % TAU;
+                self.clock += self.step;
+                if self.clock >= 1.0 {
+                    self.clock -= 1.0;
+                    self.level = None;
+                }
+            }
+        }
+
+        self.output.produce(produced);
+
+        let idle = self.level.is_none() && self.pending.is_empty();
+        if idle && self.finished {
+            io.finished = true;
+        } else if !idle && produced > 0 {
+            io.call_again = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,261 @@
+use anyhow::{bail, Context, Result};
+use futuresdr::prelude::*;
+use futuresdr::runtime::Pmt;
+use serde::Serialize;
+use std::fmt;
+
+/// Callsign and SSID of an AX.25 address field
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ax25Address {
+    pub callsign: String,
+    pub ssid: u8,
+    /// H bit of a digipeater, set once it has repeated the frame
+    pub repeated: bool,
+}
+
+impl Ax25Address {
+    /// 7 bytes: the callsign shifted left by one bit and padded with spaces, then the SSID
+    fn parse(bytes: &[u8]) -> Result<Ax25Address> {
+        let mut callsign = String::with_capacity(6);
+        for byte in &bytes[..6] {
+            let c = (byte >> 1) as char;
+            if byte & 1 != 0 || !(c.is_ascii_alphanumeric() || c == ' ') {
+                bail!("ax25: invalid callsign byte 0x{byte:02X}");
+            }
+            callsign.push(c);
+        }
+        let callsign = callsign.trim_end().to_string();
+        if callsign.is_empty() {
+            bail!("ax25: empty callsign");
+        }
+        Ok(Ax25Address {
+            callsign,
+            ssid: (bytes[6] >> 1) & 0x0F,
+            repeated: bytes[6] & 0x80 != 0,
+        })
+    }
+}
+
+/// `CALL-SSID`, the SSID being omitted when 0
+impl fmt::Display for Ax25Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.callsign)?;
+        if self.ssid != 0 {
+            write!(f, "-{}", self.ssid)?;
+        }
+        Ok(())
+    }
+}
+
+/// Fields of an AX.25 frame, without its FCS
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ax25Frame {
+    pub destination: Ax25Address,
+    pub source: Ax25Address,
+    /// At most 8 digipeaters
+    pub digipeaters: Vec<Ax25Address>,
+    pub control: u8,
+    /// Protocol identifier, only in I and UI frames
+    pub pid: Option<u8>,
+    pub info: Vec<u8>,
+}
+
+/// The frame without its KISS data frame command, if any: a leading byte below 0x40
+/// whose low nibble is 0, as no shifted callsign character can be.
+pub fn strip_kiss_command(frame: &[u8]) -> &[u8] {
+    match frame.first() {
+        Some(command) if *command < 0x40 && command & 0x0F == 0 => &frame[1..],
+        _ => frame,
+    }
+}
+
+impl Ax25Frame {
+    /// The KISS command of frames from a KISS file or TNC is skipped
+    pub fn parse(frame: &[u8]) -> Result<Ax25Frame> {
+        let frame = strip_kiss_command(frame);
+        // Address fields end with the one whose extension bit is set
+        let addresses = frame
+            .chunks_exact(7)
+            .position(|address| address[6] & 1 == 1)
+            .map(|last| last + 1)
+            .unwrap_or(0);
+        if addresses < 2 {
+            bail!("ax25: missing address field");
+        }
+        if addresses > 10 {
+            bail!("ax25: more than 8 digipeaters");
+        }
+        let mut fields = frame[..7 * addresses]
+            .chunks_exact(7)
+            .map(Ax25Address::parse)
+            .collect::<Result<Vec<_>>>()?;
+        let digipeaters = fields.split_off(2);
+        let source = fields
+            .pop()
+            .context("ax25: source and destination addresses expected")?;
+        let destination = fields
+            .pop()
+            .context("ax25: source and destination addresses expected")?;
+        let rest = &frame[7 * addresses..];
+        let Some(&control) = rest.first() else {
+            bail!("ax25: missing control field");
+        };
+        let (pid, info) = if control & 0x01 == 0 || control & 0xEF == 0x03 {
+            match rest.get(1) {
+                Some(pid) => (Some(*pid), rest[2..].to_vec()),
+                None => bail!("ax25: missing PID field"),
+            }
+        } else {
+            (None, rest[1..].to_vec())
+        };
+        Ok(Ax25Frame {
+            destination,
+            source,
+            digipeaters,
+            control,
+            pid,
+            info,
+        })
+    }
+
+    /// I (information), S (supervisory), UI (unnumbered information) or U (unnumbered)
+    pub fn frame_type(&self) -> &'static str {
+        if self.control & 0x01 == 0 {
+            "I"
+        } else if self.control & 0x03 == 0x01 {
+            "S"
+        } else if self.control & 0xEF == 0x03 {
+            "UI"
+        } else {
+            "U"
+        }
+    }
+
+    /// Digipeater path, the last one having repeated the frame being marked with `*`
+    fn path(&self) -> Vec<String> {
+        let last_repeated = self.digipeaters.iter().rposition(|digi| digi.repeated);
+        self.digipeaters
+            .iter()
+            .enumerate()
+            .map(|(i, digi)| {
+                if Some(i) == last_repeated {
+                    format!("{digi}*")
+                } else {
+                    digi.to_string()
+                }
+            })
+            .collect()
+    }
+
+    /// Information field as text, other bytes than printable ASCII being shown as `<0xNN>`
+    pub fn info_text(&self) -> String {
+        self.info
+            .iter()
+            .map(|byte| match *byte {
+                0x20..=0x7E => (*byte as char).to_string(),
+                _ => format!("<0x{byte:02x}>"),
+            })
+            .collect()
+    }
+
+    /// Monitor format of TNC2 and APRS-IS: `SRC>DST,PATH:info`
+    pub fn to_tnc2(&self) -> String {
+        let mut line = format!("{}>{}", self.source, self.destination);
+        for digi in self.path() {
+            line.push(',');
+            line.push_str(&digi);
+        }
+        line.push(':');
+        line.push_str(&self.info_text());
+        line
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        #[derive(Serialize)]
+        struct Json<'a> {
+            source: String,
+            destination: String,
+            path: Vec<String>,
+            #[serde(rename = "type")]
+            frame_type: &'a str,
+            control: u8,
+            pid: Option<u8>,
+            info: String,
+        }
+        let json = Json {
+            source: self.source.to_string(),
+            destination: self.destination.to_string(),
+            path: self.path(),
+            frame_type: self.frame_type(),
+            control: self.control,
+            pid: self.pid,
+            info: String::from_utf8_lossy(&self.info).into_owned(),
+        };
+        Ok(serde_json::to_string(&json)?)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Ax25Format {
+    Tnc2,
+    Json,
+}
+
+impl TryFrom<&str> for Ax25Format {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        match value.trim_matches(['"', '\'']) {
+            "tnc2" => Ok(Self::Tnc2),
+            "json" => Ok(Self::Json),
+            _ => bail!("Unknown AX.25 format: {value}"),
+        }
+    }
+}
+
+/// Print the AX.25 frames received as PDUs on stdout, one per line,
+/// and forward them unchanged. Invalid frames are reported on stderr.
+#[derive(Block)]
+#[message_inputs(in_port)]
+#[message_outputs(out)]
+pub struct Ax25Print {
+    format: Ax25Format,
+}
+
+impl Ax25Print {
+    pub fn new(format: Ax25Format) -> Self {
+        Self { format }
+    }
+
+    async fn in_port(
+        &mut self,
+        io: &mut WorkIo,
+        mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match p {
+            Pmt::Blob(data) => {
+                match Ax25Frame::parse(&data) {
+                    Ok(frame) => {
+                        match self.format {
+                            Ax25Format::Tnc2 => println!("{}", frame.to_tnc2()),
+                            Ax25Format::Json => println!("{}", frame.to_json()?),
+                        }
+                        mio.post("out", Pmt::Blob(data)).await?;
+                    }
+                    Err(err) => eprintln!("{err}"),
+                }
+                Ok(Pmt::Ok)
+            }
+            Pmt::Finished => {
+                io.finished = true;
+                Ok(Pmt::Ok)
+            }
+            _ => Ok(Pmt::InvalidValue),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl Kernel for Ax25Print {}
@@ -0,0 +1,297 @@
+use super::{NoiseGenerator, NoiseSample, NoiseType};
+use anyhow::{bail, Result};
+use futuresdr::num_complex::Complex32;
+use futuresdr::prelude::*;
+use std::f64::consts::{PI, TAU};
+
+/// Impairments applied by a [`ChannelModel`], like GNU Radio's `channels.channel_model`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChannelParameters {
+    /// Standard deviation of the complex AWGN, ie its power is `noise_voltage²`
+    pub noise_voltage: f32,
+    /// Frequency offset, normalized to the sample rate
+    pub frequency_offset: f64,
+    /// Ratio between the sample clocks of the receiver and of the transmitter, 1.0 for none
+    pub epsilon: f64,
+    /// Multipath FIR taps
+    pub taps: Vec<Complex32>,
+    pub noise_seed: u64,
+}
+
+impl Default for ChannelParameters {
+    fn default() -> Self {
+        ChannelParameters {
+            noise_voltage: 0.0,
+            frequency_offset: 0.0,
+            epsilon: 1.0,
+            taps: vec![Complex32::new(1.0, 0.0)],
+            noise_seed: 0,
+        }
+    }
+}
+
+impl ChannelParameters {
+    /// Noise voltage giving the SNR in dB for a signal of unit power
+    pub fn noise_voltage_for_snr(snr_db: f32) -> f32 {
+        10f32.powf(-snr_db / 20.0)
+    }
+}
+
+/// State of the impairments of a [`ChannelModel`]
+struct Channel {
+    parameters: ChannelParameters,
+    /// Position of the next output sample between the previous input sample and the next one
+    mu: f64,
+    previous: Complex32,
+    /// Last input samples of the multipath FIR, most recent first
+    history: Vec<Complex32>,
+    phase: f64,
+    noise: NoiseGenerator,
+}
+
+impl Channel {
+    /// Next output sample, interpolated between the previous input sample and `x`,
+    /// or `None` once the output position has passed `x`, which is then consumed.
+    /// `mu` is kept between calls, so that an input sample may give its outputs
+    /// over several calls to `work`, whatever the number of them.
+    fn resample(&mut self, x: Complex32) -> Option<Complex32> {
+        if self.mu < 1.0 {
+            let sample = self.previous + (x - self.previous) * self.mu as f32;
+            self.mu += self.parameters.epsilon;
+            Some(sample)
+        } else {
+            self.mu -= 1.0;
+            self.previous = x;
+            None
+        }
+    }
+
+    /// Multipath, frequency offset and noise
+    fn impair(&mut self, sample: Complex32) -> Complex32 {
+        self.history.rotate_right(1);
+        self.history[0] = sample;
+        let mut v: Complex32 = self
+            .parameters
+            .taps
+            .iter()
+            .zip(self.history.iter())
+            .map(|(tap, x)| tap * x)
+            .sum();
+        if self.parameters.frequency_offset != 0.0 {
+            v *= Complex32::from_polar(1.0, self.phase as f32);
+            self.phase = (self.phase + TAU * self.parameters.frequency_offset).rem_euclid(TAU);
+        }
+        if self.parameters.noise_voltage != 0.0 {
+            v += Complex32::noise(&mut self.noise, self.parameters.noise_voltage);
+        }
+        v
+    }
+}
+
+/// Simulate a transmission channel: sample timing offset (by linear interpolation),
+/// multipath, frequency offset then additive white gaussian noise.
+#[derive(Block)]
+pub struct ChannelModel<
+    I: CpuBufferReader<Item = Complex32> = DefaultCpuReader<Complex32>,
+    O: CpuBufferWriter<Item = Complex32> = DefaultCpuWriter<Complex32>,
+> {
+    channel: Channel,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<I, O> ChannelModel<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    pub fn new(parameters: ChannelParameters) -> Result<Self> {
+        if parameters.epsilon <= 0.0 {
+            bail!("epsilon must be positive, not {}", parameters.epsilon);
+        }
+        let taps = parameters.taps.len().max(1);
+        Ok(Self {
+            channel: Channel {
+                noise: NoiseGenerator::new(parameters.noise_seed, NoiseType::Gaussian),
+                parameters,
+                mu: 0.0,
+                previous: Complex32::new(0.0, 0.0),
+                history: vec![Complex32::new(0.0, 0.0); taps],
+                phase: 0.0,
+            },
+            input: I::default(),
+            output: O::default(),
+        })
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for ChannelModel<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let mut consumed = 0;
+        let mut produced = 0;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            ilen = i.len();
+            let resampled = self.channel.parameters.epsilon != 1.0;
+            while consumed < ilen && produced < o.len() {
+                let sample = if resampled {
+                    match self.channel.resample(i[consumed]) {
+                        Some(sample) => sample,
+                        None => {
+                            consumed += 1;
+                            continue;
+                        }
+                    }
+                } else {
+                    let sample = i[consumed];
+                    consumed += 1;
+                    sample
+                };
+                o[produced] = self.channel.impair(sample);
+                produced += 1;
+            }
+        }
+
+        self.input.consume(consumed);
+        self.output.produce(produced);
+
+        if self.input.finished() && consumed == ilen {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// Sum of sinusoids generating the gain of a [`FadingModel`]
+struct Fader {
+    /// Doppler angular frequency per sample
+    doppler: f64,
+    /// `(cos(alpha), sin(alpha), phase_i, phase_q)` of each sinusoid
+    sinusoids: Vec<(f64, f64, f64, f64)>,
+    /// Rician factor, 0 for Rayleigh fading
+    k: f64,
+    los_phase: f64,
+    /// Sample index
+    n: u64,
+}
+
+impl Fader {
+    fn next_gain(&mut self) -> Complex32 {
+        let t = self.doppler * self.n as f64;
+        self.n += 1;
+        let (re, im) =
+            self.sinusoids
+                .iter()
+                .fold((0.0, 0.0), |(re, im), (cos, sin, phase_i, phase_q)| {
+                    (
+                        re + (t * cos + phase_i).cos(),
+                        im + (t * sin + phase_q).cos(),
+                    )
+                });
+        let scale = (1.0 / self.sinusoids.len() as f64).sqrt();
+        let mut gain = Complex32::new((re * scale) as f32, (im * scale) as f32);
+        if self.k > 0.0 {
+            let los = Complex32::from_polar(self.k.sqrt() as f32, (t + self.los_phase) as f32);
+            gain = (gain + los) / ((1.0 + self.k).sqrt() as f32);
+        }
+        gain
+    }
+}
+
+/// Flat fading, like GNU Radio's `channels.fading_model`: the signal is multiplied
+/// by a Rayleigh (or Rician with a line of sight) process of unit average power,
+/// generated by a sum of sinusoids.
+#[derive(Block)]
+pub struct FadingModel<
+    I: CpuBufferReader<Item = Complex32> = DefaultCpuReader<Complex32>,
+    O: CpuBufferWriter<Item = Complex32> = DefaultCpuWriter<Complex32>,
+> {
+    fader: Fader,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<I, O> FadingModel<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    /// `sinusoids` is the number of sinusoids (8 is enough),
+    /// `doppler` the maximum Doppler frequency normalized to the sample rate,
+    /// `k` the Rician factor, 0 for no line of sight.
+    pub fn new(sinusoids: usize, doppler: f64, k: f64, seed: u64) -> Self {
+        let count = sinusoids.max(1);
+        let mut random = NoiseGenerator::new(seed, NoiseType::Uniform);
+        let mut angle = || PI * random.next_f32() as f64;
+        let theta = angle();
+        let sinusoids = (1..=count)
+            .map(|i| {
+                let alpha = (TAU * i as f64 - PI + theta) / (4.0 * count as f64);
+                (alpha.cos(), alpha.sin(), angle(), angle())
+            })
+            .collect();
+        Self {
+            fader: Fader {
+                doppler: TAU * doppler,
+                sinusoids,
+                k: k.max(0.0),
+                los_phase: angle(),
+                n: 0,
+            },
+            input: I::default(),
+            output: O::default(),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for FadingModel<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let m;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            ilen = i.len();
+            m = std::cmp::min(ilen, o.len());
+            for (x, y) in i.iter().zip(o.iter_mut()).take(m) {
+                *y = x * self.fader.next_gain();
+            }
+        }
+
+        self.input.consume(m);
+        self.output.produce(m);
+
+        if self.input.finished() && m == ilen {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use anyhow::Result;
+use futuresdr::futuredsp::firdes;
+use futuresdr::num_complex::Complex32;
+use futuresdr::prelude::*;
+use futuresdr::runtime::Pmt;
+use std::f64::consts::TAU;
+
+/// Complex band-pass FIR filter, just like csdr `bandpass_fir_fft_cc`.
+///
+/// Taps are a low-pass of half the band width shifted to the center of the band,
+/// hence `low_cut` and `high_cut` (relative to the sample rate) may be negative.
+/// The band can be changed at runtime by posting `Pmt::VecF32(vec![low_cut, high_cut])`
+/// on the `cutoff` message input. The number of taps is fixed by the window.
+#[derive(Block)]
+#[message_inputs(cutoff)]
+pub struct ComplexBandPass<
+    I: CpuBufferReader<Item = Complex32> = DefaultCpuReader<Complex32>,
+    O: CpuBufferWriter<Item = Complex32> = DefaultCpuWriter<Complex32>,
+> {
+    window: Vec<f64>,
+    /// Taps in reverse order to ease the convolution
+    taps: Vec<Complex32>,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<I, O> ComplexBandPass<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    pub fn new(low_cut: f64, high_cut: f64, window: Vec<f64>) -> Self {
+        let taps = Self::design(low_cut, high_cut, &window);
+        Self {
+            window,
+            taps,
+            input: I::default(),
+            output: O::default(),
+        }
+    }
+
+    fn design(low_cut: f64, high_cut: f64, window: &[f64]) -> Vec<Complex32> {
+        let half_bandwidth = ((high_cut - low_cut) / 2.0).abs();
+        let center = (low_cut + high_cut) / 2.0;
+        let lowpass = firdes::lowpass::<f32>(half_bandwidth, window);
+        let middle = (lowpass.len() / 2) as f64;
+        lowpass
+            .iter()
+            .enumerate()
+            .map(|(n, tap)| {
+                let phase = TAU * center * (n as f64 - middle);
+                Complex32::from_polar(*tap, phase as f32)
+            })
+            .rev()
+            .collect()
+    }
+
+    async fn cutoff(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        let (low_cut, high_cut) = match p {
+            Pmt::VecF32(v) if v.len() == 2 => (v[0] as f64, v[1] as f64),
+            _ => return Ok(Pmt::InvalidValue),
+        };
+        self.taps = Self::design(low_cut, high_cut, &self.window);
+        Ok(Pmt::Ok)
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for ComplexBandPass<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let ntaps = self.taps.len();
+        let m;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            ilen = i.len();
+            m = std::cmp::min(ilen.saturating_sub(ntaps - 1), o.len());
+
+            for (k, r) in o.iter_mut().take(m).enumerate() {
+                *r = i[k..k + ntaps]
+                    .iter()
+                    .zip(self.taps.iter())
+                    .map(|(x, t)| x * t)
+                    .sum();
+            }
+        }
+
+        self.input.consume(m);
+        self.output.produce(m);
+
+        if self.input.finished() && ilen - m < ntaps {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
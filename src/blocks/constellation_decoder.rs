@@ -0,0 +1,131 @@
+use super::modulators::{Constellation, DigitalConstellation};
+use anyhow::Result;
+use futuresdr::num_complex::Complex32;
+use futuresdr::prelude::*;
+
+/// Hard decision: the symbol of the nearest point of the constellation,
+/// like GNU Radio's `constellation_decoder_cb`.
+#[derive(Block)]
+pub struct ConstellationDecoder<
+    I: CpuBufferReader<Item = Complex32> = DefaultCpuReader<Complex32>,
+    O: CpuBufferWriter<Item = u8> = DefaultCpuWriter<u8>,
+> {
+    constellation: DigitalConstellation,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<I, O> ConstellationDecoder<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = u8>,
+{
+    pub fn new(constellation: DigitalConstellation) -> Self {
+        Self {
+            constellation,
+            input: I::default(),
+            output: O::default(),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for ConstellationDecoder<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = u8>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let m;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            ilen = i.len();
+            m = std::cmp::min(ilen, o.len());
+            for (x, y) in i.iter().zip(o.iter_mut()).take(m) {
+                *y = self.constellation.decision_maker(*x) as u8;
+            }
+        }
+
+        self.input.consume(m);
+        self.output.produce(m);
+
+        if self.input.finished() && m == ilen {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// Soft decision: for each symbol, one value per bit, most significant first,
+/// positive for a 1, like GNU Radio's `constellation_soft_decoder_cf`.
+#[derive(Block)]
+pub struct ConstellationSoftDecoder<
+    I: CpuBufferReader<Item = Complex32> = DefaultCpuReader<Complex32>,
+    O: CpuBufferWriter<Item = f32> = DefaultCpuWriter<f32>,
+> {
+    constellation: DigitalConstellation,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<I, O> ConstellationSoftDecoder<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = f32>,
+{
+    pub fn new(constellation: DigitalConstellation) -> Self {
+        Self {
+            constellation,
+            input: I::default(),
+            output: O::default(),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for ConstellationSoftDecoder<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = f32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let bits = self.constellation.bits_per_symbol();
+        let m;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            ilen = i.len();
+            m = std::cmp::min(ilen, o.len() / bits);
+            for (x, y) in i.iter().zip(o.chunks_exact_mut(bits)).take(m) {
+                y.copy_from_slice(&self.constellation.soft_decision_maker(*x));
+            }
+        }
+
+        self.input.consume(m);
+        self.output.produce(m * bits);
+
+        if self.input.finished() && m == ilen {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
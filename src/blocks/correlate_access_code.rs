@@ -0,0 +1,262 @@
+use super::pmt_as_f64;
+use anyhow::{bail, Context, Result};
+use futuresdr::prelude::*;
+use futuresdr::runtime::Pmt;
+
+/// Sync word of up to 64 bits, the first one received being the most significant
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AccessCode {
+    code: u64,
+    len: usize,
+}
+
+impl AccessCode {
+    /// One bit per item, only the least significant bit of each being used
+    pub fn from_bits(bits: &[u8]) -> Result<AccessCode> {
+        if bits.is_empty() || bits.len() > 64 {
+            bail!("access code: 1 to 64 bits expected, not {}", bits.len());
+        }
+        let code = bits
+            .iter()
+            .fold(0u64, |code, bit| (code << 1) | (*bit & 1) as u64);
+        Ok(AccessCode {
+            code,
+            len: bits.len(),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.len)
+    }
+}
+
+impl TryFrom<&str> for AccessCode {
+    type Error = anyhow::Error;
+
+    /// Hexadecimal like `0x1ACFFC1D` (4 bits per digit) or a string of bits like `01111110`,
+    /// as in GNU Radio
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        let value = value.trim().trim_matches(['"', '\'']);
+        let bits: Vec<u8> = if let Some(hex) = value
+            .strip_prefix("0x")
+            .or_else(|| value.strip_prefix("0X"))
+        {
+            let mut bits = Vec::with_capacity(4 * hex.len());
+            for digit in hex.chars() {
+                let digit = digit
+                    .to_digit(16)
+                    .with_context(|| format!("access code: invalid hexadecimal {value}"))?;
+                bits.extend((0..4).rev().map(|i| ((digit >> i) & 1) as u8));
+            }
+            bits
+        } else {
+            value
+                .chars()
+                .filter(|c| *c != '_')
+                .map(|c| match c {
+                    '0' => Ok(0),
+                    '1' => Ok(1),
+                    _ => bail!("access code: invalid bit string {value}"),
+                })
+                .collect::<Result<_>>()?
+        };
+        AccessCode::from_bits(&bits)
+    }
+}
+
+/// Sliding comparison of the received bits with an [`AccessCode`]
+#[derive(Clone, Debug)]
+pub struct Correlator {
+    access_code: AccessCode,
+    /// Maximum number of bit errors
+    threshold: u32,
+    /// Also detect the access code with all its bits inverted
+    invert: bool,
+    register: u64,
+    /// Number of bits in the register, up to the length of the access code
+    received: usize,
+}
+
+impl Correlator {
+    pub fn new(access_code: AccessCode, threshold: u32, invert: bool) -> Correlator {
+        Correlator {
+            access_code,
+            threshold,
+            invert,
+            register: 0,
+            received: 0,
+        }
+    }
+
+    /// Shift in a bit, returning whether the access code has just been received
+    /// and if so, whether it was inverted
+    pub fn push(&mut self, bit: u8) -> Option<bool> {
+        self.register = (self.register << 1) | (bit & 1) as u64;
+        self.received = (self.received + 1).min(self.access_code.len);
+        if self.received < self.access_code.len {
+            return None;
+        }
+        let errors =
+            ((self.register ^ self.access_code.code) & self.access_code.mask()).count_ones();
+        if errors <= self.threshold {
+            Some(false)
+        } else if self.invert && self.access_code.len as u32 - errors <= self.threshold {
+            Some(true)
+        } else {
+            None
+        }
+    }
+
+    /// Forget the bits received so far
+    pub fn reset(&mut self) {
+        self.register = 0;
+        self.received = 0;
+    }
+}
+
+/// What a [`CorrelateAccessCode`] outputs
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CorrelationOutput {
+    /// All the bits, like GNU Radio's `correlate_access_code_bb`: bit 1 is set on the
+    /// first bit following an access code, and bit 2 too when it was inverted
+    Flag,
+    /// Only the given number of bits following each access code,
+    /// themselves inverted when the access code was
+    Frame(usize),
+}
+
+/// Flag on the first bit following an access code
+pub const ACCESS_CODE_FLAG: u8 = 0x02;
+/// Flag on the first bit following an inverted access code, with [`ACCESS_CODE_FLAG`]
+pub const INVERTED_ACCESS_CODE_FLAG: u8 = 0x04;
+
+/// Search a sync word in a stream of unpacked bits, allowing for bit errors,
+/// then either flag or extract the frames following it.
+#[derive(Block)]
+#[message_inputs(values_after)]
+pub struct CorrelateAccessCode<
+    I: CpuBufferReader<Item = u8> = DefaultCpuReader<u8>,
+    O: CpuBufferWriter<Item = u8> = DefaultCpuWriter<u8>,
+> {
+    correlator: Correlator,
+    mode: CorrelationOutput,
+    /// Flags of the next output bit
+    flags: u8,
+    /// Bits of the current frame still to output, and whether they are inverted
+    frame: Option<(usize, bool)>,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<I, O> CorrelateAccessCode<I, O>
+where
+    I: CpuBufferReader<Item = u8>,
+    O: CpuBufferWriter<Item = u8>,
+{
+    pub fn new(correlator: Correlator, mode: CorrelationOutput) -> Self {
+        Self {
+            correlator,
+            mode,
+            flags: 0,
+            frame: None,
+            input: I::default(),
+            output: O::default(),
+        }
+    }
+
+    /// Change the length of next frames, in [`CorrelationOutput::Frame`] mode
+    async fn values_after(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match (self.mode, pmt_as_f64(&p)) {
+            (CorrelationOutput::Frame(_), Some(values_after)) if values_after >= 0.0 => {
+                self.mode = CorrelationOutput::Frame(values_after as usize);
+                Ok(Pmt::Ok)
+            }
+            _ => Ok(Pmt::InvalidValue),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for CorrelateAccessCode<I, O>
+where
+    I: CpuBufferReader<Item = u8>,
+    O: CpuBufferWriter<Item = u8>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let mut consumed = 0;
+        let mut produced = 0;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            ilen = i.len();
+            while consumed < ilen {
+                let bit = i[consumed] & 1;
+                match (self.mode, &mut self.frame) {
+                    (CorrelationOutput::Flag, _) => {
+                        if produced == o.len() {
+                            break;
+                        }
+                        o[produced] = bit | self.flags;
+                        produced += 1;
+                        self.flags = match self.correlator.push(bit) {
+                            Some(false) => ACCESS_CODE_FLAG,
+                            Some(true) => ACCESS_CODE_FLAG | INVERTED_ACCESS_CODE_FLAG,
+                            None => 0,
+                        };
+                    }
+                    (CorrelationOutput::Frame(_), Some((remaining, inverted))) => {
+                        if produced == o.len() {
+                            break;
+                        }
+                        o[produced] = bit ^ *inverted as u8;
+                        produced += 1;
+                        *remaining -= 1;
+                        if *remaining == 0 {
+                            self.frame = None;
+                        }
+                    }
+                    (CorrelationOutput::Frame(len), None) => {
+                        if let Some(inverted) = self.correlator.push(bit) {
+                            self.correlator.reset();
+                            if len > 0 {
+                                self.frame = Some((len, inverted));
+                            }
+                        }
+                    }
+                }
+                consumed += 1;
+            }
+        }
+
+        self.input.consume(consumed);
+        self.output.produce(produced);
+
+        if self.input.finished() && consumed == ilen {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::{Context, Result};
+use futures::channel::mpsc;
+use futuresdr::prelude::*;
+use futuresdr::runtime::Pmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::thread;
+
+/// Read control values from a file (usually a named pipe), just like csdr `--fifo` option.
+///
+/// Each line holds whitespace separated numbers. A line with a single number is posted
+/// as `Pmt::F32` on the `out` message output, otherwise as `Pmt::VecF32`.
+/// Lines that cannot be parsed are ignored.
+///
+/// The `input` is fed with the stream of the controlled block, whose items are only
+/// consumed, so that the reader finishes with that stream even if the file is still open.
+#[derive(Block)]
+#[message_outputs(out)]
+pub struct FifoReader<T: CpuSample, I: CpuBufferReader<Item = T> = DefaultCpuReader<T>> {
+    rx: mpsc::UnboundedReceiver<Vec<f32>>,
+    #[input]
+    input: I,
+}
+
+impl<T, I> FifoReader<T, I>
+where
+    T: CpuSample,
+    I: CpuBufferReader<Item = T>,
+{
+    pub fn new(path: &str) -> Result<Self> {
+        let file = open(path).with_context(|| format!("fifo_reader: cannot open {path}"))?;
+        let (tx, rx) = mpsc::unbounded::<Vec<f32>>();
+
+        thread::spawn(move || {
+            for line in BufReader::new(file).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                let values: Result<Vec<f32>, _> =
+                    line.split_whitespace().map(str::parse::<f32>).collect();
+                match values {
+                    Ok(values) if !values.is_empty() => {
+                        if tx.unbounded_send(values).is_err() {
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        });
+
+        Ok(Self {
+            rx,
+            input: I::default(),
+        })
+    }
+}
+
+/// Open `path` for reading. A named pipe is also opened for writing: opening it does
+/// not wait for a writer, and it does not reach its end each time a writer closes it.
+#[cfg(unix)]
+fn open(path: &str) -> std::io::Result<File> {
+    use std::os::unix::fs::FileTypeExt;
+    let is_fifo = std::fs::metadata(path)?.file_type().is_fifo();
+    std::fs::OpenOptions::new()
+        .read(true)
+        .write(is_fifo)
+        .open(path)
+}
+
+#[cfg(not(unix))]
+fn open(path: &str) -> std::io::Result<File> {
+    File::open(path)
+}
+
+#[doc(hidden)]
+impl<T, I> Kernel for FifoReader<T, I>
+where
+    T: CpuSample,
+    I: CpuBufferReader<Item = T>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        while let Ok(Some(values)) = self.rx.try_next() {
+            let p = if values.len() == 1 {
+                Pmt::F32(values[0])
+            } else {
+                Pmt::VecF32(values)
+            };
+            mio.post("out", p).await?;
+        }
+
+        let n = self.input.slice().len();
+        self.input.consume(n);
+        if self.input.finished() {
+            io.finished = true;
+        }
+        Ok(())
+    }
+}
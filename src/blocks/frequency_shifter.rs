@@ -0,0 +1,93 @@
+use super::pmt_as_f64;
+use anyhow::Result;
+use futuresdr::num_complex::Complex32;
+use futuresdr::prelude::*;
+use futuresdr::runtime::Pmt;
+use std::f64::consts::TAU;
+
+/// Shift the frequency of a complex stream by `freq` (in the same unit as `sample_rate`).
+///
+/// The frequency can be changed at runtime by posting a numerical `Pmt`
+/// on the `freq` message input, eg from a [`super::FifoReader`].
+#[derive(Block)]
+#[message_inputs(freq)]
+pub struct FrequencyShifter<
+    I: CpuBufferReader<Item = Complex32> = DefaultCpuReader<Complex32>,
+    O: CpuBufferWriter<Item = Complex32> = DefaultCpuWriter<Complex32>,
+> {
+    sample_rate: f64,
+    phase_inc: f64,
+    phase: f64,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<I, O> FrequencyShifter<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    pub fn new(freq: f32, sample_rate: f32) -> Self {
+        let sample_rate = sample_rate as f64;
+        Self {
+            sample_rate,
+            phase_inc: TAU * (freq as f64) / sample_rate,
+            phase: 0.0,
+            input: I::default(),
+            output: O::default(),
+        }
+    }
+
+    async fn freq(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        let Some(freq) = pmt_as_f64(&p) else {
+            return Ok(Pmt::InvalidValue);
+        };
+        self.phase_inc = TAU * freq / self.sample_rate;
+        Ok(Pmt::Ok)
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for FrequencyShifter<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let m;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            ilen = i.len();
+            m = std::cmp::min(ilen, o.len());
+
+            for (v, r) in i.iter().zip(o.iter_mut()).take(m) {
+                *r = v * Complex32::from_polar(1.0, self.phase as f32);
+                self.phase = (self.phase + self.phase_inc).rem_euclid(TAU);
+            }
+        }
+
+        self.input.consume(m);
+        self.output.produce(m);
+
+        if self.input.finished() && m == ilen {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use super::pmt_as_f64;
+use anyhow::Result;
+use futuresdr::prelude::*;
+use futuresdr::runtime::Pmt;
+
+/// Multiply a float stream by a constant that can be changed at runtime
+/// through the `gain` message input.
+#[derive(Block)]
+#[message_inputs(gain)]
+pub struct Gain<
+    I: CpuBufferReader<Item = f32> = DefaultCpuReader<f32>,
+    O: CpuBufferWriter<Item = f32> = DefaultCpuWriter<f32>,
+> {
+    gain: f32,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<I, O> Gain<I, O>
+where
+    I: CpuBufferReader<Item = f32>,
+    O: CpuBufferWriter<Item = f32>,
+{
+    pub fn new(gain: f32) -> Self {
+        Self {
+            gain,
+            input: I::default(),
+            output: O::default(),
+        }
+    }
+
+    async fn gain(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match pmt_as_f64(&p) {
+            Some(gain) => {
+                self.gain = gain as f32;
+                Ok(Pmt::Ok)
+            }
+            None => Ok(Pmt::InvalidValue),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for Gain<I, O>
+where
+    I: CpuBufferReader<Item = f32>,
+    O: CpuBufferWriter<Item = f32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let m;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            ilen = i.len();
+            m = std::cmp::min(ilen, o.len());
+
+            for (v, r) in i.iter().zip(o.iter_mut()).take(m) {
+                *r = v * self.gain;
+            }
+        }
+
+        self.input.consume(m);
+        self.output.produce(m);
+
+        if self.input.finished() && m == ilen {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,328 @@
+use anyhow::Result;
+use futuresdr::prelude::*;
+use futuresdr::runtime::Pmt;
+use std::collections::VecDeque;
+
+/// Flag delimiting HDLC frames, `01111110`
+pub const HDLC_FLAG: u8 = 0x7E;
+
+/// CRC-16/X.25 (as the FCS of HDLC and AX.25): reflected polynomial 0x1021,
+/// initialized and complemented with 0xFFFF
+pub fn crc16_x25(data: &[u8]) -> u16 {
+    let crc = data.iter().fold(0xFFFFu16, |crc, byte| {
+        (0..8).fold(crc ^ *byte as u16, |crc, _| {
+            if crc & 1 == 1 {
+                (crc >> 1) ^ 0x8408
+            } else {
+                crc >> 1
+            }
+        })
+    });
+    !crc
+}
+
+/// Bits of the flag, least significant first
+pub(crate) fn flag_bits() -> impl Iterator<Item = u8> {
+    (0..8).map(|i| (HDLC_FLAG >> i) & 1)
+}
+
+/// Bits of a frame followed by its FCS, least significant first, a 0 being
+/// stuffed after five consecutive ones
+pub(crate) fn stuffed_bits(data: &[u8]) -> Vec<u8> {
+    let fcs = crc16_x25(data).to_le_bytes();
+    let mut bits = Vec::with_capacity(10 * (data.len() + 2));
+    let mut ones = 0;
+    for byte in data.iter().chain(fcs.iter()) {
+        for i in 0..8 {
+            let bit = (byte >> i) & 1;
+            bits.push(bit);
+            if bit == 1 {
+                ones += 1;
+                if ones == 5 {
+                    bits.push(0);
+                    ones = 0;
+                }
+            } else {
+                ones = 0;
+            }
+        }
+    }
+    bits
+}
+
+/// NRZI decoding: a 0 is a transition, a 1 no transition
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NrziDecoder {
+    previous: u8,
+}
+
+impl NrziDecoder {
+    pub fn decode(&mut self, bit: u8) -> u8 {
+        let decoded = (bit == self.previous) as u8;
+        self.previous = bit;
+        decoded
+    }
+}
+
+/// NRZI encoding, the inverse of [`NrziDecoder`]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NrziEncoder {
+    level: u8,
+}
+
+impl NrziEncoder {
+    pub fn encode(&mut self, bit: u8) -> u8 {
+        if bit == 0 {
+            self.level ^= 1;
+        }
+        self.level
+    }
+}
+
+/// State of a [`HdlcDeframer`]
+struct Deframer {
+    nrzi: Option<NrziDecoder>,
+    crc_check: bool,
+    /// Minimum and maximum length of a frame, in bytes with its FCS
+    length_min: usize,
+    length_max: usize,
+    /// Number of consecutive ones received
+    ones: usize,
+    /// Bits received since the last flag, `None` until a flag or after an abort
+    bits: Option<Vec<u8>>,
+}
+
+impl Deframer {
+    /// Push a bit, returning the frame it ends, if valid
+    fn push(&mut self, bit: u8) -> Option<Vec<u8>> {
+        let bit = match &mut self.nrzi {
+            Some(nrzi) => nrzi.decode(bit & 1),
+            None => bit & 1,
+        };
+        if bit == 1 {
+            self.ones += 1;
+            if self.ones > 6 {
+                // Abort
+                self.bits = None;
+            } else {
+                self.push_data(1);
+            }
+            return None;
+        }
+        match std::mem::replace(&mut self.ones, 0) {
+            // Stuffed bit
+            5 => None,
+            6 => {
+                // Flag: its first seven bits have been taken as data
+                let frame = self.bits.take().and_then(|mut bits| {
+                    bits.truncate(bits.len().saturating_sub(7));
+                    self.frame(&bits)
+                });
+                self.bits = Some(Vec::new());
+                frame
+            }
+            _ => {
+                self.push_data(0);
+                None
+            }
+        }
+    }
+
+    /// Add a bit to the current frame, dropping it when too long
+    fn push_data(&mut self, bit: u8) {
+        if let Some(bits) = &mut self.bits {
+            bits.push(bit);
+            // Room for the seven first bits of the closing flag
+            if bits.len() > 8 * self.length_max + 7 {
+                self.bits = None;
+            }
+        }
+    }
+
+    /// Bytes of the frame, least significant bit first, without its FCS
+    fn frame(&self, bits: &[u8]) -> Option<Vec<u8>> {
+        if bits.len() % 8 != 0 {
+            return None;
+        }
+        let len = bits.len() / 8;
+        if len < self.length_min.max(2) || len > self.length_max {
+            return None;
+        }
+        let mut bytes: Vec<u8> = bits
+            .chunks(8)
+            .map(|byte| {
+                byte.iter()
+                    .enumerate()
+                    .fold(0, |acc, (i, bit)| acc | (bit << i))
+            })
+            .collect();
+        let fcs = u16::from_le_bytes([bytes[len - 2], bytes[len - 1]]);
+        bytes.truncate(len - 2);
+        if self.crc_check && crc16_x25(&bytes) != fcs {
+            return None;
+        }
+        Some(bytes)
+    }
+}
+
+/// HDLC deframer, like GNU Radio's `hdlc_deframer_bp`: in a stream of bits, optionally
+/// NRZI encoded, frames between flags are destuffed and posted as PDUs, without
+/// their FCS, when their CRC-16/X.25 is valid.
+#[derive(Block)]
+#[message_outputs(pdus)]
+pub struct HdlcDeframer<I: CpuBufferReader<Item = u8> = DefaultCpuReader<u8>> {
+    deframer: Deframer,
+    #[input]
+    input: I,
+}
+
+impl<I: CpuBufferReader<Item = u8>> HdlcDeframer<I> {
+    /// `length_min` and `length_max` bound the length of a frame, in bytes with its FCS.
+    /// Without `crc_check`, the FCS is removed but not checked.
+    pub fn new(length_min: usize, length_max: usize, crc_check: bool, nrzi: bool) -> Self {
+        Self {
+            deframer: Deframer {
+                nrzi: nrzi.then(NrziDecoder::default),
+                crc_check,
+                length_min,
+                length_max,
+                ones: 0,
+                bits: None,
+            },
+            input: I::default(),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<I: CpuBufferReader<Item = u8>> Kernel for HdlcDeframer<I> {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let mut frames = Vec::new();
+        let consumed;
+        {
+            let i = self.input.slice();
+            consumed = i.len();
+            frames.extend(i.iter().filter_map(|bit| self.deframer.push(*bit)));
+        }
+
+        self.input.consume(consumed);
+        for frame in frames {
+            mio.post("pdus", Pmt::Blob(frame)).await?;
+        }
+
+        if self.input.finished() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// HDLC framer, like GNU Radio's `hdlc_framer_pb`: each PDU received gets its
+/// CRC-16/X.25 FCS, is stuffed and output as bits between flags, optionally NRZI encoded.
+#[derive(Block)]
+#[message_inputs(in_port)]
+pub struct HdlcFramer<O: CpuBufferWriter<Item = u8> = DefaultCpuWriter<u8>> {
+    /// Number of flags before each frame
+    flags: usize,
+    nrzi: Option<NrziEncoder>,
+    /// Bits not yet written to the output
+    pending: VecDeque<u8>,
+    /// No more PDUs will be received
+    finished: bool,
+    #[output]
+    output: O,
+}
+
+impl<O: CpuBufferWriter<Item = u8>> HdlcFramer<O> {
+    /// `flags` is the number of flags sent before each frame, one being sent after it
+    pub fn new(flags: usize, nrzi: bool) -> Self {
+        Self {
+            flags: flags.max(1),
+            nrzi: nrzi.then(NrziEncoder::default),
+            pending: VecDeque::new(),
+            finished: false,
+            output: O::default(),
+        }
+    }
+
+    fn push_bit(&mut self, bit: u8) {
+        let bit = match &mut self.nrzi {
+            Some(nrzi) => nrzi.encode(bit),
+            None => bit,
+        };
+        self.pending.push_back(bit);
+    }
+
+    fn push_flag(&mut self) {
+        for bit in flag_bits() {
+            self.push_bit(bit);
+        }
+    }
+
+    fn push_frame(&mut self, data: &[u8]) {
+        for _ in 0..self.flags {
+            self.push_flag();
+        }
+        for bit in stuffed_bits(data) {
+            self.push_bit(bit);
+        }
+        self.push_flag();
+    }
+
+    async fn in_port(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match p {
+            Pmt::Blob(data) => {
+                self.push_frame(&data);
+                io.call_again = true;
+                Ok(Pmt::Ok)
+            }
+            Pmt::Finished => {
+                self.finished = true;
+                io.call_again = true;
+                Ok(Pmt::Ok)
+            }
+            _ => Ok(Pmt::InvalidValue),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<O: CpuBufferWriter<Item = u8>> Kernel for HdlcFramer<O> {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let produced;
+        {
+            let o = self.output.slice();
+            produced = o.len().min(self.pending.len());
+            for (y, bit) in o.iter_mut().zip(self.pending.drain(..produced)) {
+                *y = bit;
+            }
+        }
+
+        self.output.produce(produced);
+
+        if self.pending.is_empty() && self.finished {
+            io.finished = true;
+        } else if !self.pending.is_empty() && produced > 0 {
+            io.call_again = true;
+        }
+
+        Ok(())
+    }
+}
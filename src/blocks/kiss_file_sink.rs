@@ -61,4 +61,13 @@ impl KissFileSink {
 }
 
 #[doc(hidden)]
-impl Kernel for KissFileSink {}
+impl Kernel for KissFileSink {
+    /// Called when the flowgraph terminates, eg on Ctrl-C
+    async fn deinit(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        match self.file {
+            Some(ref mut f) => f.sync_all()?,
+            None => std::io::stdout().flush()?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use anyhow::{bail, Result};
+
+/// Linear feedback shift register of GNU Radio's scramblers, as `gr::digital::lfsr`:
+/// the parity of the bits of the register selected by `mask` is fed back at bit
+/// `len`, the register being shifted right and bit 0 output.
+#[derive(Clone, Debug)]
+pub struct Lfsr {
+    mask: u64,
+    seed: u64,
+    len: u32,
+    register: u64,
+}
+
+impl Lfsr {
+    /// G3RUH scrambler of 9600 baud packet radio, `1 + x^12 + x^17`
+    pub const G3RUH: (u64, u64, u32) = (0x21, 0x0, 16);
+    /// CCSDS pseudo-randomizer, `1 + x^3 + x^5 + x^7 + x^8`, when used additively
+    pub const CCSDS: (u64, u64, u32) = (0xA9, 0xFF, 7);
+
+    pub fn new(mask: u64, seed: u64, len: u32) -> Result<Lfsr> {
+        if len > 63 {
+            bail!("lfsr: register length {len} above 63");
+        }
+        Ok(Lfsr {
+            mask,
+            seed,
+            len,
+            register: seed,
+        })
+    }
+
+    fn feedback(&self) -> u8 {
+        ((self.register & self.mask).count_ones() % 2) as u8
+    }
+
+    fn shift(&mut self, bit: u8) -> u8 {
+        let output = (self.register & 1) as u8;
+        self.register = (self.register >> 1) | ((bit as u64 & 1) << self.len);
+        output
+    }
+
+    /// Next bit of the pseudo-random sequence, for additive scrambling
+    pub fn next_bit(&mut self) -> u8 {
+        self.shift(self.feedback())
+    }
+
+    /// Multiplicative scrambling: the input is added to the feedback
+    pub fn next_bit_scramble(&mut self, bit: u8) -> u8 {
+        self.shift(self.feedback() ^ (bit & 1))
+    }
+
+    /// Inverse of [`Lfsr::next_bit_scramble`], the register being fed with the received bits
+    pub fn next_bit_descramble(&mut self, bit: u8) -> u8 {
+        let output = self.feedback() ^ (bit & 1);
+        self.shift(bit);
+        output
+    }
+
+    /// Back to the seed
+    pub fn reset(&mut self) {
+        self.register = self.seed;
+    }
+}
@@ -1,3 +1,5 @@
+pub mod pmt_value;
+pub use pmt_value::pmt_as_f64;
 pub mod modulators;
 pub mod octave_complex;
 pub mod pattern_search;
@@ -17,3 +19,45 @@ pub mod tcp_kiss_client;
 pub use tcp_kiss_client::TcpKissClient;
 pub mod fixedlen_to_pdu;
 pub use fixedlen_to_pdu::FixedlenToPdu;
+pub mod frequency_shifter;
+pub use frequency_shifter::FrequencyShifter;
+pub mod complex_bandpass;
+pub use complex_bandpass::ComplexBandPass;
+pub mod fifo_reader;
+pub use fifo_reader::FifoReader;
+pub mod weaver;
+pub use weaver::{Sideband, Weaver};
+pub mod gain;
+pub use gain::Gain;
+pub mod power_squelch;
+pub use power_squelch::{PowerSquelch, SquelchSample};
+pub mod probe;
+pub use probe::{Probe, ProbeCounters};
+pub mod progress_meter;
+pub use progress_meter::ProgressMeter;
+pub mod noise_source;
+pub use noise_source::{NoiseGenerator, NoiseSample, NoiseSource, NoiseType};
+pub mod repeat_source;
+pub use repeat_source::RepeatSource;
+pub mod signal_source;
+pub use signal_source::{ChirpSource, SignalSample, SignalSource, Waveform};
+pub mod channel_model;
+pub use channel_model::{ChannelModel, ChannelParameters, FadingModel};
+pub mod constellation_decoder;
+pub use constellation_decoder::{ConstellationDecoder, ConstellationSoftDecoder};
+pub mod afc;
+pub use afc::{Afc, AfcReport};
+pub mod correlate_access_code;
+pub use correlate_access_code::{AccessCode, CorrelateAccessCode, CorrelationOutput, Correlator};
+pub mod sync_to_pdu;
+pub use sync_to_pdu::{SyncToPdu, SyncToPduInput};
+pub mod lfsr;
+pub use lfsr::Lfsr;
+pub mod hdlc;
+pub use hdlc::{crc16_x25, HdlcDeframer, HdlcFramer};
+pub mod ax25;
+pub use ax25::{strip_kiss_command, Ax25Address, Ax25Format, Ax25Frame, Ax25Print};
+pub mod afsk;
+pub use afsk::{AfskDemodulator, AfskModulator};
+pub mod repack_bits;
+pub use repack_bits::{Endianness, RepackBits};
@@ -1,7 +1,173 @@
+use anyhow::{bail, Result};
 use futuresdr::num_complex::Complex32;
+use std::f32::consts::{FRAC_1_SQRT_2, PI};
 
 pub trait Constellation {
     fn decision_maker(&self, x: Complex32) -> usize;
     fn map_to_points(&self, index: usize, tgt: &mut [Complex32]);
     fn dimensionality(&self) -> usize;
+    fn bits_per_symbol(&self) -> usize;
+    /// One value per bit of the symbol, most significant first,
+    /// positive for a 1 and the larger the more likely.
+    fn soft_decision_maker(&self, x: Complex32) -> Vec<f32>;
+}
+
+/// Gray code of `value`: consecutive values differ by a single bit
+pub fn gray_code(value: usize) -> usize {
+    value ^ (value >> 1)
+}
+
+/// Constellation of one complex point per symbol, like GNU Radio's
+/// `constellation_calcdist`: `points[symbol]` is the point sent for `symbol`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DigitalConstellation {
+    points: Vec<Complex32>,
+    bits_per_symbol: usize,
+}
+
+impl DigitalConstellation {
+    /// The number of points must be a power of two
+    pub fn custom(points: Vec<Complex32>) -> Result<DigitalConstellation> {
+        if points.len() < 2 || !points.len().is_power_of_two() {
+            bail!(
+                "constellation: the number of points must be a power of two, not {}",
+                points.len()
+            );
+        }
+        let bits_per_symbol = points.len().trailing_zeros() as usize;
+        Ok(DigitalConstellation {
+            points,
+            bits_per_symbol,
+        })
+    }
+
+    /// 0 is -1, 1 is +1
+    pub fn bpsk() -> DigitalConstellation {
+        DigitalConstellation {
+            points: vec![Complex32::new(-1.0, 0.0), Complex32::new(1.0, 0.0)],
+            bits_per_symbol: 1,
+        }
+    }
+
+    /// The least significant bit gives the sign of I, the other one the sign of Q
+    pub fn qpsk() -> DigitalConstellation {
+        let points = (0..4)
+            .map(|symbol| {
+                let sign = |bit: usize| if symbol & bit == 0 { -1.0 } else { 1.0 };
+                Complex32::new(sign(1), sign(2)) * FRAC_1_SQRT_2
+            })
+            .collect();
+        DigitalConstellation {
+            points,
+            bits_per_symbol: 2,
+        }
+    }
+
+    /// Gray coded around the unit circle, starting from 1 for 0
+    pub fn psk8() -> DigitalConstellation {
+        let mut points = vec![Complex32::default(); 8];
+        for k in 0..8 {
+            points[gray_code(k)] = Complex32::from_polar(1.0, k as f32 * PI / 4.0);
+        }
+        DigitalConstellation {
+            points,
+            bits_per_symbol: 3,
+        }
+    }
+
+    /// Gray coded on each axis, the two most significant bits giving I,
+    /// normalized to a unit average power
+    pub fn qam16() -> DigitalConstellation {
+        let levels = [-3.0, -1.0, 1.0, 3.0];
+        let mut points = vec![Complex32::default(); 16];
+        for (i, re) in levels.iter().enumerate() {
+            for (q, im) in levels.iter().enumerate() {
+                let symbol = (gray_code(i) << 2) | gray_code(q);
+                points[symbol] = Complex32::new(*re, *im) / 10f32.sqrt();
+            }
+        }
+        DigitalConstellation {
+            points,
+            bits_per_symbol: 4,
+        }
+    }
+
+    pub fn points(&self) -> &[Complex32] {
+        &self.points
+    }
+
+    fn distances(&self, x: Complex32) -> impl Iterator<Item = (usize, f32)> + '_ {
+        self.points
+            .iter()
+            .enumerate()
+            .map(move |(symbol, point)| (symbol, (x - point).norm_sqr()))
+    }
+}
+
+impl TryFrom<&str> for DigitalConstellation {
+    type Error = anyhow::Error;
+
+    /// Name of a constellation, as in GNU Radio (eg `digital.constellation_qpsk().base()`) or not (eg `qpsk`)
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        let name = value
+            .trim()
+            .trim_start_matches("digital.")
+            .trim_start_matches("constellation_")
+            .trim_end_matches(".base()")
+            .trim_end_matches("()");
+        match name {
+            "bpsk" => Ok(DigitalConstellation::bpsk()),
+            "qpsk" => Ok(DigitalConstellation::qpsk()),
+            "8psk" | "psk8" => Ok(DigitalConstellation::psk8()),
+            "16qam" | "qam16" => Ok(DigitalConstellation::qam16()),
+            _ => bail!("Unknown constellation: {value}"),
+        }
+    }
+}
+
+impl Constellation for DigitalConstellation {
+    /// Symbol of the nearest point
+    fn decision_maker(&self, x: Complex32) -> usize {
+        self.distances(x)
+            .fold((0, f32::INFINITY), |nearest, candidate| {
+                if candidate.1 < nearest.1 {
+                    candidate
+                } else {
+                    nearest
+                }
+            })
+            .0
+    }
+
+    fn map_to_points(&self, index: usize, tgt: &mut [Complex32]) {
+        tgt[0] = self.points[index];
+    }
+
+    fn dimensionality(&self) -> usize {
+        1
+    }
+
+    fn bits_per_symbol(&self) -> usize {
+        self.bits_per_symbol
+    }
+
+    /// Max-log approximation of the log likelihood ratio of each bit:
+    /// distance to the nearest point whose bit is 0 minus distance to the
+    /// nearest one whose bit is 1.
+    fn soft_decision_maker(&self, x: Complex32) -> Vec<f32> {
+        (0..self.bits_per_symbol)
+            .rev()
+            .map(|bit| {
+                let (mut zero, mut one) = (f32::INFINITY, f32::INFINITY);
+                for (symbol, distance) in self.distances(x) {
+                    if (symbol >> bit) & 1 == 0 {
+                        zero = zero.min(distance);
+                    } else {
+                        one = one.min(distance);
+                    }
+                }
+                zero - one
+            })
+            .collect()
+    }
 }
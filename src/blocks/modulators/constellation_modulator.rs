@@ -0,0 +1,218 @@
+use super::constellation::gray_code;
+use super::root_raised_cosine::root_raised_cosine;
+use super::{Constellation, DigitalConstellation};
+use anyhow::{bail, Result};
+use futuresdr::num_complex::Complex32;
+use futuresdr::prelude::*;
+use std::collections::VecDeque;
+
+/// Length of the pulse shaping filter, in symbols
+const FILTER_SPAN: usize = 11;
+
+/// Phase rotations of a differential PSK
+struct Differential {
+    /// Points ordered by phase
+    circle: Vec<Complex32>,
+    /// Position on the circle of the last point sent
+    position: usize,
+}
+
+/// Symbols to baseband samples
+struct Modulator {
+    constellation: DigitalConstellation,
+    differential: Option<Differential>,
+    /// Bits not yet made into a symbol, the oldest being the most significant
+    bits: u32,
+    bit_count: usize,
+    taps: Vec<f32>,
+    sps: usize,
+    /// Last symbols sent, most recent first, as many as the filter spans
+    history: VecDeque<Complex32>,
+    /// Samples not yet written to the output
+    pending: VecDeque<Complex32>,
+}
+
+impl Modulator {
+    fn point(&mut self, symbol: usize) -> Complex32 {
+        match &mut self.differential {
+            // The symbol is the Gray code of the number of steps to rotate by
+            Some(differential) => {
+                let steps = (0..differential.circle.len())
+                    .find(|steps| gray_code(*steps) == symbol)
+                    .unwrap_or_default();
+                differential.position = (differential.position + steps) % differential.circle.len();
+                differential.circle[differential.position]
+            }
+            None => {
+                let mut point = [Complex32::default()];
+                self.constellation.map_to_points(symbol, &mut point);
+                point[0]
+            }
+        }
+    }
+
+    /// Interpolate by the pulse shaping filter
+    fn shape(&mut self, point: Complex32) {
+        self.history.pop_back();
+        self.history.push_front(point);
+        for phase in 0..self.sps {
+            let sample = self
+                .history
+                .iter()
+                .zip(self.taps.iter().skip(phase).step_by(self.sps))
+                .map(|(symbol, tap)| *symbol * *tap)
+                .sum();
+            self.pending.push_back(sample);
+        }
+    }
+
+    fn push_byte(&mut self, byte: u8) {
+        let bits_per_symbol = self.constellation.bits_per_symbol();
+        self.bits = (self.bits << 8) | byte as u32;
+        self.bit_count += 8;
+        while self.bit_count >= bits_per_symbol {
+            self.bit_count -= bits_per_symbol;
+            let symbol = (self.bits >> self.bit_count) as usize & ((1 << bits_per_symbol) - 1);
+            let point = self.point(symbol);
+            self.shape(point);
+        }
+        self.bits &= (1 << self.bit_count) - 1;
+    }
+
+    /// Let the last symbols go through the filter
+    fn flush(&mut self) {
+        for _ in 1..self.history.len() {
+            self.shape(Complex32::default());
+        }
+    }
+}
+
+/// Modulator, like GNU Radio's `digital.generic_mod`: bytes are split into
+/// symbols, most significant bits first, optionally differentially encoded,
+/// mapped to the points of a constellation and shaped by a root raised cosine filter.
+#[derive(Block)]
+pub struct ConstellationModulator<
+    I: CpuBufferReader<Item = u8> = DefaultCpuReader<u8>,
+    O: CpuBufferWriter<Item = Complex32> = DefaultCpuWriter<Complex32>,
+> {
+    modulator: Modulator,
+    flushed: bool,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<I, O> ConstellationModulator<I, O>
+where
+    I: CpuBufferReader<Item = u8>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    /// `sps` samples per symbol (at least 2) and `excess_bw` roll-off of the
+    /// pulse shaping filter. Differential encoding needs a PSK constellation:
+    /// each symbol then gives the rotation from the previous point.
+    pub fn new(
+        constellation: DigitalConstellation,
+        differential: bool,
+        sps: usize,
+        excess_bw: f64,
+    ) -> Result<Self> {
+        if sps < 2 {
+            bail!("constellation_modulator: at least 2 samples per symbol are needed");
+        }
+        if excess_bw <= 0.0 || excess_bw > 1.0 {
+            bail!("constellation_modulator: excess bandwidth must be in (0, 1]");
+        }
+        let differential = if differential {
+            let points = constellation.points();
+            let radius = points[0].norm();
+            if points
+                .iter()
+                .any(|p| (p.norm() - radius).abs() > 1e-3 * radius)
+            {
+                bail!("constellation_modulator: differential encoding needs a PSK constellation");
+            }
+            let start = points[0].arg();
+            let phase = |p: &Complex32| (p.arg() - start).rem_euclid(std::f32::consts::TAU);
+            let mut circle = points.to_vec();
+            circle.sort_by(|a, b| phase(a).total_cmp(&phase(b)));
+            Some(Differential {
+                circle,
+                position: 0,
+            })
+        } else {
+            None
+        };
+        let taps = root_raised_cosine(sps as f64, sps as f64, excess_bw, FILTER_SPAN * sps);
+        let span = taps.len().div_ceil(sps);
+        Ok(Self {
+            modulator: Modulator {
+                constellation,
+                differential,
+                bits: 0,
+                bit_count: 0,
+                taps,
+                sps,
+                history: vec![Complex32::default(); span].into(),
+                pending: VecDeque::new(),
+            },
+            flushed: false,
+            input: I::default(),
+            output: O::default(),
+        })
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for ConstellationModulator<I, O>
+where
+    I: CpuBufferReader<Item = u8>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let mut consumed = 0;
+        let mut produced = 0;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            ilen = i.len();
+            loop {
+                while produced < o.len() {
+                    match self.modulator.pending.pop_front() {
+                        Some(sample) => {
+                            o[produced] = sample;
+                            produced += 1;
+                        }
+                        None => break,
+                    }
+                }
+                if produced == o.len() || consumed == ilen {
+                    break;
+                }
+                self.modulator.push_byte(i[consumed]);
+                consumed += 1;
+            }
+        }
+
+        self.input.consume(consumed);
+        self.output.produce(produced);
+
+        if self.input.finished() && consumed == ilen {
+            if !self.flushed {
+                self.modulator.flush();
+                self.flushed = true;
+                io.call_again = true;
+            } else if self.modulator.pending.is_empty() {
+                io.finished = true;
+            }
+        }
+
+        Ok(())
+    }
+}
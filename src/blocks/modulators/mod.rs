@@ -1 +1,6 @@
 pub mod constellation;
+pub use constellation::{Constellation, DigitalConstellation};
+pub mod constellation_modulator;
+pub use constellation_modulator::ConstellationModulator;
+pub mod root_raised_cosine;
+pub use root_raised_cosine::root_raised_cosine;
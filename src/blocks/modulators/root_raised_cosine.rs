@@ -0,0 +1,42 @@
+use std::f64::consts::PI;
+
+/// Root raised cosine taps, like GNU Radio's `firdes.root_raised_cosine`:
+/// `sps` samples per symbol, roll-off `excess_bw` in (0, 1],
+/// scaled so that the taps sum to `gain`.
+pub fn root_raised_cosine(gain: f64, sps: f64, excess_bw: f64, ntaps: usize) -> Vec<f32> {
+    assert!(
+        excess_bw > 0.0 && excess_bw <= 1.0,
+        "root_raised_cosine: excess bandwidth must be in (0, 1]"
+    );
+    let ntaps = ntaps | 1;
+    let alpha = excess_bw;
+    let taps: Vec<f64> = (0..ntaps)
+        .map(|i| {
+            let xindx = i as f64 - (ntaps / 2) as f64;
+            let x1 = PI * xindx / sps;
+            let x2 = 4.0 * alpha * xindx / sps;
+            let x3 = x2 * x2 - 1.0;
+            let (num, den) = if x3.abs() >= 1e-6 {
+                let num = if xindx != 0.0 {
+                    ((1.0 + alpha) * x1).cos() + ((1.0 - alpha) * x1).sin() / x2
+                } else {
+                    ((1.0 + alpha) * x1).cos() + (1.0 - alpha) * PI / (4.0 * alpha)
+                };
+                (num, x3 * PI)
+            } else {
+                if alpha == 1.0 {
+                    return -1.0;
+                }
+                let x3 = (1.0 - alpha) * x1;
+                let x2 = (1.0 + alpha) * x1;
+                let num = x2.sin() * (1.0 + alpha) * PI
+                    - x3.cos() * ((1.0 - alpha) * PI * sps) / (4.0 * alpha * xindx)
+                    + x3.sin() * sps * sps / (4.0 * alpha * xindx * xindx);
+                (num, -32.0 * PI * alpha * alpha * xindx / sps)
+            };
+            4.0 * alpha * num / den
+        })
+        .collect();
+    let scale = gain / taps.iter().sum::<f64>();
+    taps.iter().map(|tap| (tap * scale) as f32).collect()
+}
@@ -0,0 +1,176 @@
+use anyhow::{bail, Result};
+use futuresdr::num_complex::Complex32;
+use futuresdr::prelude::*;
+
+/// Distribution of the noise, like GNU Radio's `analog.noise_type_t`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NoiseType {
+    /// Uniform in [-1, 1]
+    Uniform,
+    /// Normal distribution of unit variance
+    Gaussian,
+    /// Laplace distribution of unit variance
+    Laplacian,
+    /// Mostly zeros with rare large positive spikes
+    Impulse,
+}
+
+impl TryFrom<&str> for NoiseType {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        match value {
+            "analog.GR_UNIFORM" | "uniform" => Ok(NoiseType::Uniform),
+            "analog.GR_GAUSSIAN" | "gaussian" => Ok(NoiseType::Gaussian),
+            "analog.GR_LAPLACIAN" | "laplacian" => Ok(NoiseType::Laplacian),
+            "analog.GR_IMPULSE" | "impulse" => Ok(NoiseType::Impulse),
+            _ => bail!("Unknown noise type: {value}"),
+        }
+    }
+}
+
+/// Impulse noise is zero unless the underlying exponential sample exceeds this factor, like in GNU Radio
+const IMPULSE_FACTOR: f32 = 9.0;
+
+/// Small xorshift64* pseudo random generator, good enough for test signals.
+/// The same seed always gives the same sequence.
+pub struct NoiseGenerator {
+    state: u64,
+    noise_type: NoiseType,
+}
+
+impl NoiseGenerator {
+    pub fn new(seed: u64, noise_type: NoiseType) -> NoiseGenerator {
+        NoiseGenerator {
+            state: seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1,
+            noise_type,
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Uniform in ]0, 1]
+    fn next_unit(&mut self) -> f32 {
+        ((self.next_u64() >> 40) as f32 + 1.0) / (1u64 << 24) as f32
+    }
+
+    fn next_laplacian(&mut self) -> f32 {
+        let z = -std::f32::consts::FRAC_1_SQRT_2 * self.next_unit().ln();
+        if self.next_u64() & 1 == 0 {
+            z
+        } else {
+            -z
+        }
+    }
+
+    pub fn next_f32(&mut self) -> f32 {
+        match self.noise_type {
+            NoiseType::Uniform => 2.0 * self.next_unit() - 1.0,
+            NoiseType::Gaussian => {
+                // Box-Muller
+                let r = (-2.0 * self.next_unit().ln()).sqrt();
+                r * (std::f32::consts::TAU * self.next_unit()).cos()
+            }
+            NoiseType::Laplacian => self.next_laplacian(),
+            NoiseType::Impulse => {
+                let z = -std::f32::consts::SQRT_2 * self.next_unit().ln();
+                if z.abs() <= IMPULSE_FACTOR {
+                    0.0
+                } else {
+                    z
+                }
+            }
+        }
+    }
+
+    pub fn noise_type(&self) -> NoiseType {
+        self.noise_type
+    }
+}
+
+/// Item types a noise source can produce
+pub trait NoiseSample: CpuSample {
+    fn noise(generator: &mut NoiseGenerator, amplitude: f32) -> Self;
+}
+
+impl NoiseSample for f32 {
+    fn noise(generator: &mut NoiseGenerator, amplitude: f32) -> Self {
+        amplitude * generator.next_f32()
+    }
+}
+
+impl NoiseSample for Complex32 {
+    /// Independent I and Q, scaled so that gaussian noise has a power of `amplitude²`
+    fn noise(generator: &mut NoiseGenerator, amplitude: f32) -> Self {
+        let scale = match generator.noise_type() {
+            NoiseType::Gaussian => amplitude * std::f32::consts::FRAC_1_SQRT_2,
+            _ => amplitude,
+        };
+        let re = generator.next_f32();
+        let im = generator.next_f32();
+        Complex32::new(scale * re, scale * im)
+    }
+}
+
+impl NoiseSample for i16 {
+    fn noise(generator: &mut NoiseGenerator, amplitude: f32) -> Self {
+        (amplitude * generator.next_f32()) as i16
+    }
+}
+
+impl NoiseSample for u8 {
+    /// Not in GNU Radio: unsigned bytes centred on 127.5, like rtl_sdr samples
+    fn noise(generator: &mut NoiseGenerator, amplitude: f32) -> Self {
+        (127.5 + 127.5 * amplitude * generator.next_f32()).clamp(0.0, 255.0) as u8
+    }
+}
+
+/// Output pseudo random noise, like GNU Radio's `noise_source`.
+#[derive(Block)]
+pub struct NoiseSource<T: NoiseSample, O: CpuBufferWriter<Item = T> = DefaultCpuWriter<T>> {
+    generator: NoiseGenerator,
+    amplitude: f32,
+    #[output]
+    output: O,
+}
+
+impl<T, O> NoiseSource<T, O>
+where
+    T: NoiseSample,
+    O: CpuBufferWriter<Item = T>,
+{
+    pub fn new(noise_type: NoiseType, amplitude: f32, seed: u64) -> Self {
+        Self {
+            generator: NoiseGenerator::new(seed, noise_type),
+            amplitude,
+            output: O::default(),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<T, O> Kernel for NoiseSource<T, O>
+where
+    T: NoiseSample,
+    O: CpuBufferWriter<Item = T>,
+{
+    async fn work(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let o = self.output.slice();
+        let n = o.len();
+        for v in o.iter_mut() {
+            *v = T::noise(&mut self.generator, self.amplitude);
+        }
+        self.output.produce(n);
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
+use super::pmt_as_f64;
 use anyhow::Result;
 use futuresdr::prelude::*;
+use futuresdr::runtime::Pmt;
 use std::collections::VecDeque;
 
 #[derive(Debug)]
@@ -9,6 +11,7 @@ enum State {
 }
 
 #[derive(Block)]
+#[message_inputs(values_after)]
 pub struct PatternSearch<A>
 where
     A: Copy + Send + Sync + 'static + Default + std::fmt::Debug + PartialEq,
@@ -49,6 +52,23 @@ where
             output: Default::default(),
         }
     }
+
+    /// Change the number of items dumped after next matches
+    async fn values_after(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match pmt_as_f64(&p) {
+            Some(values_after) if values_after >= 0.0 => {
+                self.values_after = values_after as usize;
+                Ok(Pmt::Ok)
+            }
+            _ => Ok(Pmt::InvalidValue),
+        }
+    }
 }
 
 #[doc(hidden)]
@@ -0,0 +1,15 @@
+use futuresdr::runtime::Pmt;
+
+/// Numerical value of a message received on a control message input.
+/// Return `None` for non numerical messages.
+pub fn pmt_as_f64(p: &Pmt) -> Option<f64> {
+    match p {
+        Pmt::F32(v) => Some(*v as f64),
+        Pmt::F64(v) => Some(*v),
+        Pmt::U32(v) => Some(*v as f64),
+        Pmt::U64(v) => Some(*v as f64),
+        Pmt::Usize(v) => Some(*v as f64),
+        Pmt::Isize(v) => Some(*v as f64),
+        _ => None,
+    }
+}
@@ -0,0 +1,127 @@
+use super::pmt_as_f64;
+use anyhow::Result;
+use futuresdr::num_complex::Complex32;
+use futuresdr::prelude::*;
+use futuresdr::runtime::Pmt;
+
+/// Item types a [`PowerSquelch`] can mute
+pub trait SquelchSample: CpuSample + Copy + Default {
+    fn power(&self) -> f32;
+}
+
+impl SquelchSample for f32 {
+    fn power(&self) -> f32 {
+        self * self
+    }
+}
+
+impl SquelchSample for Complex32 {
+    fn power(&self) -> f32 {
+        self.norm_sqr()
+    }
+}
+
+fn db_to_power(threshold_db: f64) -> f32 {
+    10f64.powf(threshold_db / 10.0) as f32
+}
+
+/// Power squelch, like GNU Radio's `pwr_squelch_cc` and `pwr_squelch_ff`: the input
+/// power, averaged by a single pole IIR filter of `alpha`, is compared to `threshold`
+/// (in dB). Below it, the output is muted: zeros, or no item at all with `gate`.
+/// The threshold can be changed at runtime through the `threshold` message input.
+#[derive(Block)]
+#[message_inputs(threshold)]
+pub struct PowerSquelch<
+    T: SquelchSample,
+    I: CpuBufferReader<Item = T> = DefaultCpuReader<T>,
+    O: CpuBufferWriter<Item = T> = DefaultCpuWriter<T>,
+> {
+    /// Linear power
+    threshold: f32,
+    alpha: f32,
+    average: f32,
+    gate: bool,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<T, I, O> PowerSquelch<T, I, O>
+where
+    T: SquelchSample,
+    I: CpuBufferReader<Item = T>,
+    O: CpuBufferWriter<Item = T>,
+{
+    pub fn new(threshold_db: f64, alpha: f32, gate: bool) -> Self {
+        Self {
+            threshold: db_to_power(threshold_db),
+            alpha,
+            average: 0.0,
+            gate,
+            input: I::default(),
+            output: O::default(),
+        }
+    }
+
+    async fn threshold(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match pmt_as_f64(&p) {
+            Some(threshold_db) => {
+                self.threshold = db_to_power(threshold_db);
+                Ok(Pmt::Ok)
+            }
+            None => Ok(Pmt::InvalidValue),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<T, I, O> Kernel for PowerSquelch<T, I, O>
+where
+    T: SquelchSample,
+    I: CpuBufferReader<Item = T>,
+    O: CpuBufferWriter<Item = T>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let mut consumed = 0;
+        let mut produced = 0;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            ilen = i.len();
+            while consumed < ilen && produced < o.len() {
+                let v = i[consumed];
+                consumed += 1;
+                self.average = self.alpha * v.power() + (1.0 - self.alpha) * self.average;
+                if self.average >= self.threshold {
+                    o[produced] = v;
+                    produced += 1;
+                } else if !self.gate {
+                    o[produced] = T::default();
+                    produced += 1;
+                }
+            }
+        }
+
+        self.input.consume(consumed);
+        self.output.produce(produced);
+
+        if self.input.finished() && consumed == ilen {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use anyhow::Result;
+use futuresdr::prelude::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Counters updated by a [`Probe`] and read by anyone holding a clone of the `Arc`.
+#[derive(Debug, Default)]
+pub struct ProbeCounters {
+    items: AtomicU64,
+    /// Largest free space seen in the output buffer, ie an estimation of its size
+    capacity: AtomicU64,
+    fill_samples: AtomicU64,
+    /// Sum of the output buffer fill, in per mille, for each sample
+    fill_sum: AtomicU64,
+}
+
+impl ProbeCounters {
+    /// Number of items that went through the probe
+    pub fn items(&self) -> u64 {
+        self.items.load(Ordering::Relaxed)
+    }
+
+    /// Average fill of the buffer downstream of the probe, between 0.0 and 1.0
+    pub fn average_fill(&self) -> Option<f64> {
+        let samples = self.fill_samples.load(Ordering::Relaxed);
+        if samples == 0 {
+            return None;
+        }
+        let sum = self.fill_sum.load(Ordering::Relaxed);
+        Some(sum as f64 / samples as f64 / 1000.0)
+    }
+
+    fn record(&self, items: usize, free: usize) {
+        self.items.fetch_add(items as u64, Ordering::Relaxed);
+        let capacity = self
+            .capacity
+            .fetch_max(free as u64, Ordering::Relaxed)
+            .max(free as u64);
+        if capacity > 0 {
+            let fill = 1000 - (free as u64 * 1000 / capacity);
+            self.fill_sum.fetch_add(fill, Ordering::Relaxed);
+            self.fill_samples.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Copy its input to its output, counting items and sampling how full
+/// the downstream buffer is, ie how much the next block lags behind.
+#[derive(Block)]
+pub struct Probe<
+    T: CpuSample,
+    I: CpuBufferReader<Item = T> = DefaultCpuReader<T>,
+    O: CpuBufferWriter<Item = T> = DefaultCpuWriter<T>,
+> {
+    counters: Arc<ProbeCounters>,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<T, I, O> Probe<T, I, O>
+where
+    T: CpuSample,
+    I: CpuBufferReader<Item = T>,
+    O: CpuBufferWriter<Item = T>,
+{
+    pub fn new(counters: Arc<ProbeCounters>) -> Self {
+        Self {
+            counters,
+            input: I::default(),
+            output: O::default(),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<T, I, O> Kernel for Probe<T, I, O>
+where
+    T: CpuSample,
+    I: CpuBufferReader<Item = T>,
+    O: CpuBufferWriter<Item = T>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let m;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            ilen = i.len();
+            m = std::cmp::min(ilen, o.len());
+            self.counters.record(m, o.len());
+            o[..m].clone_from_slice(&i[..m]);
+        }
+
+        self.input.consume(m);
+        self.output.produce(m);
+
+        if self.input.finished() && m == ilen {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use anyhow::Result;
+use futuresdr::prelude::*;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+const REFRESH_PERIOD: Duration = Duration::from_secs(1);
+
+/// Copy its input to its output, reporting on stderr the amount of data processed,
+/// the current rate and, if the expected number of items is known, the ETA.
+///
+/// The report is refreshed in place (with `\r`), so it does not disturb data on stdout.
+#[derive(Block)]
+pub struct ProgressMeter<
+    T: CpuSample,
+    I: CpuBufferReader<Item = T> = DefaultCpuReader<T>,
+    O: CpuBufferWriter<Item = T> = DefaultCpuWriter<T>,
+> {
+    total: Option<u64>,
+    items: u64,
+    start: Option<Instant>,
+    last_report: Option<(Instant, u64)>,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<T, I, O> ProgressMeter<T, I, O>
+where
+    T: CpuSample,
+    I: CpuBufferReader<Item = T>,
+    O: CpuBufferWriter<Item = T>,
+{
+    /// `total` is the expected number of items, eg from the size of the input file.
+    pub fn new(total: Option<u64>) -> Self {
+        Self {
+            total,
+            items: 0,
+            start: None,
+            last_report: None,
+            input: I::default(),
+            output: O::default(),
+        }
+    }
+
+    fn report(&mut self, now: Instant, last: bool) {
+        let start = *self.start.get_or_insert(now);
+        let (previous, previous_items) = self.last_report.unwrap_or((start, 0));
+        let elapsed = now.duration_since(previous).as_secs_f64();
+        let rate = if elapsed > 0.0 {
+            (self.items - previous_items) as f64 / elapsed
+        } else {
+            0.0
+        };
+        let bytes = self.items * std::mem::size_of::<T>() as u64;
+        let mut line = format!(
+            "\r{}B, {} samples, {} samples/s",
+            human(bytes as f64),
+            human(self.items as f64),
+            human(rate)
+        );
+        if let Some(total) = self.total.filter(|total| *total > 0) {
+            let average = self.items as f64 / now.duration_since(start).as_secs_f64().max(1e-9);
+            let percent = 100.0 * self.items as f64 / total as f64;
+            line.push_str(&format!(", {percent:.1}%"));
+            if average > 0.0 && self.items < total {
+                let eta = ((total - self.items) as f64 / average) as u64;
+                line.push_str(&format!(", ETA {}:{:02}", eta / 60, eta % 60));
+            }
+        }
+        line.push_str("\x1b[K");
+        if last {
+            line.push('\n');
+        }
+        let mut stderr = std::io::stderr();
+        let _ = stderr.write_all(line.as_bytes());
+        let _ = stderr.flush();
+        self.last_report = Some((now, self.items));
+    }
+}
+
+/// Format a value with engineering multipliers, eg 2.4M
+fn human(value: f64) -> String {
+    if value >= 1e9 {
+        format!("{:.2}G", value / 1e9)
+    } else if value >= 1e6 {
+        format!("{:.2}M", value / 1e6)
+    } else if value >= 1e3 {
+        format!("{:.2}K", value / 1e3)
+    } else {
+        format!("{value:.0}")
+    }
+}
+
+#[doc(hidden)]
+impl<T, I, O> Kernel for ProgressMeter<T, I, O>
+where
+    T: CpuSample,
+    I: CpuBufferReader<Item = T>,
+    O: CpuBufferWriter<Item = T>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let now = Instant::now();
+        self.start.get_or_insert(now);
+        let m;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            ilen = i.len();
+            m = std::cmp::min(ilen, o.len());
+            o[..m].clone_from_slice(&i[..m]);
+        }
+
+        self.input.consume(m);
+        self.output.produce(m);
+        self.items += m as u64;
+
+        if self.input.finished() && m == ilen {
+            self.report(now, true);
+            io.finished = true;
+        } else if self
+            .last_report
+            .is_none_or(|(last, _)| now.duration_since(last) >= REFRESH_PERIOD)
+        {
+            self.report(now, false);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use anyhow::{bail, Result};
+use futuresdr::prelude::*;
+
+/// Order of the bits in an item, as GNU Radio's `gr.GR_MSB_FIRST` and `gr.GR_LSB_FIRST`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Endianness {
+    MsbFirst,
+    LsbFirst,
+}
+
+impl TryFrom<&str> for Endianness {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        let value = value.trim_matches(['"', '\'']);
+        match value.strip_prefix("gr.").unwrap_or(value) {
+            "GR_MSB_FIRST" | "msb" => Ok(Self::MsbFirst),
+            "GR_LSB_FIRST" | "lsb" => Ok(Self::LsbFirst),
+            _ => bail!("Unknown endianness: {value}"),
+        }
+    }
+}
+
+/// State of a [`RepackBits`]
+struct Repacker {
+    k: usize,
+    l: usize,
+    endianness: Endianness,
+    /// Input byte being read, and its number of bits not read yet
+    current: u8,
+    current_left: usize,
+    /// Output byte being written, and its number of bits written
+    pending: u8,
+    pending_len: usize,
+}
+
+impl Repacker {
+    /// Next bit of the current input byte
+    fn read_bit(&mut self) -> u8 {
+        let index = match self.endianness {
+            Endianness::MsbFirst => self.current_left - 1,
+            Endianness::LsbFirst => self.k - self.current_left,
+        };
+        self.current_left -= 1;
+        (self.current >> index) & 1
+    }
+
+    /// Add a bit to the output byte, returning it once complete
+    fn write_bit(&mut self, bit: u8) -> Option<u8> {
+        match self.endianness {
+            Endianness::MsbFirst => self.pending = (self.pending << 1) | bit,
+            Endianness::LsbFirst => self.pending |= bit << self.pending_len,
+        }
+        self.pending_len += 1;
+        if self.pending_len == self.l {
+            self.pending_len = 0;
+            Some(std::mem::take(&mut self.pending))
+        } else {
+            None
+        }
+    }
+}
+
+/// Repack the `k` lower bits of each input byte into the `l` lower bits of the
+/// output bytes, like GNU Radio's `repack_bits_bb`: the bits of a byte are read and
+/// written most significant first, or least significant first. Trailing bits not
+/// filling an output byte are dropped.
+#[derive(Block)]
+pub struct RepackBits<
+    I: CpuBufferReader<Item = u8> = DefaultCpuReader<u8>,
+    O: CpuBufferWriter<Item = u8> = DefaultCpuWriter<u8>,
+> {
+    repacker: Repacker,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<I, O> RepackBits<I, O>
+where
+    I: CpuBufferReader<Item = u8>,
+    O: CpuBufferWriter<Item = u8>,
+{
+    pub fn new(k: usize, l: usize, endianness: Endianness) -> Result<Self> {
+        if !(1..=8).contains(&k) || !(1..=8).contains(&l) {
+            bail!("repack_bits: {k} and {l} bits per byte must be within [1, 8]");
+        }
+        Ok(Self {
+            repacker: Repacker {
+                k,
+                l,
+                endianness,
+                current: 0,
+                current_left: 0,
+                pending: 0,
+                pending_len: 0,
+            },
+            input: I::default(),
+            output: O::default(),
+        })
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for RepackBits<I, O>
+where
+    I: CpuBufferReader<Item = u8>,
+    O: CpuBufferWriter<Item = u8>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let repacker = &mut self.repacker;
+        let mut consumed = 0;
+        let mut produced = 0;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            ilen = i.len();
+            // Each bit completes at most one output byte
+            while produced < o.len() {
+                if repacker.current_left == 0 {
+                    if consumed == ilen {
+                        break;
+                    }
+                    repacker.current = i[consumed];
+                    repacker.current_left = repacker.k;
+                    consumed += 1;
+                }
+                let bit = repacker.read_bit();
+                if let Some(byte) = repacker.write_bit(bit) {
+                    o[produced] = byte;
+                    produced += 1;
+                }
+            }
+        }
+
+        self.input.consume(consumed);
+        self.output.produce(produced);
+
+        if self.input.finished() && consumed == ilen && self.repacker.current_left == 0 {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use anyhow::{bail, Result};
+use futuresdr::prelude::*;
+
+/// Output the given items forever, in a loop, eg a repeated vector
+/// or a pool of pre-computed noise samples like GNU Radio's `fastnoise_source`.
+#[derive(Block)]
+pub struct RepeatSource<T: CpuSample, O: CpuBufferWriter<Item = T> = DefaultCpuWriter<T>> {
+    items: Vec<T>,
+    index: usize,
+    #[output]
+    output: O,
+}
+
+impl<T, O> RepeatSource<T, O>
+where
+    T: CpuSample,
+    O: CpuBufferWriter<Item = T>,
+{
+    pub fn new(items: Vec<T>) -> Result<Self> {
+        if items.is_empty() {
+            bail!("repeated items must not be empty");
+        }
+        Ok(Self {
+            items,
+            index: 0,
+            output: O::default(),
+        })
+    }
+}
+
+#[doc(hidden)]
+impl<T, O> Kernel for RepeatSource<T, O>
+where
+    T: CpuSample,
+    O: CpuBufferWriter<Item = T>,
+{
+    async fn work(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let o = self.output.slice();
+        let n = o.len();
+        for v in o.iter_mut() {
+            *v = self.items[self.index].clone();
+            self.index = (self.index + 1) % self.items.len();
+        }
+        self.output.produce(n);
+        Ok(())
+    }
+}
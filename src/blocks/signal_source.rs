@@ -0,0 +1,240 @@
+use super::pmt_as_f64;
+use anyhow::{bail, Result};
+use futuresdr::num_complex::Complex32;
+use futuresdr::prelude::*;
+use futuresdr::runtime::Pmt;
+use std::f64::consts::{PI, TAU};
+
+/// Shape of a periodic signal, like GNU Radio's `analog.waveform_t`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Waveform {
+    Constant,
+    Sine,
+    Cosine,
+    /// 0 during the first half of the period, `amplitude` during the second one
+    Square,
+    /// From 0 up to `amplitude` and back
+    Triangle,
+    /// From `amplitude / 2` up to `amplitude`, then from 0
+    Sawtooth,
+}
+
+impl TryFrom<&str> for Waveform {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        match value {
+            "analog.GR_CONST_WAVE" | "const" => Ok(Waveform::Constant),
+            "analog.GR_SIN_WAVE" | "sin" => Ok(Waveform::Sine),
+            "analog.GR_COS_WAVE" | "cos" => Ok(Waveform::Cosine),
+            "analog.GR_SQR_WAVE" | "square" => Ok(Waveform::Square),
+            "analog.GR_TRI_WAVE" | "triangle" => Ok(Waveform::Triangle),
+            "analog.GR_SAW_WAVE" | "sawtooth" => Ok(Waveform::Sawtooth),
+            _ => bail!("Unknown waveform: {value}"),
+        }
+    }
+}
+
+impl Waveform {
+    /// Value for a phase in [0, 2π), without offset.
+    /// Like GNU Radio, square, triangle and sawtooth are computed on the phase in [-π, π).
+    pub fn value(self, phase: f64, amplitude: f32) -> f32 {
+        let amplitude = amplitude as f64;
+        let centered = if phase < PI { phase } else { phase - TAU };
+        let value = match self {
+            Waveform::Constant => amplitude,
+            Waveform::Sine => amplitude * phase.sin(),
+            Waveform::Cosine => amplitude * phase.cos(),
+            Waveform::Square => {
+                if centered < 0.0 {
+                    amplitude
+                } else {
+                    0.0
+                }
+            }
+            Waveform::Triangle => amplitude * centered.abs() / PI,
+            Waveform::Sawtooth => amplitude * (centered / TAU + 0.5),
+        };
+        value as f32
+    }
+}
+
+/// Item types a signal source can produce
+pub trait SignalSample: CpuSample {
+    fn signal(waveform: Waveform, phase: f64, amplitude: f32, offset: f32) -> Self;
+}
+
+impl SignalSample for f32 {
+    fn signal(waveform: Waveform, phase: f64, amplitude: f32, offset: f32) -> Self {
+        waveform.value(phase, amplitude) + offset
+    }
+}
+
+impl SignalSample for Complex32 {
+    /// The imaginary part lags a quarter of period behind the real one,
+    /// eg a cosine wave gives `amplitude * exp(j * phase)`.
+    fn signal(waveform: Waveform, phase: f64, amplitude: f32, offset: f32) -> Self {
+        let quadrature = (phase - PI / 2.0).rem_euclid(TAU);
+        Complex32::new(
+            waveform.value(phase, amplitude) + offset,
+            waveform.value(quadrature, amplitude),
+        )
+    }
+}
+
+/// Periodic signal generator, like GNU Radio's `sig_source`.
+/// The frequency and amplitude can be changed at runtime through
+/// the `freq` and `amp` message inputs.
+#[derive(Block)]
+#[message_inputs(freq, amp)]
+pub struct SignalSource<T: SignalSample, O: CpuBufferWriter<Item = T> = DefaultCpuWriter<T>> {
+    waveform: Waveform,
+    sample_rate: f64,
+    /// Phase increment per sample
+    step: f64,
+    phase: f64,
+    amplitude: f32,
+    offset: f32,
+    #[output]
+    output: O,
+}
+
+impl<T, O> SignalSource<T, O>
+where
+    T: SignalSample,
+    O: CpuBufferWriter<Item = T>,
+{
+    pub fn new(
+        waveform: Waveform,
+        frequency: f64,
+        sample_rate: f64,
+        amplitude: f32,
+        offset: f32,
+    ) -> Self {
+        Self {
+            waveform,
+            sample_rate,
+            step: TAU * frequency / sample_rate,
+            phase: 0.0,
+            amplitude,
+            offset,
+            output: O::default(),
+        }
+    }
+
+    async fn freq(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match pmt_as_f64(&p) {
+            Some(frequency) => {
+                self.step = TAU * frequency / self.sample_rate;
+                Ok(Pmt::Ok)
+            }
+            None => Ok(Pmt::InvalidValue),
+        }
+    }
+
+    async fn amp(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match pmt_as_f64(&p) {
+            Some(amplitude) => {
+                self.amplitude = amplitude as f32;
+                Ok(Pmt::Ok)
+            }
+            None => Ok(Pmt::InvalidValue),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<T, O> Kernel for SignalSource<T, O>
+where
+    T: SignalSample,
+    O: CpuBufferWriter<Item = T>,
+{
+    async fn work(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let o = self.output.slice();
+        let n = o.len();
+        for v in o.iter_mut() {
+            *v = T::signal(self.waveform, self.phase, self.amplitude, self.offset);
+            self.phase = (self.phase + self.step).rem_euclid(TAU);
+        }
+        self.output.produce(n);
+        Ok(())
+    }
+}
+
+/// Linear chirp: a cosine (or complex exponential) whose frequency sweeps
+/// from `start` to `stop` Hz in `duration` seconds, then starts again.
+#[derive(Block)]
+pub struct ChirpSource<T: SignalSample, O: CpuBufferWriter<Item = T> = DefaultCpuWriter<T>> {
+    sample_rate: f64,
+    start: f64,
+    /// Frequency increase per sample
+    rate: f64,
+    sweep_length: u64,
+    index: u64,
+    phase: f64,
+    amplitude: f32,
+    #[output]
+    output: O,
+}
+
+impl<T, O> ChirpSource<T, O>
+where
+    T: SignalSample,
+    O: CpuBufferWriter<Item = T>,
+{
+    pub fn new(start: f64, stop: f64, duration: f64, sample_rate: f64, amplitude: f32) -> Self {
+        let sweep_length = ((duration * sample_rate) as u64).max(1);
+        Self {
+            sample_rate,
+            start,
+            rate: (stop - start) / sweep_length as f64,
+            sweep_length,
+            index: 0,
+            phase: 0.0,
+            amplitude,
+            output: O::default(),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<T, O> Kernel for ChirpSource<T, O>
+where
+    T: SignalSample,
+    O: CpuBufferWriter<Item = T>,
+{
+    async fn work(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let o = self.output.slice();
+        let n = o.len();
+        for v in o.iter_mut() {
+            *v = T::signal(Waveform::Cosine, self.phase, self.amplitude, 0.0);
+            let frequency = self.start + self.rate * self.index as f64;
+            self.phase = (self.phase + TAU * frequency / self.sample_rate).rem_euclid(TAU);
+            self.index = (self.index + 1) % self.sweep_length;
+        }
+        self.output.produce(n);
+        Ok(())
+    }
+}
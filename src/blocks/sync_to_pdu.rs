@@ -0,0 +1,151 @@
+use super::Correlator;
+use anyhow::Result;
+use futuresdr::prelude::*;
+use futuresdr::runtime::Pmt;
+
+/// Item types a [`SyncToPdu`] can search a sync word in
+pub trait SyncToPduInput: CpuSample + Copy {
+    /// Hard decision of the bit `index` of the item, 0 being the least significant
+    fn bit(self, index: usize) -> u8;
+}
+
+impl SyncToPduInput for u8 {
+    fn bit(self, index: usize) -> u8 {
+        (self >> index) & 1
+    }
+}
+
+/// Soft symbols, positive for a 1
+impl SyncToPduInput for f32 {
+    fn bit(self, _index: usize) -> u8 {
+        (self > 0.0) as u8
+    }
+}
+
+/// Frames following the sync words received so far
+struct Framer {
+    correlator: Correlator,
+    /// Length of a frame, in bits
+    packet_len: usize,
+    /// Bits of the frames being received. A frame starts at each sync word
+    /// found, even inside the previous one, so a false detection does not hide a
+    /// real one.
+    frames: Vec<Vec<u8>>,
+}
+
+impl Framer {
+    /// Push a bit, adding the frames it completes to `completed`
+    fn push(&mut self, bit: u8, completed: &mut Vec<Vec<u8>>) {
+        for frame in self.frames.iter_mut() {
+            frame.push(bit);
+        }
+        let (done, pending): (Vec<_>, Vec<_>) = self
+            .frames
+            .drain(..)
+            .partition(|frame| frame.len() == self.packet_len);
+        self.frames = pending;
+        completed.extend(done.iter().map(|frame| Self::pack(frame)));
+        if self.correlator.push(bit).is_some() {
+            self.frames.push(Vec::with_capacity(self.packet_len));
+        }
+    }
+
+    /// Bytes of the bits, most significant first, the last one padded with zeros
+    fn pack(bits: &[u8]) -> Vec<u8> {
+        bits.chunks(8)
+            .map(|byte| {
+                byte.iter()
+                    .enumerate()
+                    .fold(0, |acc, (i, bit)| acc | (bit << (7 - i)))
+            })
+            .collect()
+    }
+}
+
+/// Search a sync word in a stream of bits, allowing for bit errors, and post the
+/// fixed length frame following each one as a PDU of packed bytes, like
+/// gr-satellites' `sync_to_pdu`.
+#[derive(Block)]
+#[message_outputs(pdus)]
+pub struct SyncToPdu<T: SyncToPduInput, I: CpuBufferReader<Item = T> = DefaultCpuReader<T>> {
+    framer: Framer,
+    /// 8 when bytes are packed, most significant bit first, 1 otherwise
+    bits_per_item: usize,
+    #[input]
+    input: I,
+}
+
+impl<T, I> SyncToPdu<T, I>
+where
+    T: SyncToPduInput,
+    I: CpuBufferReader<Item = T>,
+{
+    /// One bit per item: the least significant one of a byte or the sign of a soft symbol.
+    /// `packet_len` is the length of the frames, in bits.
+    pub fn new(correlator: Correlator, packet_len: usize) -> Self {
+        Self::with_bits_per_item(correlator, packet_len, 1)
+    }
+
+    fn with_bits_per_item(correlator: Correlator, packet_len: usize, bits_per_item: usize) -> Self {
+        assert!(
+            packet_len > 0,
+            "sync_to_pdu: packet length must be positive"
+        );
+        Self {
+            framer: Framer {
+                correlator,
+                packet_len,
+                frames: Vec::new(),
+            },
+            bits_per_item,
+            input: I::default(),
+        }
+    }
+}
+
+impl<I> SyncToPdu<u8, I>
+where
+    I: CpuBufferReader<Item = u8>,
+{
+    /// Eight bits per byte, most significant first
+    pub fn packed(correlator: Correlator, packet_len: usize) -> Self {
+        Self::with_bits_per_item(correlator, packet_len, 8)
+    }
+}
+
+#[doc(hidden)]
+impl<T, I> Kernel for SyncToPdu<T, I>
+where
+    T: SyncToPduInput,
+    I: CpuBufferReader<Item = T>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let mut completed = Vec::new();
+        let consumed;
+        {
+            let i = self.input.slice();
+            consumed = i.len();
+            for item in i.iter() {
+                for index in (0..self.bits_per_item).rev() {
+                    self.framer.push(item.bit(index), &mut completed);
+                }
+            }
+        }
+
+        self.input.consume(consumed);
+        for frame in completed {
+            mio.post("pdus", Pmt::Blob(frame)).await?;
+        }
+
+        if self.input.finished() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -99,6 +99,43 @@ impl ClockTrackingLoop {
         }
     }
 
+    /// Loop tracking a clock of nominal period `nominal_period` (in input samples),
+    /// with the loop bandwidth (normalized natural radian frequency), damping factor
+    /// and timing error detector gain, like GNU Radio's `clock_tracking_loop`.
+    pub fn with_loop_parameters(
+        loop_bw: f32,
+        max_period: f32,
+        min_period: f32,
+        nominal_period: f32,
+        damping: f32,
+        ted_gain: f32,
+    ) -> Self {
+        assert!(
+            min_period > 0.0 && min_period <= nominal_period && nominal_period <= max_period,
+            "clock_tracking_loop: periods must satisfy 0 < min <= nominal <= max"
+        );
+        let mut clock = Self::new(
+            nominal_period,
+            max_period,
+            min_period,
+            nominal_period,
+            nominal_period,
+            0.0,
+            damping,
+            loop_bw,
+            ted_gain,
+            0.0,
+            0.0,
+            nominal_period,
+            nominal_period,
+            0.0,
+        );
+        clock.set_loop_bandwidth(loop_bw);
+        clock.set_damping_factor(damping);
+        clock.set_ted_gain(ted_gain);
+        clock
+    }
+
     pub fn advance_loop(&mut self, error: f32) {
         // So the loop can be reverted one step, if needed.
         self.d_prev_avg_period = self.d_avg_period;
@@ -227,6 +264,18 @@ impl ClockTrackingLoop {
         }
     }
 
+    pub fn avg_period(&self) -> f32 {
+        self.d_avg_period
+    }
+
+    pub fn inst_period(&self) -> f32 {
+        self.d_inst_period
+    }
+
+    pub fn phase(&self) -> f32 {
+        self.d_phase
+    }
+
     pub fn loop_bandwidth(&self) -> f32 {
         self.d_omega_n_norm
     }
@@ -0,0 +1,59 @@
+use std::f32::consts::{FRAC_1_SQRT_2, TAU};
+
+/// Second order phase locked loop, like GNU Radio's `blocks::control_loop`:
+/// a proportional-integral filter of the phase error drives the phase
+/// and the frequency (in radians per sample) of a numerically controlled oscillator.
+#[derive(Clone, Debug)]
+pub struct ControlLoop {
+    phase: f32,
+    freq: f32,
+    max_freq: f32,
+    min_freq: f32,
+    /// Proportional gain
+    alpha: f32,
+    /// Integral gain
+    beta: f32,
+}
+
+impl ControlLoop {
+    /// `loop_bw` is the normalized natural radian frequency of the loop
+    /// (eg 2π/100), the frequency is kept within `[min_freq, max_freq]`.
+    pub fn new(loop_bw: f32, damping: f32, max_freq: f32, min_freq: f32) -> ControlLoop {
+        let denom = 1.0 + 2.0 * damping * loop_bw + loop_bw * loop_bw;
+        ControlLoop {
+            phase: 0.0,
+            freq: 0.0,
+            max_freq,
+            min_freq,
+            alpha: (4.0 * damping * loop_bw) / denom,
+            beta: (4.0 * loop_bw * loop_bw) / denom,
+        }
+    }
+
+    /// Damping factor of √2/2, the one GNU Radio uses when it is not given
+    pub fn with_default_damping(loop_bw: f32, max_freq: f32, min_freq: f32) -> ControlLoop {
+        Self::new(loop_bw, FRAC_1_SQRT_2, max_freq, min_freq)
+    }
+
+    /// Update the phase and frequency from the phase error, then keep them in range
+    pub fn advance_loop(&mut self, error: f32) {
+        self.freq += self.beta * error;
+        self.phase += self.freq + self.alpha * error;
+        while self.phase > TAU {
+            self.phase -= TAU;
+        }
+        while self.phase < -TAU {
+            self.phase += TAU;
+        }
+        self.freq = self.freq.clamp(self.min_freq, self.max_freq);
+    }
+
+    pub fn phase(&self) -> f32 {
+        self.phase
+    }
+
+    /// Frequency estimate, in radians per sample
+    pub fn frequency(&self) -> f32 {
+        self.freq
+    }
+}
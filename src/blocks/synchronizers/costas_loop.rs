@@ -0,0 +1,113 @@
+use super::control_loop::ControlLoop;
+use anyhow::{bail, Result};
+use futuresdr::num_complex::Complex32;
+use futuresdr::prelude::*;
+use std::f32::consts::SQRT_2;
+
+fn sign(x: f32) -> f32 {
+    if x > 0.0 {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+/// Phase error of a sample for a PSK of `order` points, like GNU Radio's Costas loop
+fn phase_error(order: usize, sample: Complex32) -> f32 {
+    let (re, im) = (sample.re, sample.im);
+    let error = match order {
+        2 => re * im,
+        4 => sign(re) * im - sign(im) * re,
+        _ => {
+            let k = SQRT_2 - 1.0;
+            if re.abs() >= im.abs() {
+                sign(re) * im - sign(im) * re * k
+            } else {
+                sign(re) * im * k - sign(im) * re
+            }
+        }
+    };
+    error.clamp(-1.0, 1.0)
+}
+
+/// Carrier recovery of a BPSK, QPSK or 8PSK signal, like GNU Radio's
+/// `digital.costas_loop_cc`: the input is derotated by the phase of a control loop
+/// driven by a phase detector insensitive to the modulation.
+/// The frequency estimate, in radians per sample, is output on `frequency`.
+#[derive(Block)]
+pub struct CostasLoop<
+    I: CpuBufferReader<Item = Complex32> = DefaultCpuReader<Complex32>,
+    O: CpuBufferWriter<Item = Complex32> = DefaultCpuWriter<Complex32>,
+    F: CpuBufferWriter<Item = f32> = DefaultCpuWriter<f32>,
+> {
+    order: usize,
+    control: ControlLoop,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+    #[output]
+    frequency: F,
+}
+
+impl<I, O, F> CostasLoop<I, O, F>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+    F: CpuBufferWriter<Item = f32>,
+{
+    /// `order` is the number of points of the PSK (2, 4 or 8),
+    /// `loop_bw` the normalized natural radian frequency of the loop.
+    pub fn new(order: usize, loop_bw: f32, damping: f32) -> Result<Self> {
+        if ![2, 4, 8].contains(&order) {
+            bail!("costas_loop: order must be 2, 4 or 8, not {order}");
+        }
+        Ok(Self {
+            order,
+            control: ControlLoop::new(loop_bw, damping, 1.0, -1.0),
+            input: I::default(),
+            output: O::default(),
+            frequency: F::default(),
+        })
+    }
+}
+
+#[doc(hidden)]
+impl<I, O, F> Kernel for CostasLoop<I, O, F>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+    F: CpuBufferWriter<Item = f32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let m;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            let f = self.frequency.slice();
+            ilen = i.len();
+            m = ilen.min(o.len()).min(f.len());
+            for ((x, y), freq) in i.iter().zip(o.iter_mut()).zip(f.iter_mut()).take(m) {
+                *y = x * Complex32::from_polar(1.0, -self.control.phase());
+                self.control.advance_loop(phase_error(self.order, *y));
+                *freq = self.control.frequency();
+            }
+        }
+
+        self.input.consume(m);
+        self.output.produce(m);
+        self.frequency.produce(m);
+
+        if self.input.finished() && m == ilen {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+use super::control_loop::ControlLoop;
+use anyhow::{bail, Result};
+use futuresdr::num_complex::Complex32;
+use futuresdr::prelude::*;
+use std::collections::VecDeque;
+use std::f32::consts::{PI, TAU};
+
+fn sinc(x: f32) -> f32 {
+    if x == 0.0 {
+        1.0
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
+
+/// Filters of the lower and upper band edges of a signal of `sps` samples per symbol,
+/// as designed by GNU Radio: a baseband filter made of two sincs, shifted to
+/// ±(1 + rolloff) / (2 sps) cycles per sample.
+fn band_edge_filters(
+    sps: f32,
+    rolloff: f32,
+    filter_size: usize,
+) -> (Vec<Complex32>, Vec<Complex32>) {
+    let m = (filter_size as f32 / sps).round();
+    let baseband: Vec<f32> = (0..filter_size)
+        .map(|i| {
+            let k = -m + i as f32 * 2.0 / sps;
+            sinc(rolloff * k - 0.5) + sinc(rolloff * k + 0.5)
+        })
+        .collect();
+    let power: f32 = baseband.iter().sum();
+    let n = (filter_size - 1) as f32 / 2.0;
+    baseband
+        .iter()
+        .enumerate()
+        .map(|(i, tap)| {
+            let tap = tap / power;
+            let k = (i as f32 - n) / (2.0 * sps);
+            (
+                Complex32::from_polar(tap, -TAU * (1.0 + rolloff) * k),
+                Complex32::from_polar(tap, TAU * (1.0 + rolloff) * k),
+            )
+        })
+        .unzip()
+}
+
+/// State of a [`FllBandEdge`]
+struct BandEdge {
+    lower: Vec<Complex32>,
+    upper: Vec<Complex32>,
+    /// Last corrected samples, most recent first
+    history: VecDeque<Complex32>,
+    control: ControlLoop,
+}
+
+impl BandEdge {
+    fn filter(&self, taps: &[Complex32]) -> Complex32 {
+        taps.iter()
+            .zip(self.history.iter())
+            .map(|(t, x)| t * x)
+            .sum()
+    }
+
+    fn correct(&mut self, x: Complex32) -> Complex32 {
+        let y = x * Complex32::from_polar(1.0, self.control.phase());
+        self.history.pop_back();
+        self.history.push_front(y);
+        // More power on the upper band edge than on the lower one means
+        // that the signal is too high
+        let error = self.filter(&self.lower).norm_sqr() - self.filter(&self.upper).norm_sqr();
+        self.control.advance_loop(error);
+        y
+    }
+}
+
+/// Frequency locked loop, like GNU Radio's `digital.fll_band_edge_cc`: the power
+/// of the signal on its two band edges is balanced by a control loop,
+/// correcting its frequency offset without needing any symbol synchronization.
+/// The correction, in radians per sample, is output on `frequency`.
+#[derive(Block)]
+pub struct FllBandEdge<
+    I: CpuBufferReader<Item = Complex32> = DefaultCpuReader<Complex32>,
+    O: CpuBufferWriter<Item = Complex32> = DefaultCpuWriter<Complex32>,
+    F: CpuBufferWriter<Item = f32> = DefaultCpuWriter<f32>,
+> {
+    band_edge: BandEdge,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+    #[output]
+    frequency: F,
+}
+
+impl<I, O, F> FllBandEdge<I, O, F>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+    F: CpuBufferWriter<Item = f32>,
+{
+    /// `sps` samples per symbol and `rolloff` excess bandwidth of the signal,
+    /// `filter_size` number of taps of the band edge filters
+    /// and `loop_bw` normalized natural radian frequency of the loop.
+    pub fn new(sps: f32, rolloff: f32, filter_size: usize, loop_bw: f32) -> Result<Self> {
+        if sps <= 0.0 {
+            bail!("fll_band_edge: samples per symbol must be positive");
+        }
+        if !(0.0..=1.0).contains(&rolloff) {
+            bail!("fll_band_edge: rolloff must be in [0, 1]");
+        }
+        if filter_size == 0 {
+            bail!("fll_band_edge: filter size must be positive");
+        }
+        let (lower, upper) = band_edge_filters(sps, rolloff, filter_size);
+        let max_freq = TAU * 2.0 / sps;
+        Ok(Self {
+            band_edge: BandEdge {
+                lower,
+                upper,
+                history: vec![Complex32::default(); filter_size].into(),
+                control: ControlLoop::with_default_damping(loop_bw, max_freq, -max_freq),
+            },
+            input: I::default(),
+            output: O::default(),
+            frequency: F::default(),
+        })
+    }
+}
+
+#[doc(hidden)]
+impl<I, O, F> Kernel for FllBandEdge<I, O, F>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+    F: CpuBufferWriter<Item = f32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let m;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            let f = self.frequency.slice();
+            ilen = i.len();
+            m = ilen.min(o.len()).min(f.len());
+            for ((x, y), freq) in i.iter().zip(o.iter_mut()).zip(f.iter_mut()).take(m) {
+                *y = self.band_edge.correct(*x);
+                *freq = self.band_edge.control.frequency();
+            }
+        }
+
+        self.input.consume(m);
+        self.output.produce(m);
+        self.frequency.produce(m);
+
+        if self.input.finished() && m == ilen {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
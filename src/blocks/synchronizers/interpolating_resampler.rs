@@ -0,0 +1,36 @@
+use futuresdr::num_complex::Complex32;
+
+/// Cubic Lagrange interpolation, in Farrow form, between the two middle
+/// ones of four consecutive samples.
+pub struct InterpolatingResampler {
+    coefficients: [Complex32; 4],
+}
+
+impl InterpolatingResampler {
+    /// Number of samples needed around an interpolation point: one before, two after
+    pub const TAPS: usize = 4;
+
+    pub fn new(x: [Complex32; 4]) -> Self {
+        let [xm1, x0, x1, x2] = x;
+        Self {
+            coefficients: [
+                x0,
+                -xm1 / 3.0 - x0 / 2.0 + x1 - x2 / 6.0,
+                xm1 / 2.0 - x0 + x1 / 2.0,
+                -xm1 / 6.0 + x0 / 2.0 - x1 / 2.0 + x2 / 6.0,
+            ],
+        }
+    }
+
+    /// Value at `mu` in [0, 1) between `x[1]` and `x[2]`
+    pub fn interpolate(&self, mu: f32) -> Complex32 {
+        let [c0, c1, c2, c3] = self.coefficients;
+        ((c3 * mu + c2) * mu + c1) * mu + c0
+    }
+
+    /// Derivative, per input sample, at `mu`
+    pub fn differentiate(&self, mu: f32) -> Complex32 {
+        let [_, c1, c2, c3] = self.coefficients;
+        (c3 * (3.0 * mu) + c2 * 2.0) * mu + c1
+    }
+}
@@ -1,8 +1,22 @@
 pub mod clock_tracking_loop;
+pub mod control_loop;
+pub mod costas_loop;
+pub mod fll_band_edge;
+pub mod interpolating_resampler;
+pub mod pll;
 pub mod symbol_sync;
 pub mod timing_error_detector;
 
+pub mod ted_early_late;
+pub mod ted_gardner;
 pub mod ted_mueller_and_muller;
+pub mod ted_signal_times_slope;
+pub mod ted_zero_crossing;
 pub mod timing_recovery;
 
+pub use control_loop::ControlLoop;
+pub use costas_loop::CostasLoop;
+pub use fll_band_edge::FllBandEdge;
+pub use pll::{PllCarrierTracking, PllFreqDet};
+pub use symbol_sync::{SymbolSync, SymbolSyncSample, TedType};
 pub use timing_recovery::{TimingAlgorithm, TimingRecovery};
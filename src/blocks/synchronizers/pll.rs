@@ -0,0 +1,143 @@
+use super::control_loop::ControlLoop;
+use anyhow::Result;
+use futuresdr::num_complex::Complex32;
+use futuresdr::prelude::*;
+use std::f32::consts::{PI, TAU};
+
+/// Phase of the sample relative to the one of the loop, in [-π, π]
+fn phase_error(control: &ControlLoop, sample: Complex32) -> f32 {
+    let error = (sample.arg() - control.phase()).rem_euclid(TAU);
+    if error > PI {
+        error - TAU
+    } else {
+        error
+    }
+}
+
+/// Lock on a carrier and bring it to baseband, like GNU Radio's
+/// `analog.pll_carriertracking_cc`. Frequencies are in radians per sample.
+#[derive(Block)]
+pub struct PllCarrierTracking<
+    I: CpuBufferReader<Item = Complex32> = DefaultCpuReader<Complex32>,
+    O: CpuBufferWriter<Item = Complex32> = DefaultCpuWriter<Complex32>,
+> {
+    control: ControlLoop,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<I, O> PllCarrierTracking<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    pub fn new(loop_bw: f32, max_freq: f32, min_freq: f32) -> Self {
+        Self {
+            control: ControlLoop::with_default_damping(loop_bw, max_freq, min_freq),
+            input: I::default(),
+            output: O::default(),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for PllCarrierTracking<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let m;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            ilen = i.len();
+            m = std::cmp::min(ilen, o.len());
+            for (x, y) in i.iter().zip(o.iter_mut()).take(m) {
+                self.control.advance_loop(phase_error(&self.control, *x));
+                *y = x * Complex32::from_polar(1.0, -self.control.phase());
+            }
+        }
+
+        self.input.consume(m);
+        self.output.produce(m);
+
+        if self.input.finished() && m == ilen {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// Frequency of the carrier the loop is locked on, in radians per sample,
+/// like GNU Radio's `analog.pll_freqdet_cf`.
+#[derive(Block)]
+pub struct PllFreqDet<
+    I: CpuBufferReader<Item = Complex32> = DefaultCpuReader<Complex32>,
+    O: CpuBufferWriter<Item = f32> = DefaultCpuWriter<f32>,
+> {
+    control: ControlLoop,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<I, O> PllFreqDet<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = f32>,
+{
+    pub fn new(loop_bw: f32, max_freq: f32, min_freq: f32) -> Self {
+        Self {
+            control: ControlLoop::with_default_damping(loop_bw, max_freq, min_freq),
+            input: I::default(),
+            output: O::default(),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for PllFreqDet<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = f32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let m;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            ilen = i.len();
+            m = std::cmp::min(ilen, o.len());
+            for (x, y) in i.iter().zip(o.iter_mut()).take(m) {
+                self.control.advance_loop(phase_error(&self.control, *x));
+                *y = self.control.frequency();
+            }
+        }
+
+        self.input.consume(m);
+        self.output.produce(m);
+
+        if self.input.finished() && m == ilen {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -1,3 +1,315 @@
-pub struct SymbolSync {}
+use super::clock_tracking_loop::ClockTrackingLoop;
+use super::interpolating_resampler::InterpolatingResampler;
+use super::ted_early_late::TedEarlyLate;
+use super::ted_gardner::TedGardner;
+use super::ted_mueller_and_muller::TedMuellerAndMuller;
+use super::ted_signal_times_slope::TedSignalTimesSlope;
+use super::ted_zero_crossing::TedZeroCrossing;
+use super::timing_error_detector::*;
+use crate::blocks::modulators::DigitalConstellation;
+use anyhow::{bail, Result};
+use futuresdr::num_complex::Complex32;
+use futuresdr::prelude::*;
 
-impl SymbolSync {}
+/// Timing error detectors of a [`SymbolSync`], like GNU Radio's `digital.TED_*`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TedType {
+    MuellerAndMuller,
+    ZeroCrossing,
+    Gardner,
+    EarlyLate,
+    SignalTimesSlope,
+}
+
+impl TryFrom<&str> for TedType {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        match value {
+            "digital.TED_MUELLER_AND_MULLER" | "mueller_and_muller" | "mm" => {
+                Ok(TedType::MuellerAndMuller)
+            }
+            "digital.TED_ZERO_CROSSING" | "zero_crossing" => Ok(TedType::ZeroCrossing),
+            "digital.TED_GARDNER" | "gardner" => Ok(TedType::Gardner),
+            "digital.TED_EARLY_LATE" | "early_late" => Ok(TedType::EarlyLate),
+            "digital.TED_SIGNAL_TIMES_SLOPE_ML" | "signal_times_slope" => {
+                Ok(TedType::SignalTimesSlope)
+            }
+            _ => bail!("Unknown timing error detector: {value}"),
+        }
+    }
+}
+
+type Detector<A, L, D> = TimingErrorDetector<A, DigitalConstellation, L, D>;
+
+enum Ted {
+    MuellerAndMuller(Detector<TedMuellerAndMuller, WithoutLookAhead, NoDerivativeType>),
+    ZeroCrossing(Detector<TedZeroCrossing, WithoutLookAhead, NoDerivativeType>),
+    Gardner(Detector<TedGardner, WithoutLookAhead, NoDerivativeType>),
+    EarlyLate(Detector<TedEarlyLate, WithLookAhead, NoDerivativeType>),
+    SignalTimesSlope(Detector<TedSignalTimesSlope, WithoutLookAhead, NeedsDerivativeType>),
+}
+
+macro_rules! each_ted {
+    ($ted:expr, $detector:ident => $body:expr) => {
+        match $ted {
+            Ted::MuellerAndMuller($detector) => $body,
+            Ted::ZeroCrossing($detector) => $body,
+            Ted::Gardner($detector) => $body,
+            Ted::EarlyLate($detector) => $body,
+            Ted::SignalTimesSlope($detector) => $body,
+        }
+    };
+}
+
+impl Ted {
+    /// `constellation` is only used by the decision directed detectors
+    fn new(ted_type: TedType, constellation: DigitalConstellation) -> Result<Ted> {
+        let ted = match ted_type {
+            TedType::MuellerAndMuller => Ted::MuellerAndMuller(
+                TedMuellerAndMuller::build(1, 2, constellation).map_err(anyhow::Error::msg)?,
+            ),
+            TedType::ZeroCrossing => Ted::ZeroCrossing(
+                TedZeroCrossing::build(constellation).map_err(anyhow::Error::msg)?,
+            ),
+            TedType::Gardner => Ted::Gardner(TedGardner::build()),
+            TedType::EarlyLate => Ted::EarlyLate(TedEarlyLate::build()),
+            TedType::SignalTimesSlope => Ted::SignalTimesSlope(TedSignalTimesSlope::build()),
+        };
+        Ok(ted)
+    }
+
+    fn inputs_per_symbol(&self) -> usize {
+        each_ted!(self, ted => ted.inputs_per_symbol())
+    }
+
+    fn input_clock(&self) -> usize {
+        each_ted!(self, ted => ted.input_clock())
+    }
+
+    fn error(&self) -> f32 {
+        each_ted!(self, ted => ted.error())
+    }
+
+    fn needs_lookahead(&self) -> bool {
+        matches!(self, Ted::EarlyLate(_))
+    }
+
+    fn needs_derivative(&self) -> bool {
+        matches!(self, Ted::SignalTimesSlope(_))
+    }
+
+    fn input(&mut self, x: Complex32, d_x: Complex32) {
+        match self {
+            Ted::MuellerAndMuller(ted) => ted.input(x),
+            Ted::ZeroCrossing(ted) => ted.input(x),
+            Ted::Gardner(ted) => ted.input(x),
+            Ted::EarlyLate(ted) => ted.input(x),
+            Ted::SignalTimesSlope(ted) => ted.input(x, d_x),
+        }
+    }
+
+    fn input_lookahead(&mut self, x: Complex32) {
+        if let Ted::EarlyLate(ted) = self {
+            ted.input_lookahead(x);
+        }
+    }
+}
+
+/// Item types a [`SymbolSync`] can synchronize
+pub trait SymbolSyncSample: CpuSample + Copy {
+    fn to_complex(self) -> Complex32;
+    fn from_complex(x: Complex32) -> Self;
+}
+
+impl SymbolSyncSample for f32 {
+    fn to_complex(self) -> Complex32 {
+        Complex32::new(self, 0.0)
+    }
+
+    fn from_complex(x: Complex32) -> Self {
+        x.re
+    }
+}
+
+impl SymbolSyncSample for Complex32 {
+    fn to_complex(self) -> Complex32 {
+        self
+    }
+
+    fn from_complex(x: Complex32) -> Self {
+        x
+    }
+}
+
+/// State of a [`SymbolSync`]
+struct Synchronizer {
+    ted: Ted,
+    clock: ClockTrackingLoop,
+    /// Position of the next input of the detector, in samples of the input buffer
+    position: f64,
+    /// Distance between two inputs of the detector, ie the clock period divided
+    /// by the number of inputs per symbol
+    step: f64,
+    max_period: f64,
+    /// Input samples needed after the position to go to the next symbol
+    margin: f64,
+}
+
+impl Synchronizer {
+    fn interpolator<T: SymbolSyncSample>(
+        input: &[T],
+        position: f64,
+    ) -> (InterpolatingResampler, f32) {
+        let index = position.floor() as usize;
+        let mu = (position - index as f64) as f32;
+        let samples = [
+            input[index - 1].to_complex(),
+            input[index].to_complex(),
+            input[index + 1].to_complex(),
+            input[index + 2].to_complex(),
+        ];
+        (InterpolatingResampler::new(samples), mu)
+    }
+
+    /// Feed the detector with the next interpolated sample, returning it if it is a symbol
+    fn next<T: SymbolSyncSample>(&mut self, input: &[T]) -> Option<Complex32> {
+        let (resampler, mu) = Self::interpolator(input, self.position);
+        let x = resampler.interpolate(mu);
+        let d_x = if self.ted.needs_derivative() {
+            resampler.differentiate(mu)
+        } else {
+            Complex32::default()
+        };
+        self.ted.input(x, d_x);
+
+        let mut symbol = None;
+        if self.ted.input_clock() == 0 {
+            if self.ted.needs_lookahead() {
+                let (resampler, mu) = Self::interpolator(input, self.position + self.step);
+                self.ted.input_lookahead(resampler.interpolate(mu));
+            }
+            self.clock.advance_loop(self.ted.error());
+            self.clock.phase_wrap();
+            let period = (self.clock.inst_period() as f64).min(self.max_period);
+            self.step = period / self.ted.inputs_per_symbol() as f64;
+            symbol = Some(x);
+        }
+        self.position += self.step;
+        symbol
+    }
+}
+
+/// Symbol synchronizer, like GNU Radio's `symbol_sync`: the input is resampled
+/// (by cubic interpolation) at the instants given by a clock tracking loop driven
+/// by a timing error detector, and one sample per symbol is output.
+#[derive(Block)]
+pub struct SymbolSync<
+    T: SymbolSyncSample,
+    I: CpuBufferReader<Item = T> = DefaultCpuReader<T>,
+    O: CpuBufferWriter<Item = T> = DefaultCpuWriter<T>,
+> {
+    sync: Synchronizer,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<T, I, O> SymbolSync<T, I, O>
+where
+    T: SymbolSyncSample,
+    I: CpuBufferReader<Item = T>,
+    O: CpuBufferWriter<Item = T>,
+{
+    /// `constellation` gives the decisions of the decision directed detectors
+    /// (Mueller and Müller, zero crossing).
+    /// `sps` is the nominal number of samples per symbol and `max_deviation`
+    /// how far from it the clock period can go. `loop_bw` is the normalized
+    /// natural radian frequency of the loop, `damping` its damping factor
+    /// and `ted_gain` the expected gain of the timing error detector.
+    pub fn new(
+        ted_type: TedType,
+        constellation: DigitalConstellation,
+        sps: f32,
+        loop_bw: f32,
+        damping: f32,
+        ted_gain: f32,
+        max_deviation: f32,
+    ) -> Result<Self> {
+        if sps <= 1.0 {
+            bail!("symbol_sync: samples per symbol must be > 1.0, not {sps}");
+        }
+        if max_deviation < 0.0 || max_deviation >= sps - 1.0 {
+            bail!("symbol_sync: maximum deviation must be in [0, sps - 1), not {max_deviation}");
+        }
+        let ted = Ted::new(ted_type, constellation)?;
+        let clock = ClockTrackingLoop::with_loop_parameters(
+            loop_bw,
+            sps + max_deviation,
+            sps - max_deviation,
+            sps,
+            damping,
+            ted_gain,
+        );
+        let step = sps as f64 / ted.inputs_per_symbol() as f64;
+        let max_period = (sps + max_deviation) as f64;
+        Ok(Self {
+            sync: Synchronizer {
+                ted,
+                clock,
+                position: 1.0,
+                step,
+                max_period,
+                margin: max_period + InterpolatingResampler::TAPS as f64,
+            },
+            input: I::default(),
+            output: O::default(),
+        })
+    }
+}
+
+#[doc(hidden)]
+impl<T, I, O> Kernel for SymbolSync<T, I, O>
+where
+    T: SymbolSyncSample,
+    I: CpuBufferReader<Item = T>,
+    O: CpuBufferWriter<Item = T>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let mut produced = 0;
+        let consumed;
+        let starved;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            let ilen = i.len() as f64;
+            while produced < o.len() && self.sync.position + self.sync.margin < ilen {
+                if let Some(symbol) = self.sync.next(i) {
+                    o[produced] = T::from_complex(symbol);
+                    produced += 1;
+                }
+            }
+            starved = self.sync.position + self.sync.margin >= ilen;
+            // Keep the sample before the position for the interpolation
+            consumed = (self.sync.position.floor() as usize)
+                .saturating_sub(1)
+                .min(i.len());
+            self.sync.position -= consumed as f64;
+        }
+
+        self.input.consume(consumed);
+        self.output.produce(produced);
+
+        if self.input.finished() && starved {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use std::collections::VecDeque;
+
+use futuresdr::num_complex::Complex32;
+
+use crate::blocks::modulators::constellation::Constellation;
+
+use super::timing_error_detector::*;
+
+/// Early-late: `(x[n+1/2] - x[n-1/2]) * x[n]`, needs the next half symbol sample
+pub struct TedEarlyLate {}
+
+impl TimingErrorDetectorAlgorithm for TedEarlyLate {
+    type Derivative = NoDerivativeType;
+    type LookAhead = WithLookAhead;
+}
+
+impl TimingErrorDetectorAlgorithmNoDerivatives<WithLookAhead> for TedEarlyLate {
+    fn compute_error_cf(_d_decision: &VecDeque<Complex32>, d_input: &VecDeque<Complex32>) -> f32 {
+        (d_input[0].re - d_input[2].re) * d_input[1].re
+            + (d_input[0].im - d_input[2].im) * d_input[1].im
+    }
+}
+
+impl TimingErrorDetectorAlgorithmNeedsLookahead<NoDerivativeType> for TedEarlyLate {}
+
+impl TedEarlyLate {
+    pub fn build<C>() -> TimingErrorDetector<TedEarlyLate, C, WithLookAhead, NoDerivativeType>
+    where
+        C: Constellation,
+    {
+        TimingErrorDetector::new(2, 2, None)
+    }
+}
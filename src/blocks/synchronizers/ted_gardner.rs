@@ -0,0 +1,33 @@
+use std::collections::VecDeque;
+
+use futuresdr::num_complex::Complex32;
+
+use crate::blocks::modulators::constellation::Constellation;
+
+use super::timing_error_detector::*;
+
+/// Gardner: `(x[n-1] - x[n]) * x[n-1/2]`, needs no decision
+pub struct TedGardner {}
+
+impl TimingErrorDetectorAlgorithm for TedGardner {
+    type Derivative = NoDerivativeType;
+    type LookAhead = WithoutLookAhead;
+}
+
+impl TimingErrorDetectorAlgorithmNoDerivatives<WithoutLookAhead> for TedGardner {
+    fn compute_error_cf(_d_decision: &VecDeque<Complex32>, d_input: &VecDeque<Complex32>) -> f32 {
+        (d_input[2].re - d_input[0].re) * d_input[1].re
+            + (d_input[2].im - d_input[0].im) * d_input[1].im
+    }
+}
+
+impl TimingErrorDetectorAlgorithmNoLookahead<NoDerivativeType> for TedGardner {}
+
+impl TedGardner {
+    pub fn build<C>() -> TimingErrorDetector<TedGardner, C, WithoutLookAhead, NoDerivativeType>
+    where
+        C: Constellation,
+    {
+        TimingErrorDetector::new(2, 3, None)
+    }
+}
@@ -31,9 +31,9 @@ impl TedMuellerAndMuller {
     pub fn build<C>(
         inputs_per_symbol: usize,
         error_computation_depth: usize,
-        constellation: &C,
+        constellation: C,
     ) -> Result<
-        TimingErrorDetector<'_, TedMuellerAndMuller, C, WithoutLookAhead, NoDerivativeType>,
+        TimingErrorDetector<TedMuellerAndMuller, C, WithoutLookAhead, NoDerivativeType>,
         &'static str,
     >
     where
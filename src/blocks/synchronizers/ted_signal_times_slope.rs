@@ -0,0 +1,37 @@
+use std::collections::VecDeque;
+
+use futuresdr::num_complex::Complex32;
+
+use crate::blocks::modulators::constellation::Constellation;
+
+use super::timing_error_detector::*;
+
+/// Maximum likelihood approximation: `x[n] * x'[n]`, needs the derivative of the input
+pub struct TedSignalTimesSlope {}
+
+impl TimingErrorDetectorAlgorithm for TedSignalTimesSlope {
+    type Derivative = NeedsDerivativeType;
+    type LookAhead = WithoutLookAhead;
+}
+
+impl TimingErrorDetectorAlgorithmNeedsDerivatives<WithoutLookAhead> for TedSignalTimesSlope {
+    fn compute_error_cf(
+        _d_decision: &VecDeque<Complex32>,
+        d_input: &VecDeque<Complex32>,
+        d_input_derivative: &VecDeque<Complex32>,
+    ) -> f32 {
+        d_input[0].re * d_input_derivative[0].re + d_input[0].im * d_input_derivative[0].im
+    }
+}
+
+impl TimingErrorDetectorAlgorithmNoLookahead<NeedsDerivativeType> for TedSignalTimesSlope {}
+
+impl TedSignalTimesSlope {
+    pub fn build<C>(
+    ) -> TimingErrorDetector<TedSignalTimesSlope, C, WithoutLookAhead, NeedsDerivativeType>
+    where
+        C: Constellation,
+    {
+        TimingErrorDetector::new(1, 1, None)
+    }
+}
@@ -0,0 +1,41 @@
+use std::collections::VecDeque;
+
+use futuresdr::num_complex::Complex32;
+
+use crate::blocks::modulators::constellation::Constellation;
+
+use super::timing_error_detector::*;
+
+/// Zero crossing: Gardner's detector on the decisions, `(a[n-1] - a[n]) * x[n-1/2]`
+pub struct TedZeroCrossing {}
+
+impl TimingErrorDetectorAlgorithm for TedZeroCrossing {
+    type Derivative = NoDerivativeType;
+    type LookAhead = WithoutLookAhead;
+}
+
+impl TimingErrorDetectorAlgorithmNoDerivatives<WithoutLookAhead> for TedZeroCrossing {
+    fn compute_error_cf(d_decision: &VecDeque<Complex32>, d_input: &VecDeque<Complex32>) -> f32 {
+        (d_decision[2].re - d_decision[0].re) * d_input[1].re
+            + (d_decision[2].im - d_decision[0].im) * d_input[1].im
+    }
+}
+
+impl TimingErrorDetectorAlgorithmNoLookahead<NoDerivativeType> for TedZeroCrossing {}
+
+impl TedZeroCrossing {
+    pub fn build<C>(
+        constellation: C,
+    ) -> Result<
+        TimingErrorDetector<TedZeroCrossing, C, WithoutLookAhead, NoDerivativeType>,
+        &'static str,
+    >
+    where
+        C: Constellation,
+    {
+        if constellation.dimensionality() != 1 {
+            return Err("timing_error_detector: constellation dimensionality (ie complex numbers per symbol) must be 1.");
+        }
+        Ok(TimingErrorDetector::new(2, 3, Some(constellation)))
+    }
+}
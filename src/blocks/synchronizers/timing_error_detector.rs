@@ -67,14 +67,14 @@ where
     // fn compute_error_ff(d_decision: [Complex32], d_input: [Complex32]) -> f32;
 }
 
-pub struct TimingErrorDetector<'a, A, C, L, D>
+pub struct TimingErrorDetector<A, C, L, D>
 where
     A: TimingErrorDetectorAlgorithm<LookAhead = L, Derivative = D>,
     C: Constellation,
     D: DerivativeType,
     L: LookAheadType,
 {
-    d_constellation: Option<&'a C>,
+    d_constellation: Option<C>,
     d_error: f32,
     d_prev_error: f32,
     d_inputs_per_symbol: usize,
@@ -89,7 +89,7 @@ where
     // _phantom_c: PhantomData<C>,
 }
 
-impl<'a, A, C, L, D> TimingErrorDetector<'a, A, C, L, D>
+impl<A, C, L, D> TimingErrorDetector<A, C, L, D>
 where
     D: DerivativeType,
     L: LookAheadType,
@@ -99,8 +99,8 @@ where
     pub fn new(
         inputs_per_symbol: usize,
         error_computation_depth: usize,
-        constellation: Option<&'a C>,
-    ) -> TimingErrorDetector<'a, A, C, L, D> {
+        constellation: Option<C>,
+    ) -> TimingErrorDetector<A, C, L, D> {
         let mut ted = TimingErrorDetector {
             d_constellation: constellation,
             d_error: 0.0,
@@ -126,6 +126,11 @@ where
         self.d_error
     }
 
+    /// Index of the next input within the symbol, 0 when it is a symbol sample
+    pub fn input_clock(&self) -> usize {
+        self.d_input_clock
+    }
+
     pub fn advance_input_clock(&mut self) {
         self.d_input_clock = (self.d_input_clock + 1) % self.d_inputs_per_symbol;
     }
@@ -184,7 +189,7 @@ pub fn dup_back_same_size(v: &mut VecDeque<Complex32>) {
     }
 }
 
-impl<'a, A, C, L> TimingErrorDetector<'a, A, C, L, NeedsDerivativeType>
+impl<A, C, L> TimingErrorDetector<A, C, L, NeedsDerivativeType>
 where
     A: TimingErrorDetectorAlgorithmNeedsDerivatives<L, Derivative = NeedsDerivativeType>,
     C: Constellation,
@@ -216,7 +221,7 @@ where
     }
 }
 
-impl<'a, A, C, L> TimingErrorDetector<'a, A, C, L, NoDerivativeType>
+impl<A, C, L> TimingErrorDetector<A, C, L, NoDerivativeType>
 where
     A: TimingErrorDetectorAlgorithmNoDerivatives<L, Derivative = NoDerivativeType>,
     C: Constellation,
@@ -246,7 +251,7 @@ where
     }
 }
 
-impl<'a, A, C> TimingErrorDetector<'a, A, C, WithLookAhead, NeedsDerivativeType>
+impl<A, C> TimingErrorDetector<A, C, WithLookAhead, NeedsDerivativeType>
 where
     A: TimingErrorDetectorAlgorithmNeedsLookahead<NeedsDerivativeType, LookAhead = WithLookAhead>
         + TimingErrorDetectorAlgorithmNeedsDerivatives<
@@ -291,7 +296,7 @@ where
     }
 }
 
-impl<'a, A, C> TimingErrorDetector<'a, A, C, WithLookAhead, NoDerivativeType>
+impl<A, C> TimingErrorDetector<A, C, WithLookAhead, NoDerivativeType>
 where
     A: TimingErrorDetectorAlgorithmNeedsLookahead<NoDerivativeType, LookAhead = WithLookAhead>
         + TimingErrorDetectorAlgorithmNoDerivatives<WithLookAhead, Derivative = NoDerivativeType>,
@@ -328,7 +333,7 @@ where
     }
 }
 
-impl<'a, A, C, D> TimingErrorDetector<'a, A, C, WithoutLookAhead, D>
+impl<A, C, D> TimingErrorDetector<A, C, WithoutLookAhead, D>
 where
     D: DerivativeType,
     A: TimingErrorDetectorAlgorithmNoLookahead<D, LookAhead = WithoutLookAhead>,
@@ -339,7 +344,7 @@ where
     }
 }
 
-impl<'a, A, C> TimingErrorDetector<'a, A, C, WithoutLookAhead, NeedsDerivativeType>
+impl<A, C> TimingErrorDetector<A, C, WithoutLookAhead, NeedsDerivativeType>
 where
     A: TimingErrorDetectorAlgorithmNoLookahead<NeedsDerivativeType, LookAhead = WithoutLookAhead>
         + TimingErrorDetectorAlgorithmNeedsDerivatives<
@@ -368,7 +373,7 @@ where
     }
 }
 
-impl<'a, A, C> TimingErrorDetector<'a, A, C, WithoutLookAhead, NoDerivativeType>
+impl<A, C> TimingErrorDetector<A, C, WithoutLookAhead, NoDerivativeType>
 where
     A: TimingErrorDetectorAlgorithmNoLookahead<NoDerivativeType, LookAhead = WithoutLookAhead>
         + TimingErrorDetectorAlgorithmNoDerivatives<WithoutLookAhead, Derivative = NoDerivativeType>,
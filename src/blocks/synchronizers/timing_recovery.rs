@@ -1,6 +1,8 @@
+use crate::blocks::pmt_as_f64;
 use anyhow::Result;
 use futuresdr::num_complex::Complex32;
 use futuresdr::prelude::*;
+use futuresdr::runtime::Pmt;
 
 #[derive(Clone, Debug)]
 pub enum TimingAlgorithm {
@@ -9,6 +11,7 @@ pub enum TimingAlgorithm {
 }
 
 #[derive(Block)]
+#[message_inputs(mu, max_error)]
 pub struct TimingRecovery<A>
 where
     A: Copy + Send + Sync + 'static + Default + std::fmt::Debug,
@@ -54,6 +57,39 @@ where
             output: Default::default(),
         }
     }
+
+    /// Change the loop gain
+    async fn mu(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match pmt_as_f64(&p) {
+            Some(mu) => {
+                self.mu = mu as f32;
+                Ok(Pmt::Ok)
+            }
+            None => Ok(Pmt::InvalidValue),
+        }
+    }
+
+    async fn max_error(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match pmt_as_f64(&p) {
+            Some(max_error) => {
+                self.max_error = max_error as f32;
+                Ok(Pmt::Ok)
+            }
+            None => Ok(Pmt::InvalidValue),
+        }
+    }
 }
 
 #[doc(hidden)]
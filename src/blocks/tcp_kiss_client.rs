@@ -4,22 +4,30 @@ use futures::StreamExt;
 use futuresdr::prelude::*;
 use futuresdr::runtime::Pmt;
 use std::io::Read;
-use std::net::TcpStream;
+use std::net::{Shutdown, TcpStream};
+use std::sync::{Arc, Mutex};
 use std::thread;
 
 #[derive(Block)]
 #[message_outputs(out)]
 pub struct TcpKissClient {
     rx: mpsc::UnboundedReceiver<Vec<u8>>,
+    /// Connection, once established, to close it when the flowgraph terminates
+    stream: Arc<Mutex<Option<TcpStream>>>,
 }
 
 impl TcpKissClient {
     pub fn new(address: &str) -> Result<Self> {
         let (tx, rx) = mpsc::unbounded::<Vec<u8>>();
         let addr = address.to_string();
+        let stream = Arc::new(Mutex::new(None));
+        let connected = stream.clone();
 
         thread::spawn(move || {
             if let Ok(mut stream) = TcpStream::connect(&addr) {
+                if let Ok(mut connected) = connected.lock() {
+                    *connected = stream.try_clone().ok();
+                }
                 let mut buffer = [0u8; 4096];
                 let mut current_frame = Vec::new();
                 let mut escape = false;
@@ -56,12 +64,19 @@ impl TcpKissClient {
             }
         });
 
-        Ok(Self { rx })
+        Ok(Self { rx, stream })
     }
 }
 
 #[doc(hidden)]
 impl Kernel for TcpKissClient {
+    async fn deinit(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        if let Some(stream) = self.stream.lock().ok().and_then(|mut s| s.take()) {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+        Ok(())
+    }
+
     async fn work(
         &mut self,
         io: &mut WorkIo,
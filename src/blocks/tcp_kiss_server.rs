@@ -2,13 +2,14 @@ use anyhow::Result;
 use futuresdr::prelude::*;
 use futuresdr::runtime::Pmt;
 use std::io::Write;
-use std::net::{TcpListener, TcpStream};
+use std::net::{Shutdown, TcpListener, TcpStream};
 use std::sync::mpsc;
 use std::thread;
 
 enum Event {
     Frame(Vec<u8>),
     NewClient(TcpStream),
+    Shutdown,
 }
 
 #[derive(Block)]
@@ -57,6 +58,12 @@ impl TcpKissServer {
 
                         clients.retain_mut(|stream| stream.write_all(&escaped).is_ok());
                     }
+                    Event::Shutdown => {
+                        for stream in clients.drain(..) {
+                            let _ = stream.shutdown(Shutdown::Both);
+                        }
+                        break;
+                    }
                 }
             }
         });
@@ -85,4 +92,10 @@ impl TcpKissServer {
 }
 
 #[doc(hidden)]
-impl Kernel for TcpKissServer {}
+impl Kernel for TcpKissServer {
+    /// Close the connections of the clients when the flowgraph terminates
+    async fn deinit(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        let _ = self.tx.send(Event::Shutdown);
+        Ok(())
+    }
+}
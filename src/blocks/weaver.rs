@@ -0,0 +1,110 @@
+use super::pmt_as_f64;
+use anyhow::Result;
+use futuresdr::num_complex::Complex32;
+use futuresdr::prelude::*;
+use futuresdr::runtime::Pmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Sideband {
+    Lower,
+    Upper,
+}
+
+/// Last stage of the weaver method for SSB decoding.
+///
+/// The middle of the audio band `audio_rate` (relative to the sample rate) can be changed
+/// at runtime through the `audio_rate` message input.
+#[derive(Block)]
+#[message_inputs(audio_rate)]
+pub struct Weaver<
+    I: CpuBufferReader<Item = Complex32> = DefaultCpuReader<Complex32>,
+    O: CpuBufferWriter<Item = f32> = DefaultCpuWriter<f32>,
+> {
+    sideband: Sideband,
+    osc: Complex32,
+    shift: Complex32,
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+}
+
+impl<I, O> Weaver<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = f32>,
+{
+    pub fn new(sideband: Sideband, audio_rate: f32) -> Self {
+        Self {
+            sideband,
+            osc: Complex32::new(1.0, 0.0),
+            shift: Self::shift(audio_rate),
+            input: I::default(),
+            output: O::default(),
+        }
+    }
+
+    fn shift(audio_rate: f32) -> Complex32 {
+        Complex32::from_polar(1.0, 2.0 * std::f32::consts::PI * audio_rate)
+    }
+
+    async fn audio_rate(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match pmt_as_f64(&p) {
+            Some(audio_rate) => {
+                self.shift = Self::shift(audio_rate as f32);
+                Ok(Pmt::Ok)
+            }
+            None => Ok(Pmt::InvalidValue),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for Weaver<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = f32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let m;
+        let ilen;
+        {
+            let i = self.input.slice();
+            let o = self.output.slice();
+            ilen = i.len();
+            m = std::cmp::min(ilen, o.len());
+
+            for (v, r) in i.iter().zip(o.iter_mut()).take(m) {
+                self.osc *= self.shift;
+                let term1 = v.re * self.osc.re;
+                let term2 = v.im * self.osc.im;
+                *r = match self.sideband {
+                    Sideband::Lower => term1 - term2,
+                    Sideband::Upper => term1 + term2,
+                };
+            }
+            // Prevent the oscillator amplitude from drifting
+            self.osc /= self.osc.norm();
+        }
+
+        self.input.consume(m);
+        self.output.produce(m);
+
+        if self.input.finished() && m == ilen {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use pest::iterators::Pair;
 use pest::Parser;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 
 #[derive(Parser)]
 #[grammar = "src/cmd_line.pest"]
@@ -9,19 +9,29 @@ pub struct CommandsParser;
 
 impl CommandsParser {
     pub fn parse_main(input: &str) -> Result<Pair<'_, Rule>> {
-        let input = CommandsParser::parse(Rule::main, input)
-            .context("Error while parsing:")?
-            .next()
-            .expect("msg");
+        let (_options, input) = CommandsParser::parse_main_with_options(input)?;
         //println!("input: {input:?}");
         Ok(input)
     }
 
+    /// Parse the whole command line, returning the global options (eg `--ctrlport`)
+    /// apart from the actual command.
+    pub fn parse_main_with_options(input: &str) -> Result<(Vec<Pair<'_, Rule>>, Pair<'_, Rule>)> {
+        let pairs = CommandsParser::parse(Rule::main, input).context("Error while parsing:")?;
+        let mut options = Vec::new();
+        for pair in pairs {
+            match pair.as_rule() {
+                Rule::ctrlport_opt | Rule::stats_opt | Rule::progress_opt => options.push(pair),
+                _ => return Ok((options, pair)),
+            }
+        }
+        bail!("no command found")
+    }
+
     pub fn parse_expr<'i>(expr: impl Into<&'i str>) -> Result<Pair<'i, Rule>> {
-        let expr = CommandsParser::parse(Rule::expr, expr.into())
-            .expect("msg")
+        let expr = CommandsParser::parse(Rule::expr, expr.into())?
             .next()
-            .expect("msg");
+            .context("expression expected")?;
         //println!("input: {input:?}");
         Ok(expr)
     }
@@ -5,9 +5,13 @@ pub trait HighLevelCmdLine<'i> {
     fn is_grc_cmd(&self) -> bool;
     fn is_csdr_cmd(&self) -> bool;
     fn is_help_cmd(&self) -> bool;
+    fn is_repl_cmd(&self) -> bool;
     fn as_grc_cmd(&self) -> Option<&Pair<'i, Rule>>;
     fn as_csdr_cmd(&self) -> Option<&Pair<'i, Rule>>;
+    fn as_run_cmd(&self) -> Option<&Pair<'i, Rule>>;
     fn as_iqengine_cmd(&self) -> Option<&Pair<'i, Rule>>;
+    fn as_bench_cmd(&self) -> Option<&Pair<'i, Rule>>;
+    fn as_selftest_cmd(&self) -> Option<&Pair<'i, Rule>>;
 }
 
 impl<'i> HighLevelCmdLine<'i> for Pair<'i, Rule> {
@@ -33,6 +37,13 @@ impl<'i> HighLevelCmdLine<'i> for Pair<'i, Rule> {
         }
     }
 
+    fn as_run_cmd(&self) -> Option<&Self> {
+        match self.as_rule() {
+            Rule::run_cmd => Some(self),
+            _ => None,
+        }
+    }
+
     fn as_iqengine_cmd(&self) -> Option<&Self> {
         match self.as_rule() {
             Rule::iqengine_cmd => Some(self),
@@ -40,7 +51,25 @@ impl<'i> HighLevelCmdLine<'i> for Pair<'i, Rule> {
         }
     }
 
+    fn as_bench_cmd(&self) -> Option<&Self> {
+        match self.as_rule() {
+            Rule::bench_cmd => Some(self),
+            _ => None,
+        }
+    }
+
+    fn as_selftest_cmd(&self) -> Option<&Self> {
+        match self.as_rule() {
+            Rule::selftest_cmd => Some(self),
+            _ => None,
+        }
+    }
+
     fn is_help_cmd(&self) -> bool {
         matches!(self.as_rule(), Rule::help_cmd)
     }
+
+    fn is_repl_cmd(&self) -> bool {
+        matches!(self.as_rule(), Rule::repl_cmd)
+    }
 }
@@ -0,0 +1,39 @@
+use super::CmdOptions;
+use crate::cmd_grammar::Rule;
+use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
+use anyhow::Result;
+use pest::iterators::Pair;
+
+pub trait AfcCmd<'i>: CmdOptions<'i> {
+    fn averaging(&self) -> Option<&str>;
+    fn is_quiet(&self) -> bool;
+
+    fn build_afc(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        let mut grc = grc;
+        grc = grc.ensure_source(GrcItemType::C32)?;
+        let mut blk = grc.create_block_instance("afc_cc");
+        blk.with_parameter("averaging", self.averaging().unwrap_or("4096"))
+            .with_parameter("report", if self.is_quiet() { "False" } else { "True" });
+        if let Some(sample_rate) = self.option_value(Rule::afc_rate_param)? {
+            blk.with_parameter("samp_rate", sample_rate);
+        }
+        grc = blk.assert_output(GrcItemType::C32).push_and_link()?;
+        Ok(grc)
+    }
+}
+
+impl<'i> AfcCmd<'i> for Pair<'i, Rule> {
+    fn averaging(&self) -> Option<&'i str> {
+        self.clone()
+            .into_inner()
+            .next()
+            .filter(|arg| !matches!(arg.as_rule(), Rule::afc_rate_param | Rule::afc_quiet_opt))
+            .map(|arg| arg.as_str())
+    }
+
+    fn is_quiet(&self) -> bool {
+        self.clone()
+            .into_inner()
+            .any(|arg| arg.as_rule() == Rule::afc_quiet_opt)
+    }
+}
@@ -0,0 +1,67 @@
+use super::CmdOptions;
+use crate::cmd_grammar::Rule;
+use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
+use anyhow::Result;
+use pest::iterators::Pair;
+
+pub trait AfskCmd<'i>: CmdOptions<'i> {
+    fn sample_rate(&self) -> &'i str;
+
+    /// Bell 202 audio to AX.25 frames, as gr-satellites' AFSK demodulator and AX.25 deframer
+    fn build_afsk1200_demod(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        let mut grc = grc;
+        grc = grc
+            .ensure_source(GrcItemType::F32)?
+            .create_block_instance("satellites_afsk_demodulator")
+            .with_parameter("baudrate", "1200")
+            .with_parameter("samp_rate", self.sample_rate())
+            .with_parameter("iq", "False")
+            .with_parameter("af_carrier", "1700")
+            .with_parameter("deviation", "-500")
+            .assert_output(GrcItemType::F32)
+            .push_and_link()?;
+        grc = grc
+            .create_block_instance("satellites_ax25_deframer")
+            .with_parameter("g3ruh_scrambler", "False")
+            .push_and_link()?;
+        Ok(grc)
+    }
+
+    /// AX.25 frames to Bell 202 audio
+    fn build_afsk1200_mod(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        let mut grc = grc;
+        grc = grc
+            .create_block_instance("afsk_modulator")
+            .with_parameter("samp_rate", self.sample_rate())
+            .with_parameter("baudrate", "1200")
+            .with_parameter("mark", "1200")
+            .with_parameter("space", "2200")
+            .with_parameter(
+                "preamble",
+                self.option_value(Rule::afsk_preamble_param)?
+                    .unwrap_or("32"),
+            )
+            .with_parameter(
+                "tail",
+                self.option_value(Rule::afsk_tail_param)?.unwrap_or("3"),
+            )
+            .assert_output(GrcItemType::F32)
+            .push_and_link()?;
+        Ok(grc)
+    }
+}
+
+impl<'i> AfskCmd<'i> for Pair<'i, Rule> {
+    fn sample_rate(&self) -> &'i str {
+        self.clone()
+            .into_inner()
+            .find(|arg| {
+                !matches!(
+                    arg.as_rule(),
+                    Rule::afsk_preamble_param | Rule::afsk_tail_param
+                )
+            })
+            .map(|value| value.as_str())
+            .unwrap_or("48000")
+    }
+}
@@ -0,0 +1,25 @@
+use crate::cmd_grammar::Rule;
+use crate::grc::builder::{GraphLevel, GrcBuilder};
+use anyhow::Result;
+use pest::iterators::Pair;
+
+pub trait Ax25PrintCmd<'i> {
+    fn is_json(&self) -> bool;
+
+    fn build_ax25_print(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        let mut grc = grc;
+        grc = grc
+            .create_block_instance("ax25_print")
+            .with_parameter("format", if self.is_json() { "json" } else { "tnc2" })
+            .push_and_link()?;
+        Ok(grc)
+    }
+}
+
+impl<'i> Ax25PrintCmd<'i> for Pair<'i, Rule> {
+    fn is_json(&self) -> bool {
+        self.clone()
+            .into_inner()
+            .any(|arg| arg.as_rule() == Rule::ax25_json_opt)
+    }
+}
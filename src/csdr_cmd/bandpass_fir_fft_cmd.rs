@@ -1,21 +1,27 @@
 use crate::cmd_grammar::Rule;
 use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
-use anyhow::{bail, Result};
-use pest::iterators::Pair;
+use anyhow::{bail, Context, Result};
+use pest::iterators::{Pair, Pairs};
 
 pub trait BandpassFirFftcmd<'i> {
     fn low_cut(&self) -> Result<&str>;
     fn high_cut(&self) -> Result<&str>;
     fn bandwidth(&self) -> Result<Option<&str>>;
     fn window(&self) -> Result<Option<&str>>;
+    fn fifo(&self) -> Result<Option<&str>>;
 
     fn build_bandpass_fir_fft_cc(
         &self,
         grc: GrcBuilder<GraphLevel>,
     ) -> Result<GrcBuilder<GraphLevel>> {
         let mut grc = grc;
-        let low_cut = self.low_cut()?;
-        let high_cut = self.high_cut()?;
+        let fifo = self.fifo()?;
+        let (low_cut, high_cut) = if fifo.is_some() {
+            // csdr waits for the first values, we let almost everything pass meanwhile
+            ("-0.45", "0.45")
+        } else {
+            (self.low_cut()?, self.high_cut()?)
+        };
         let transition_bw = self.bandwidth()?.unwrap_or("0.05");
         let window = self.window()?.unwrap_or("HAMMING");
         grc = grc
@@ -33,22 +39,41 @@ pub trait BandpassFirFftcmd<'i> {
             .with_parameter("win", format!("window.WIN_{window}"))
             .assert_output(GrcItemType::C32)
             .push_and_link()?;
+        if let Some(fifo) = fifo {
+            grc = grc
+                .create_block_instance("fifo_reader")
+                .with_parameter("file", fifo)
+                .push_as_message_source("cutoff")?;
+        }
         Ok(grc)
     }
 }
 
+/// Parameters following either `--fifo <path>` or `<low_cut> <high_cut>`
+fn remaining_params<'i>(cmd: &Pair<'i, Rule>) -> Pairs<'i, Rule> {
+    let mut inner = cmd.clone().into_inner();
+    if let Some(first) = inner.next() {
+        if first.as_rule() != Rule::fifo_opt {
+            inner.next();
+        }
+    }
+    inner
+}
+
 impl<'i> BandpassFirFftcmd<'i> for Pair<'i, Rule> {
     fn low_cut(&self) -> Result<&'i str> {
-        if let Some(value) = self.clone().into_inner().next() {
-            Ok(value.as_str())
-        } else {
-            bail!("missing mandatory <low_cut> parameters for bandpass_fir_fft_cc")
+        match self.clone().into_inner().next() {
+            Some(value) if value.as_rule() != Rule::fifo_opt => Ok(value.as_str()),
+            _ => bail!("missing mandatory <low_cut> parameters for bandpass_fir_fft_cc"),
         }
     }
 
     fn high_cut(&self) -> Result<&'i str> {
         let mut inner = self.clone().into_inner();
-        inner.next();
+        match inner.next() {
+            Some(value) if value.as_rule() != Rule::fifo_opt => {}
+            _ => bail!("missing mandatory <high_cut> parameters for bandpass_fir_fft_cc"),
+        }
         if let Some(value) = inner.next() {
             Ok(value.as_str())
         } else {
@@ -57,9 +82,7 @@ impl<'i> BandpassFirFftcmd<'i> for Pair<'i, Rule> {
     }
 
     fn bandwidth(&self) -> Result<Option<&'i str>> {
-        let mut inner = self.clone().into_inner();
-        inner.next();
-        inner.next();
+        let mut inner = remaining_params(self);
         if let Some(value) = inner.next() {
             Ok(Some(value.as_str()))
         } else {
@@ -68,9 +91,7 @@ impl<'i> BandpassFirFftcmd<'i> for Pair<'i, Rule> {
     }
 
     fn window(&self) -> Result<Option<&'i str>> {
-        let mut inner = self.clone().into_inner();
-        inner.next();
-        inner.next();
+        let mut inner = remaining_params(self);
         inner.next();
         if let Some(value) = inner.next() {
             Ok(Some(value.as_str()))
@@ -78,4 +99,14 @@ impl<'i> BandpassFirFftcmd<'i> for Pair<'i, Rule> {
             Ok(None)
         }
     }
+
+    fn fifo(&self) -> Result<Option<&'i str>> {
+        match self.clone().into_inner().next() {
+            Some(opt) if opt.as_rule() == Rule::fifo_opt => {
+                let path = opt.into_inner().next().context("fifo filepath expected")?;
+                Ok(Some(path.as_str()))
+            }
+            _ => Ok(None),
+        }
+    }
 }
@@ -0,0 +1,53 @@
+use crate::cmd_grammar::Rule;
+use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
+use anyhow::{Context, Result};
+use pest::iterators::Pair;
+
+pub trait CarrierRecoveryCmd<'i> {
+    fn arg(&self, index: usize) -> Option<&str>;
+
+    fn build_costas_loop(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        // csdr's loop bandwidth is in cycles per sample, GNU Radio's in radians
+        let loop_bw = self
+            .arg(0)
+            .context("missing mandatory <loop_bandwidth> parameter for bpsk_costas_loop_cc")?;
+        let mut grc = grc;
+        grc = grc
+            .ensure_source(GrcItemType::C32)?
+            .create_block_instance("digital_costas_loop_cc")
+            .with_parameter("w", format!("2*pi*({loop_bw})"))
+            .with_parameter("order", "2")
+            .with_parameter("use_snr", "False")
+            .with_parameter("damping", self.arg(1).unwrap_or("0.7071"))
+            .assert_output(GrcItemType::C32)
+            .push_and_link()?;
+        Ok(grc)
+    }
+
+    fn build_pll(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        let (block_type, output_type) = match self.arg(0) {
+            Some("freqdet_cf") => ("analog_pll_freqdet_cf", GrcItemType::F32),
+            _ => ("analog_pll_carriertracking_cc", GrcItemType::C32),
+        };
+        let mut grc = grc;
+        grc = grc
+            .ensure_source(GrcItemType::C32)?
+            .create_block_instance(block_type)
+            .with_parameter(
+                "w",
+                self.arg(1)
+                    .context("missing mandatory <loop_bw> parameter for pll")?,
+            )
+            .with_parameter("max_freq", self.arg(2).unwrap_or("1"))
+            .with_parameter("min_freq", self.arg(3).unwrap_or("-1"))
+            .assert_output(output_type)
+            .push_and_link()?;
+        Ok(grc)
+    }
+}
+
+impl<'i> CarrierRecoveryCmd<'i> for Pair<'i, Rule> {
+    fn arg(&self, index: usize) -> Option<&'i str> {
+        self.clone().into_inner().nth(index).map(|arg| arg.as_str())
+    }
+}
@@ -0,0 +1,73 @@
+use super::CmdOptions;
+use crate::cmd_grammar::Rule;
+use crate::csdr_cmd::eval_cmd::{EvalCmd, Variables};
+use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
+use anyhow::{bail, Context, Result};
+use pest::iterators::Pair;
+
+pub trait ChannelModelCmd<'i>: CmdOptions<'i> {
+    fn noise_voltage(&self, variables: &Variables) -> Result<String>;
+
+    fn build_channel_model(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        let mut grc = grc;
+        grc = grc.ensure_source(GrcItemType::C32)?;
+        let seed = self.option_value(Rule::channel_seed_param)?.unwrap_or("0");
+        if let Some(fading) = self.option_values(Rule::channel_fading_param) {
+            let doppler = *fading.first().context("doppler expected")?;
+            let k = fading.get(1).copied();
+            grc = grc
+                .create_block_instance("channels_fading_model")
+                .with_parameter("N", "8")
+                .with_parameter("fDTs", doppler)
+                .with_parameter("LOS", if k.is_some() { "True" } else { "False" })
+                .with_parameter("K", k.unwrap_or("4.0"))
+                .with_parameter("seed", seed)
+                .assert_output(GrcItemType::C32)
+                .push_and_link()?;
+        }
+        let noise_voltage = self.noise_voltage(grc.variables())?;
+        let taps = match self.option_values(Rule::channel_taps_param) {
+            Some(taps) => format!("({})", taps.join(", ")),
+            None => "1.0".to_string(),
+        };
+        grc = grc
+            .create_block_instance("channels_channel_model")
+            .with_parameter("noise_voltage", noise_voltage)
+            .with_parameter(
+                "freq_offset",
+                self.option_value(Rule::channel_freq_param)?
+                    .unwrap_or("0.0"),
+            )
+            .with_parameter(
+                "epsilon",
+                self.option_value(Rule::channel_epsilon_param)?
+                    .unwrap_or("1.0"),
+            )
+            .with_parameter("taps", taps)
+            .with_parameter("seed", seed)
+            .assert_output(GrcItemType::C32)
+            .push_and_link()?;
+        Ok(grc)
+    }
+}
+
+impl<'i> ChannelModelCmd<'i> for Pair<'i, Rule> {
+    /// `--noise` is the noise voltage while `--snr` is relative to a signal of unit power.
+    fn noise_voltage(&self, variables: &Variables) -> Result<String> {
+        let noise = self.option_value(Rule::channel_noise_param)?;
+        let snr = self
+            .clone()
+            .into_inner()
+            .find(|arg| arg.as_rule() == Rule::channel_snr_param);
+        match (noise, snr) {
+            (Some(_), Some(_)) => bail!("channel_model_cc: use either --noise or --snr"),
+            (Some(noise), None) => Ok(noise.to_string()),
+            (None, Some(snr)) => {
+                let snr = snr.into_inner().next().context("SNR expected")?;
+                let snr = snr.eval_with(variables)? as f32;
+                Ok(crate::blocks::ChannelParameters::noise_voltage_for_snr(snr).to_string())
+            }
+            (None, None) => Ok("0.0".to_string()),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use crate::cmd_grammar::Rule;
+use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
+use anyhow::{Context, Result};
+use pest::iterators::Pair;
+
+pub trait ConstellationDecoderCmd<'i> {
+    fn constellation(&self) -> Result<&str>;
+    fn is_soft(&self) -> bool;
+
+    fn build_constellation_decoder(
+        &self,
+        grc: GrcBuilder<GraphLevel>,
+    ) -> Result<GrcBuilder<GraphLevel>> {
+        let (block_type, output_type) = if self.is_soft() {
+            ("digital_constellation_soft_decoder_cf", GrcItemType::F32)
+        } else {
+            ("digital_constellation_decoder_cb", GrcItemType::U8)
+        };
+        let mut grc = grc;
+        grc = grc
+            .ensure_source(GrcItemType::C32)?
+            .create_block_instance(block_type)
+            .with_parameter("constellation", self.constellation()?)
+            .assert_output(output_type)
+            .push_and_link()?;
+        Ok(grc)
+    }
+}
+
+impl<'i> ConstellationDecoderCmd<'i> for Pair<'i, Rule> {
+    fn constellation(&self) -> Result<&'i str> {
+        self.clone()
+            .into_inner()
+            .next()
+            .map(|arg| arg.as_str())
+            .context("missing mandatory <constellation> parameter for constellation_decoder")
+    }
+
+    fn is_soft(&self) -> bool {
+        self.as_rule() == Rule::constellation_soft_decoder_cmd
+    }
+}
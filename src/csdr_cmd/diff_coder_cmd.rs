@@ -0,0 +1,53 @@
+use crate::cmd_grammar::Rule;
+use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
+use anyhow::{bail, Context, Result};
+use pest::iterators::Pair;
+
+pub trait DiffCoderCmd<'i> {
+    /// `digital_diff_encoder_bb` or `digital_diff_decoder_bb`
+    fn block_type(&self) -> Result<&'static str>;
+
+    fn build_diff_coder(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>>;
+}
+
+impl<'i> DiffCoderCmd<'i> for Pair<'i, Rule> {
+    fn block_type(&self) -> Result<&'static str> {
+        let coder = self
+            .clone()
+            .into_inner()
+            .next()
+            .context("diff: encoder or decoder expected")?;
+        match coder.as_str() {
+            "encoder" => Ok("digital_diff_encoder_bb"),
+            "decoder" => Ok("digital_diff_decoder_bb"),
+            other => bail!("diff: unknown coder {other}"),
+        }
+    }
+
+    fn build_diff_coder(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        let mut modulus = "2";
+        let mut coding = "digital.DIFF_DIFFERENTIAL";
+        match self.as_rule() {
+            Rule::diff_coder_cmd => {
+                for arg in self.clone().into_inner().skip(1) {
+                    match arg.as_rule() {
+                        Rule::diff_nrzi_opt => coding = "digital.DIFF_NRZI",
+                        _ => modulus = arg.as_str(),
+                    }
+                }
+            }
+            // As csdr's, used for PSK31: a 0 is a phase change, a 1 none
+            Rule::differential_coder_cmd => coding = "digital.DIFF_NRZI",
+            rule => bail!("diff: unexpected {rule:?}"),
+        }
+        let mut grc = grc;
+        grc = grc
+            .ensure_source(GrcItemType::U8)?
+            .create_block_instance(self.block_type()?)
+            .with_parameter("modulus", modulus)
+            .with_parameter("coding", coding)
+            .assert_output(GrcItemType::U8)
+            .push_and_link()?;
+        Ok(grc)
+    }
+}
@@ -1,76 +1,92 @@
 use crate::cmd_grammar::Rule;
 use crate::grc::Grc;
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use pest::iterators::Pair;
-use std::f32::consts::{E, PI};
+use std::collections::BTreeMap;
+use std::f64::consts::{E, PI};
+
+/// Values of the variables (eg from `let` statements or GRC `variable` blocks)
+/// that can be used in expressions.
+pub type Variables = BTreeMap<String, f64>;
 
 pub trait EvalCmd<'i> {
-    fn eval(&self) -> Result<f32>;
+    fn eval(&self) -> Result<f64>;
+    fn eval_with(&self, variables: &Variables) -> Result<f64>;
     fn execute_eval(&self) -> Result<Option<Grc>>;
+    fn execute_eval_with(&self, variables: &Variables) -> Result<Option<Grc>>;
 }
 
 impl<'i> EvalCmd<'i> for Pair<'i, Rule> {
     fn execute_eval(&self) -> Result<Option<Grc>> {
-        let expr = self.clone().into_inner().next().expect("msg");
-        let result = expr.eval()?;
+        self.execute_eval_with(&Variables::new())
+    }
+
+    fn execute_eval_with(&self, variables: &Variables) -> Result<Option<Grc>> {
+        let expr = self
+            .clone()
+            .into_inner()
+            .next()
+            .context("expression expected")?;
+        let result = expr.eval_with(variables)?;
         println!("{result}");
         Ok(None)
     }
 
-    fn eval(&self) -> Result<f32> {
+    fn eval(&self) -> Result<f64> {
+        self.eval_with(&Variables::new())
+    }
+
+    fn eval_with(&self, variables: &Variables) -> Result<f64> {
         match self.as_rule() {
             Rule::ident => match self.as_str() {
                 "pi" => Ok(PI),
                 "e" => Ok(E),
-                "nan" => Ok(f32::NAN),
-                "inf" => Ok(f32::INFINITY),
-                "neg_inf" => Ok(f32::NEG_INFINITY),
+                "nan" => Ok(f64::NAN),
+                "inf" => Ok(f64::INFINITY),
+                "neg_inf" => Ok(f64::NEG_INFINITY),
                 "tau" => Ok(2.0 * PI),
-                _ => {
-                    todo!("Unknown identifier {self:?}")
-                }
+                name => match variables.get(name) {
+                    Some(value) => Ok(*value),
+                    None => bail!("Unknown identifier {name}"),
+                },
             },
             Rule::number => {
-                let mut input = self.as_str().to_string();
+                let mut input = self.as_str().to_uppercase();
                 let mut multiplier = 1.0;
-                if input.contains('K') {
-                    multiplier = 1e3;
-                    input = input.replace('K', ".");
-                } else if input.contains('M') {
-                    multiplier = 1e6;
-                    input = input.replace('M', ".");
-                } else if input.contains('G') {
-                    multiplier = 1e9;
-                    input = input.replace('G', ".");
+                for (suffix, value) in [('K', 1e3), ('M', 1e6), ('G', 1e9)] {
+                    if input.contains(suffix) {
+                        multiplier = value;
+                        // Either 145M500 or 2.4M
+                        let separator = if input.contains('.') { "" } else { "." };
+                        input = input.replace(suffix, separator);
+                    }
                 }
-                Ok(multiplier * input.replace('_', "").parse::<f32>()?)
+                Ok(multiplier * input.replace('_', "").parse::<f64>()?)
             }
             Rule::func_call => {
                 let mut it = self.clone().into_inner();
                 let func_name = it.next().expect("func_name");
                 let func_name = func_name.as_str();
                 let arg1 = it.next().expect("arg1");
-                let arg1 = (arg1).eval()?;
+                let arg1 = (arg1).eval_with(variables)?;
                 match func_name {
                     "sqrt" => Ok(arg1.sqrt()),
-                    _ => {
-                        todo!()
-                    }
+                    _ => bail!("Unknown function {func_name}"),
                 }
             }
             Rule::term => {
                 let mut it = self.clone().into_inner();
                 let subterm = it.next().expect("subterm");
-                let r = (subterm).eval()?;
+                let r = (subterm).eval_with(variables)?;
                 Ok(r)
             }
             Rule::expr1 => {
                 let mut it = self.clone().into_inner();
                 let expr3 = it.next().expect("expr3");
-                let mut r = (expr3).eval()?;
+                let mut r = (expr3).eval_with(variables)?;
                 while let Some(operator) = it.next() {
                     let right = it.next().expect("right expr expected");
-                    let right = (right).eval()?;
+                    let right = (right).eval_with(variables)?;
                     match operator.as_rule() {
                         Rule::addition => {
                             r += right;
@@ -88,10 +104,10 @@ impl<'i> EvalCmd<'i> for Pair<'i, Rule> {
             Rule::expr2 => {
                 let mut it = self.clone().into_inner();
                 let expr3 = it.next().expect("expr3");
-                let mut r = (expr3).eval()?;
+                let mut r = (expr3).eval_with(variables)?;
                 while let Some(operator) = it.next() {
                     let right = it.next().expect("right expr expected");
-                    let right = (right).eval()?;
+                    let right = (right).eval_with(variables)?;
                     match operator.as_rule() {
                         Rule::multiply => {
                             r *= right;
@@ -112,10 +128,10 @@ impl<'i> EvalCmd<'i> for Pair<'i, Rule> {
             Rule::expr3 => {
                 let mut it = self.clone().into_inner();
                 let expr3 = it.next().expect("expr3");
-                let mut r = (expr3).eval()?;
+                let mut r = (expr3).eval_with(variables)?;
                 while let Some(operator) = it.next() {
                     let right = it.next().expect("right expr expected");
-                    let right = (right).eval()?;
+                    let right = (right).eval_with(variables)?;
                     match operator.as_rule() {
                         Rule::exponentiation => {
                             r = r.powf(right);
@@ -133,9 +149,9 @@ impl<'i> EvalCmd<'i> for Pair<'i, Rule> {
                 match first.as_rule() {
                     Rule::minus => {
                         let term = it.next().expect("expr4");
-                        Ok(-(term).eval()?)
+                        Ok(-(term).eval_with(variables)?)
                     }
-                    Rule::term => first.eval(),
+                    Rule::term => first.eval_with(variables),
                     _ => {
                         todo!()
                     }
@@ -1,22 +1,22 @@
 use crate::cmd_grammar::Rule;
-use crate::csdr_cmd::eval_cmd::EvalCmd;
+use crate::csdr_cmd::eval_cmd::{EvalCmd, Variables};
 use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
 use anyhow::{Context, Result};
 use pest::iterators::Pair;
 
 pub trait FixedlenToPduCmd<'i> {
-    fn packet_len(&self) -> Result<String>;
+    fn packet_len(&self, variables: &Variables) -> Result<String>;
     fn syncword_tag(&self) -> Result<Option<String>>;
     fn build_fixedlen_to_pdu(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>>;
 }
 
 impl<'i> FixedlenToPduCmd<'i> for Pair<'i, Rule> {
-    fn packet_len(&self) -> Result<String> {
+    fn packet_len(&self, variables: &Variables) -> Result<String> {
         self.clone()
             .into_inner()
             .next()
             .context("packet_len expression expected")?
-            .eval()
+            .eval_with(variables)
             .map(|v| v.to_string())
     }
 
@@ -32,7 +32,7 @@ impl<'i> FixedlenToPduCmd<'i> for Pair<'i, Rule> {
 
     fn build_fixedlen_to_pdu(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
         let mut grc = grc;
-        let packet_len = self.packet_len()?;
+        let packet_len = self.packet_len(grc.variables())?;
         let syncword_tag = self.syncword_tag()?.unwrap_or_else(|| "".to_string());
 
         grc = grc
@@ -0,0 +1,30 @@
+use crate::cmd_grammar::Rule;
+use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
+use anyhow::Result;
+use pest::iterators::Pair;
+
+pub trait FmModCmd<'i> {
+    fn sensitivity(&self) -> &'i str;
+
+    fn build_fmmod(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        let mut grc = grc;
+        grc = grc
+            .ensure_source(GrcItemType::F32)?
+            .create_block_instance("analog_frequency_modulator_fc")
+            .with_parameter("sensitivity", self.sensitivity())
+            .assert_output(GrcItemType::C32)
+            .push_and_link()?;
+        Ok(grc)
+    }
+}
+
+impl<'i> FmModCmd<'i> for Pair<'i, Rule> {
+    /// Radians per sample for an input of 1, π as in csdr by default
+    fn sensitivity(&self) -> &'i str {
+        self.clone()
+            .into_inner()
+            .next()
+            .map(|value| value.as_str())
+            .unwrap_or("pi")
+    }
+}
@@ -0,0 +1,70 @@
+use super::CmdOptions;
+use crate::cmd_grammar::Rule;
+use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
+use anyhow::Result;
+use pest::iterators::Pair;
+
+pub trait HdlcCmd<'i>: CmdOptions<'i> {
+    fn has_flag(&self, rule: Rule) -> bool;
+
+    fn build_hdlc_deframer(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        let mut grc = grc;
+        grc = grc
+            .ensure_source(GrcItemType::U8)?
+            .create_block_instance("digital_hdlc_deframer_bp")
+            .with_parameter(
+                "length_min",
+                self.option_value(Rule::hdlc_min_param)?.unwrap_or("32"),
+            )
+            .with_parameter(
+                "length_max",
+                self.option_value(Rule::hdlc_max_param)?.unwrap_or("500"),
+            )
+            .with_parameter(
+                "crc_check",
+                if self.has_flag(Rule::hdlc_no_crc_opt) {
+                    "False"
+                } else {
+                    "True"
+                },
+            )
+            .with_parameter(
+                "nrzi",
+                if self.has_flag(Rule::hdlc_nrzi_opt) {
+                    "True"
+                } else {
+                    "False"
+                },
+            )
+            .push_and_link()?;
+        Ok(grc)
+    }
+
+    fn build_hdlc_framer(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        let mut grc = grc;
+        grc = grc
+            .create_block_instance("digital_hdlc_framer_pb")
+            .with_parameter("frame_tag_name", "\"packet_len\"")
+            .with_parameter(
+                "flags",
+                self.option_value(Rule::hdlc_flags_param)?.unwrap_or("1"),
+            )
+            .with_parameter(
+                "nrzi",
+                if self.has_flag(Rule::hdlc_nrzi_opt) {
+                    "True"
+                } else {
+                    "False"
+                },
+            )
+            .assert_output(GrcItemType::U8)
+            .push_and_link()?;
+        Ok(grc)
+    }
+}
+
+impl<'i> HdlcCmd<'i> for Pair<'i, Rule> {
+    fn has_flag(&self, rule: Rule) -> bool {
+        self.clone().into_inner().any(|arg| arg.as_rule() == rule)
+    }
+}
@@ -0,0 +1,226 @@
+use super::AnyCmd;
+use crate::cmd_grammar::{CommandsParser, Rule};
+use crate::grc::builder::{GraphLevel, GrcBuilder};
+use anyhow::{bail, Context, Result};
+use pest::error::InputLocation;
+use pest::iterators::Pair;
+use pest::Parser;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Maximum depth of macros calling other macros
+const MAX_EXPANSION_DEPTH: usize = 32;
+
+/// A named sub-chain of csdr commands with parameters, eg:
+///
+/// ```toml
+/// [macros.nfm_rx]
+/// params = ["samp_rate", "audio_rate"]
+/// body = "fir_decimate_cc ($samp_rate/$audio_rate) ! fmdemod_quadri_cf ! deemphasis_nfm_ff $audio_rate ! agc_ff"
+/// ```
+///
+/// or in a pipeline script:
+///
+/// ```text
+/// macro nfm_rx samp_rate audio_rate = fir_decimate_cc ($samp_rate/$audio_rate) ! fmdemod_quadri_cf ! deemphasis_nfm_ff $audio_rate ! agc_ff
+/// ```
+///
+/// When called, eg `nfm_rx 2.4M 48k`, arguments replace the `$param` placeholders in the body as written,
+/// so that they can also be identifiers or filepaths.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Macro {
+    #[serde(default)]
+    pub params: Vec<String>,
+    pub body: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+pub type Macros = BTreeMap<String, Macro>;
+
+#[derive(Deserialize, Default)]
+struct MacroConfig {
+    #[serde(default)]
+    macros: Macros,
+}
+
+impl Macro {
+    /// Macros defined in the user configuration file: `$XDG_CONFIG_HOME/futuresdr/config.toml`
+    /// (`$HOME/.config/futuresdr/config.toml` by default).
+    pub fn from_config() -> &'static Macros {
+        static USER_MACROS: OnceLock<Macros> = OnceLock::new();
+        USER_MACROS.get_or_init(|| match Self::config_file() {
+            Some(path) if path.exists() => Self::load(&path).unwrap_or_else(|err| {
+                eprintln!("{err:#}");
+                Macros::new()
+            }),
+            _ => Macros::new(),
+        })
+    }
+
+    /// Macros defined in the `[macros]` table of a configuration file
+    pub fn load(path: impl AsRef<Path>) -> Result<Macros> {
+        let path = path.as_ref();
+        let config = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read {}", path.display()))?;
+        let config = toml::from_str::<MacroConfig>(&config)
+            .with_context(|| format!("invalid macros in {}", path.display()))?;
+        Ok(config.macros)
+    }
+
+    /// List of macros with their description, as displayed by `--help`
+    pub fn help(macros: &Macros) -> String {
+        let mut msg = String::new();
+        if macros.is_empty() {
+            return msg;
+        }
+        msg.push_str("Macros:\n");
+        for (name, definition) in macros.iter() {
+            let usage = definition.usage(name);
+            match &definition.description {
+                Some(description) => msg.push_str(&format!("\t{usage}\t{description}\n")),
+                None => msg.push_str(&format!("\t{usage}\n")),
+            }
+        }
+        msg.push('\n');
+        msg
+    }
+
+    fn config_file() -> Option<PathBuf> {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .map(|config_dir| config_dir.join("futuresdr").join("config.toml"))
+    }
+
+    /// Signature of the macro, eg `nfm_rx <samp_rate> <audio_rate>`
+    pub fn usage(&self, name: &str) -> String {
+        let mut usage = name.to_string();
+        for param in self.params.iter() {
+            usage.push_str(&format!(" <{param}>"));
+        }
+        usage
+    }
+
+    /// Replace `$param` placeholders by the arguments in the body
+    pub fn expand(&self, args: &[&str]) -> Result<String> {
+        if args.len() != self.params.len() {
+            bail!(
+                "expecting {} arguments but got {}",
+                self.params.len(),
+                args.len()
+            );
+        }
+        let mut expanded = String::with_capacity(self.body.len());
+        let mut rest = self.body.as_str();
+        while let Some(start) = rest.find('$') {
+            expanded.push_str(&rest[..start]);
+            rest = &rest[start + 1..];
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            let index = self
+                .params
+                .iter()
+                .position(|param| param == word)
+                .with_context(|| format!("unknown parameter ${word}"))?;
+            expanded.push_str(args[index]);
+            rest = &rest[end..];
+        }
+        expanded.push_str(rest);
+        Ok(expanded)
+    }
+}
+
+pub trait MacroCmd<'i> {
+    fn macro_name(&self) -> Result<&'i str>;
+    fn macro_args(&self) -> Vec<Pair<'i, Rule>>;
+    fn builtin_cmd_error(&self) -> Result<()>;
+
+    fn build_macro(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        let mut grc = grc;
+        self.builtin_cmd_error()?;
+        let name = self.macro_name()?;
+        let definition = grc
+            .macro_definition(name)
+            .or_else(|| Macro::from_config().get(name))
+            .cloned()
+            .with_context(|| format!("unknown command or macro {name}"))?;
+        let args: Vec<&str> = self.macro_args().iter().map(|arg| arg.as_str()).collect();
+        let body = definition
+            .expand(&args)
+            .context(format!("in macro {name}"))?;
+
+        if grc.enter_macro() > MAX_EXPANSION_DEPTH {
+            bail!("too many nested macros, eg {name} calling itself");
+        }
+        let cmds = CommandsParser::parse(Rule::pipeline, &body)
+            .with_context(|| format!("in macro {name}: {body}"))?;
+        for cmd in cmds {
+            if cmd.as_rule() != Rule::EOI {
+                grc = AnyCmd::parse(&cmd, grc).with_context(|| format!("in macro {name}"))?;
+            }
+        }
+        grc.exit_macro();
+        Ok(grc)
+    }
+}
+
+impl<'i> MacroCmd<'i> for Pair<'i, Rule> {
+    fn macro_name(&self) -> Result<&'i str> {
+        self.clone()
+            .into_inner()
+            .next()
+            .map(|name| name.as_str())
+            .context("macro name expected")
+    }
+
+    fn macro_args(&self) -> Vec<Pair<'i, Rule>> {
+        self.clone().into_inner().skip(1).collect()
+    }
+
+    /// A built-in command with invalid arguments ends up parsed as a macro call,
+    /// so report its own syntax error when the whole command name was recognized.
+    fn builtin_cmd_error(&self) -> Result<()> {
+        let name = self.macro_name()?;
+        let Err(err) = CommandsParser::parse(Rule::builtin_cmd_line, self.as_str()) else {
+            return Ok(());
+        };
+        let pos = match err.location {
+            InputLocation::Pos(pos) => pos,
+            InputLocation::Span((start, _)) => start,
+        };
+        if pos < name.len() {
+            return Ok(());
+        }
+        Err(anyhow::Error::new(err).context(format!("syntax error in {name}")))
+    }
+}
+
+/// Definition of a macro in a pipeline script: `macro name param1 param2 = body`
+pub fn parse_macro_def(pair: &Pair<'_, Rule>) -> Result<(String, Macro)> {
+    let mut name = None;
+    let mut params = Vec::new();
+    let mut body = None;
+    for item in pair.clone().into_inner() {
+        match item.as_rule() {
+            Rule::ident => name = Some(item.as_str().to_string()),
+            Rule::macro_param => params.push(item.as_str().to_string()),
+            Rule::macro_body => body = Some(item.as_str().trim().to_string()),
+            _ => {}
+        }
+    }
+    let name = name.context("macro name expected")?;
+    let body = body.context("macro body expected")?;
+    Ok((
+        name,
+        Macro {
+            params,
+            body,
+            description: None,
+        },
+    ))
+}
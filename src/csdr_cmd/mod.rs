@@ -5,15 +5,22 @@ use anyhow::{bail, Context, Result};
 use pest::iterators::Pair;
 use pest::Parser;
 
+use self::afc_cmd::AfcCmd;
+use self::afsk_cmd::AfskCmd;
 use self::agc_cmd::AgcCmd;
 use self::amdemod_cmd::AmDemodCmd;
 use self::audio_cmd::AudioCmd;
+use self::ax25_print_cmd::Ax25PrintCmd;
 use self::bandpass_fir_fft_cmd::BandpassFirFftcmd;
 use self::binary_slicer::BinarySlicerCmd;
+use self::carrier_recovery_cmd::CarrierRecoveryCmd;
+use self::channel_model_cmd::ChannelModelCmd;
 use self::clipdetect_cmd::ClipDetectCmd;
+use self::constellation_decoder_cmd::ConstellationDecoderCmd;
 use self::convert_cmd::ConvertCmd;
 use self::deemphasis_nfm_ff_cmd::DeemphasisNfnCmd;
 use self::deemphasis_wfm_ff_cmd::DeemphasisWfmCmd;
+use self::diff_coder_cmd::DiffCoderCmd;
 use self::dsb_cmd::DsbCmd;
 use self::dump_cmd::DumpCmd;
 use self::eval_cmd::EvalCmd;
@@ -21,33 +28,51 @@ use self::fastdcblock_cmd::FastDCBlockCmd;
 use self::fir_decimate_cmd::FirDecimateCmd;
 use self::fixedlen_to_pdu_cmd::FixedlenToPduCmd;
 use self::fmdemod_quadri_cmd::FmDemodQuadriCmd;
+use self::fmmod_cmd::FmModCmd;
 use self::fractional_decimator_cmd::FractionalDecimatorCmd;
 use self::gain_cmd::GainCmd;
+use self::hdlc_cmd::HdlcCmd;
 use self::limit_cmd::LimitCmd;
 use self::load_cmd::LoadCmd;
 use self::load_kiss_cmd::LoadKissCmd;
+use self::macro_cmd::MacroCmd;
+use self::noise_cmd::NoiseCmd;
 use self::octave_complex_cmd::OctaveComplexCmd;
 use self::pack_bits_cmd::PackBitsCmd;
 use self::pattern_search_cmd::PatternSearchCmd;
+use self::psk_modulator_cmd::PskModulatorCmd;
+use self::pwr_squelch_cmd::PwrSquelchCmd;
 use self::rational_resampler_cmd::RationalResamplerCmd;
 use self::realpart_cmd::RealPartCmd;
 use self::save_kiss_cmd::SaveKissCmd;
+use self::scrambler_cmd::ScramblerCmd;
 use self::shift_addition_cmd::ShiftAdditionCmd;
+use self::symbol_sync_cmd::SymbolSyncCmd;
+use self::sync_to_pdu_cmd::SyncToPduCmd;
 use self::tcp_kiss_client_cmd::TcpKissClientCmd;
 use self::tcp_kiss_server_cmd::TcpKissServerCmd;
 use self::throttle_cmd::ThrottleCmd;
+use self::through_cmd::ThroughCmd;
 use self::timing_recovery_cmd::TimingRecoveryCmd;
+use self::tone_cmd::ToneCmd;
 use self::weaver_cmd::WeaverCmd;
 
+mod afc_cmd;
+mod afsk_cmd;
 mod agc_cmd;
 mod amdemod_cmd;
 mod audio_cmd;
+mod ax25_print_cmd;
 mod bandpass_fir_fft_cmd;
 mod binary_slicer;
+mod carrier_recovery_cmd;
+mod channel_model_cmd;
 mod clipdetect_cmd;
+mod constellation_decoder_cmd;
 mod convert_cmd;
 mod deemphasis_nfm_ff_cmd;
 mod deemphasis_wfm_ff_cmd;
+mod diff_coder_cmd;
 mod dsb_cmd;
 mod dump_cmd;
 pub mod eval_cmd;
@@ -55,22 +80,35 @@ mod fastdcblock_cmd;
 mod fir_decimate_cmd;
 mod fixedlen_to_pdu_cmd;
 mod fmdemod_quadri_cmd;
+mod fmmod_cmd;
 mod fractional_decimator_cmd;
 mod gain_cmd;
+mod hdlc_cmd;
 mod limit_cmd;
 mod load_cmd;
 mod load_kiss_cmd;
+pub mod macro_cmd;
+mod noise_cmd;
 mod octave_complex_cmd;
 mod pack_bits_cmd;
 mod pattern_search_cmd;
+mod psk_modulator_cmd;
+mod pwr_squelch_cmd;
 mod rational_resampler_cmd;
 mod realpart_cmd;
 mod save_kiss_cmd;
+mod scrambler_cmd;
+pub mod script;
+pub use self::script::ScriptParser;
 mod shift_addition_cmd;
+mod symbol_sync_cmd;
+mod sync_to_pdu_cmd;
 mod tcp_kiss_client_cmd;
 mod tcp_kiss_server_cmd;
 mod throttle_cmd;
+mod through_cmd;
 mod timing_recovery_cmd;
+mod tone_cmd;
 mod weaver_cmd;
 
 pub trait AnyCmd<'i> {
@@ -80,19 +118,31 @@ pub trait AnyCmd<'i> {
 impl<'i> AnyCmd<'i> for Pair<'i, Rule> {
     fn parse(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
         match self.as_rule() {
+            Rule::additive_scrambler_cmd => self.build_additive_scrambler(grc),
+            Rule::afc_cmd => self.build_afc(grc),
+            Rule::afsk1200_demod_cmd => self.build_afsk1200_demod(grc),
+            Rule::afsk1200_mod_cmd => self.build_afsk1200_mod(grc),
             Rule::agc_cmd => self.build_agc(grc),
             Rule::amdemod_cmd => self.build_amdemod(grc),
             Rule::audio_cmd => self.build_audio_sink(grc),
+            Rule::ax25_print_cmd => self.build_ax25_print(grc),
             Rule::bandpass_fir_fft_cc_cmd => self.build_bandpass_fir_fft_cc(grc),
             Rule::binary_slicer_cmd => self.build_binary_slicer(grc),
+            Rule::bpsk_costas_loop_cmd => self.build_costas_loop(grc),
+            Rule::channel_model_cmd => self.build_channel_model(grc),
             Rule::clipdetect_cmd => self.build_clipdetect(grc),
+            Rule::constellation_decoder_cmd | Rule::constellation_soft_decoder_cmd => {
+                self.build_constellation_decoder(grc)
+            }
             Rule::convert_cmd => self.build_convert(grc),
             Rule::deemphasis_nfm_cmd => self.build_deemphasis_nfm(grc),
             Rule::deemphasis_wfm_cmd => self.build_deemphasis_wfm(grc),
+            Rule::descrambler_cmd => self.build_scrambler(grc, "digital_descrambler_bb"),
+            Rule::diff_coder_cmd | Rule::differential_coder_cmd => self.build_diff_coder(grc),
             Rule::dsb_cmd => self.build_dsb(grc),
             Rule::dump_cmd => self.build_dump(grc),
             Rule::eval_cmd => {
-                self.execute_eval()?;
+                self.execute_eval_with(grc.variables())?;
                 Ok(grc)
             }
             Rule::fastdcblock_cmd => self.build_fastdcblock(grc),
@@ -100,8 +150,12 @@ impl<'i> AnyCmd<'i> for Pair<'i, Rule> {
             Rule::fir_decimate_cmd => self.build_fir_decimate(grc),
             Rule::fmdemod_quadri_cmd => self.build_fm_demod_quadri(grc),
             Rule::fmdemod_atan_cmd => self.build_fm_demod_atan(grc),
+            Rule::fmmod_cmd => self.build_fmmod(grc),
             Rule::gain_cmd => self.build_gain(grc),
+            Rule::hdlc_deframer_cmd => self.build_hdlc_deframer(grc),
+            Rule::hdlc_framer_cmd => self.build_hdlc_framer(grc),
             Rule::limit_cmd => self.build_limit(grc),
+            Rule::noise_cmd => self.build_noise(grc),
             Rule::load_cmd => self.build_load(grc),
             Rule::load_kiss_cmd => self.build_load_kiss(grc),
             Rule::fixedlen_to_pdu_cmd => self.build_fixedlen_to_pdu(grc),
@@ -110,13 +164,30 @@ impl<'i> AnyCmd<'i> for Pair<'i, Rule> {
             Rule::tcp_kiss_client_cmd => self.build_tcp_kiss_client(grc),
             Rule::octave_complex_cmd => self.build_octave_complex(grc),
             Rule::pack_bits_cmd => self.build_pack_bits(grc),
+            Rule::pack_k_bits_cmd => self.build_pack_k_bits(grc, "blocks_pack_k_bits_bb"),
+            Rule::packed_to_unpacked_cmd => {
+                self.build_packed_to_unpacked(grc, "blocks_packed_to_unpacked_xx")
+            }
             Rule::pattern_search_cmd => self.build_pattern_search(grc),
+            Rule::pll_cmd => self.build_pll(grc),
+            Rule::psk_modulator_cmd => self.build_psk_modulator(grc),
+            Rule::pwr_squelch_cmd => self.build_pwr_squelch(grc),
             Rule::rational_resampler_cmd => self.build_rational_resampler(grc),
             Rule::realpart_cmd => self.build_realpart(grc),
+            Rule::scrambler_cmd => self.build_scrambler(grc, "digital_scrambler_bb"),
             Rule::shift_addition_cmd => self.build_shift_addition(grc),
+            Rule::symbol_sync_cmd => self.build_symbol_sync(grc),
+            Rule::sync_to_pdu_cmd => self.build_sync_to_pdu(grc),
             Rule::throttle_cmd => self.build_throttle(grc),
+            Rule::through_cmd => self.build_through(grc),
             Rule::timing_recovery_cmd => self.build_timing_recovery(grc),
+            Rule::tone_cmd => self.build_tone(grc),
+            Rule::unpack_k_bits_cmd => self.build_pack_k_bits(grc, "blocks_unpack_k_bits_bb"),
+            Rule::unpacked_to_packed_cmd => {
+                self.build_packed_to_unpacked(grc, "blocks_unpacked_to_packed_xx")
+            }
             Rule::weaver_lsb_cmd | Rule::weaver_usb_cmd => self.build_weaver(grc),
+            Rule::macro_call => self.build_macro(grc),
 
             Rule::csdr_save_opt => Ok(grc),
             _ => {
@@ -127,6 +198,36 @@ impl<'i> AnyCmd<'i> for Pair<'i, Rule> {
     }
 }
 
+/// Options of a command, eg `--averaging 4096`
+pub(crate) trait CmdOptions<'i> {
+    /// Value of the option `rule`, if given
+    fn option_value(&self, rule: Rule) -> Result<Option<&'i str>>;
+    /// Values of the option `rule` taking several ones, eg `--taps 1, 0.5`, if given
+    fn option_values(&self, rule: Rule) -> Option<Vec<&'i str>>;
+}
+
+impl<'i> CmdOptions<'i> for Pair<'i, Rule> {
+    fn option_value(&self, rule: Rule) -> Result<Option<&'i str>> {
+        match self.clone().into_inner().find(|arg| arg.as_rule() == rule) {
+            Some(option) => Ok(Some(
+                option
+                    .into_inner()
+                    .next()
+                    .with_context(|| format!("value expected for {rule:?}"))?
+                    .as_str(),
+            )),
+            None => Ok(None),
+        }
+    }
+
+    fn option_values(&self, rule: Rule) -> Option<Vec<&'i str>> {
+        self.clone()
+            .into_inner()
+            .find(|arg| arg.as_rule() == rule)
+            .map(|option| option.into_inner().map(|value| value.as_str()).collect())
+    }
+}
+
 pub trait CsdrCmd<'i> {
     fn output(&self) -> Result<Option<&'i str>>;
     fn parse(&self) -> Result<Option<Grc>>;
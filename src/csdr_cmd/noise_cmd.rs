@@ -0,0 +1,54 @@
+use crate::cmd_grammar::Rule;
+use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
+use anyhow::{bail, Context, Result};
+use pest::iterators::Pair;
+
+pub trait NoiseCmd<'i> {
+    fn output_type(&self) -> Result<GrcItemType>;
+    fn amplitude(&self) -> Result<&str>;
+    fn seed(&self) -> &str;
+
+    fn build_noise(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        if grc.last_output_type().is_some() {
+            bail!("noise_c and noise_f must start the pipeline");
+        }
+        let mut grc = grc;
+        let output_type = self.output_type()?;
+        grc = grc
+            .create_block_instance("analog_noise_source_x")
+            .with_parameter("type", output_type.as_grc())
+            .with_parameter("noise_type", "analog.GR_GAUSSIAN")
+            .with_parameter("amp", self.amplitude()?)
+            .with_parameter("seed", self.seed())
+            .assert_output(output_type)
+            .push()?;
+        Ok(grc)
+    }
+}
+
+impl<'i> NoiseCmd<'i> for Pair<'i, Rule> {
+    fn output_type(&self) -> Result<GrcItemType> {
+        let output_type = self
+            .clone()
+            .into_inner()
+            .next()
+            .context("noise output type expected")?;
+        GrcItemType::try_from(output_type.as_str())
+    }
+
+    fn amplitude(&self) -> Result<&'i str> {
+        self.clone()
+            .into_inner()
+            .nth(1)
+            .map(|arg| arg.as_str())
+            .context("missing mandatory <amplitude> parameter for noise")
+    }
+
+    /// The same seed gives the same noise, for reproducible flowgraphs.
+    fn seed(&self) -> &'i str {
+        self.clone()
+            .into_inner()
+            .nth(2)
+            .map_or("0", |arg| arg.as_str())
+    }
+}
@@ -1,19 +1,97 @@
 use crate::cmd_grammar::Rule;
 use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use pest::iterators::Pair;
 
 pub trait PackBitsCmd<'i> {
-    fn build_pack_bits(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+    fn bits(&self) -> Result<&'i str>;
+    fn endianness(&self) -> &'static str;
+
+    /// csdr's `pack_bits_8to1_u8_u8`, most significant bit first, and
+    /// `pack_bits_1to8_u8_u8`, least significant bit first
+    fn build_pack_bits(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>>;
+
+    /// `blocks_pack_k_bits_bb` or `blocks_unpack_k_bits_bb`
+    fn build_pack_k_bits(
+        &self,
+        grc: GrcBuilder<GraphLevel>,
+        block_type: &str,
+    ) -> Result<GrcBuilder<GraphLevel>> {
+        let mut grc = grc;
+        grc = grc
+            .ensure_source(GrcItemType::U8)?
+            .create_block_instance(block_type)
+            .with_parameter("k", self.bits()?)
+            .assert_output(GrcItemType::U8)
+            .push_and_link()?;
+        Ok(grc)
+    }
+
+    /// `blocks_packed_to_unpacked_xx` or `blocks_unpacked_to_packed_xx`
+    fn build_packed_to_unpacked(
+        &self,
+        grc: GrcBuilder<GraphLevel>,
+        block_type: &str,
+    ) -> Result<GrcBuilder<GraphLevel>> {
         let mut grc = grc;
         grc = grc
             .ensure_source(GrcItemType::U8)?
-            .create_block_instance("blocks_pack_k_bits_bb")
-            .with_parameter("k", "8")
+            .create_block_instance(block_type)
+            .with_parameter("type", "byte")
+            .with_parameter("bits_per_chunk", self.bits()?)
+            .with_parameter("endianness", self.endianness())
             .assert_output(GrcItemType::U8)
             .push_and_link()?;
         Ok(grc)
     }
 }
 
-impl<'i> PackBitsCmd<'i> for Pair<'i, Rule> {}
+impl<'i> PackBitsCmd<'i> for Pair<'i, Rule> {
+    fn bits(&self) -> Result<&'i str> {
+        Ok(self
+            .clone()
+            .into_inner()
+            .next()
+            .context("number of bits expected")?
+            .as_str())
+    }
+
+    fn endianness(&self) -> &'static str {
+        if self
+            .clone()
+            .into_inner()
+            .any(|arg| arg.as_rule() == Rule::bits_lsb_opt)
+        {
+            "gr.GR_LSB_FIRST"
+        } else {
+            "gr.GR_MSB_FIRST"
+        }
+    }
+
+    fn build_pack_bits(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        let direction = self
+            .clone()
+            .into_inner()
+            .next()
+            .context("pack_bits: 8to1 or 1to8 expected")?;
+        let mut grc = grc;
+        grc = match direction.as_str() {
+            "8to1" => grc
+                .ensure_source(GrcItemType::U8)?
+                .create_block_instance("blocks_pack_k_bits_bb")
+                .with_parameter("k", "8")
+                .assert_output(GrcItemType::U8)
+                .push_and_link()?,
+            "1to8" => grc
+                .ensure_source(GrcItemType::U8)?
+                .create_block_instance("blocks_packed_to_unpacked_xx")
+                .with_parameter("type", "byte")
+                .with_parameter("bits_per_chunk", "1")
+                .with_parameter("endianness", "gr.GR_LSB_FIRST")
+                .assert_output(GrcItemType::U8)
+                .push_and_link()?,
+            other => bail!("pack_bits: unknown direction {other}"),
+        };
+        Ok(grc)
+    }
+}
@@ -1,22 +1,33 @@
+use super::CmdOptions;
 use crate::cmd_grammar::Rule;
 use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
 use anyhow::{bail, Result};
 use pest::iterators::Pair;
 
-pub trait PatternSearchCmd<'i> {
+pub trait PatternSearchCmd<'i>: CmdOptions<'i> {
     fn values_after(&self) -> Result<&'i str>;
 
     fn pattern_values(&self) -> Result<Vec<&'i str>>;
 
+    fn is_inverted(&self) -> bool;
+
     fn build_pattern_search(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
         let mut grc = grc;
-        grc = grc
-            .ensure_source(GrcItemType::U8)?
-            .create_block_instance("pattern_search")
-            .with_parameter("values_after", self.values_after()?)
-            .with_parameter("pattern_values", self.pattern_values()?.join(","))
-            .assert_output(GrcItemType::U8)
-            .push_and_link()?;
+        grc = grc.ensure_source(GrcItemType::U8)?;
+        let mut blk = grc.create_block_instance("pattern_search");
+        blk.with_parameter("values_after", self.values_after()?)
+            .with_parameter("pattern_values", self.pattern_values()?.join(","));
+        let threshold = self.option_value(Rule::pattern_search_threshold_param)?;
+        if let Some(threshold) = threshold {
+            blk.with_parameter("threshold", threshold);
+        }
+        if self.is_inverted() {
+            if threshold.is_none() {
+                blk.with_parameter("threshold", "0");
+            }
+            blk.with_parameter("invert", "True");
+        }
+        grc = blk.assert_output(GrcItemType::U8).push_and_link()?;
         Ok(grc)
     }
 }
@@ -34,11 +45,25 @@ impl<'i> PatternSearchCmd<'i> for Pair<'i, Rule> {
     fn pattern_values(&self) -> Result<Vec<&'i str>> {
         let mut inner = self.clone().into_inner();
         inner.next();
-        let preamble_data: Vec<&'i str> = inner.map(|x| x.as_str()).collect();
+        let preamble_data: Vec<&'i str> = inner
+            .filter(|x| {
+                !matches!(
+                    x.as_rule(),
+                    Rule::pattern_search_threshold_param | Rule::pattern_search_invert_opt
+                )
+            })
+            .map(|x| x.as_str())
+            .collect();
         if preamble_data.is_empty() {
             bail!("missing mandatory <pattern_values> parameters for pattern_search_u8_u8")
         } else {
             Ok(preamble_data)
         }
     }
+
+    fn is_inverted(&self) -> bool {
+        self.clone()
+            .into_inner()
+            .any(|x| x.as_rule() == Rule::pattern_search_invert_opt)
+    }
 }
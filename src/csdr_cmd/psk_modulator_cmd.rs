@@ -0,0 +1,62 @@
+use crate::cmd_grammar::Rule;
+use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
+use anyhow::{Context, Result};
+use pest::iterators::Pair;
+
+pub trait PskModulatorCmd<'i> {
+    fn constellation(&self) -> Result<&str>;
+    fn samples_per_symbol(&self) -> Result<&str>;
+    fn excess_bw(&self) -> Option<&str>;
+    fn is_differential(&self) -> bool;
+
+    fn build_psk_modulator(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        let differential = if self.is_differential() {
+            "True"
+        } else {
+            "False"
+        };
+        let mut grc = grc;
+        grc = grc
+            .ensure_source(GrcItemType::U8)?
+            .create_block_instance("digital_constellation_modulator")
+            .with_parameter("constellation", self.constellation()?)
+            .with_parameter("differential", differential)
+            .with_parameter("samples_per_symbol", self.samples_per_symbol()?)
+            .with_parameter("excess_bw", self.excess_bw().unwrap_or("0.35"))
+            .assert_output(GrcItemType::C32)
+            .push_and_link()?;
+        Ok(grc)
+    }
+}
+
+impl<'i> PskModulatorCmd<'i> for Pair<'i, Rule> {
+    fn constellation(&self) -> Result<&'i str> {
+        self.clone()
+            .into_inner()
+            .next()
+            .map(|arg| arg.as_str())
+            .context("missing mandatory <constellation> parameter for psk_modulator")
+    }
+
+    fn samples_per_symbol(&self) -> Result<&'i str> {
+        self.clone()
+            .into_inner()
+            .nth(1)
+            .map(|arg| arg.as_str())
+            .context("missing mandatory <samples_per_symbol> parameter for psk_modulator")
+    }
+
+    fn excess_bw(&self) -> Option<&'i str> {
+        self.clone()
+            .into_inner()
+            .nth(2)
+            .filter(|arg| arg.as_rule() != Rule::differential_opt)
+            .map(|arg| arg.as_str())
+    }
+
+    fn is_differential(&self) -> bool {
+        self.clone()
+            .into_inner()
+            .any(|arg| arg.as_rule() == Rule::differential_opt)
+    }
+}
@@ -0,0 +1,78 @@
+use crate::cmd_grammar::Rule;
+use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
+use anyhow::{bail, Context, Result};
+use pest::iterators::Pair;
+
+pub trait PwrSquelchCmd<'i> {
+    fn item_type(&self) -> Result<GrcItemType>;
+    fn threshold(&self) -> Result<&str>;
+    fn fifo(&self) -> Result<Option<&str>>;
+    fn gate(&self) -> bool;
+
+    fn build_pwr_squelch(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        let mut grc = grc;
+        let item_type = self.item_type()?;
+        let fifo = self.fifo()?;
+        let threshold = if fifo.is_some() {
+            // Open until the first threshold is read
+            "-1000"
+        } else {
+            self.threshold()?
+        };
+        grc = grc
+            .ensure_source(item_type)?
+            .create_block_instance("analog_pwr_squelch_xx")
+            .with_parameter("type", item_type.as_grc())
+            .with_parameter("threshold", threshold)
+            .with_parameter("alpha", "1e-4")
+            .with_parameter("ramp", "0")
+            .with_parameter("gate", if self.gate() { "True" } else { "False" })
+            .assert_output(item_type)
+            .push_and_link()?;
+        if let Some(fifo) = fifo {
+            grc = grc
+                .create_block_instance("fifo_reader")
+                .with_parameter("file", fifo)
+                .push_as_message_source("threshold")?;
+        }
+        Ok(grc)
+    }
+}
+
+impl<'i> PwrSquelchCmd<'i> for Pair<'i, Rule> {
+    fn item_type(&self) -> Result<GrcItemType> {
+        let item_type = self
+            .clone()
+            .into_inner()
+            .next()
+            .context("pwr_squelch: cc or ff expected")?;
+        match item_type.as_str() {
+            "cc" => Ok(GrcItemType::C32),
+            "ff" => Ok(GrcItemType::F32),
+            other => bail!("pwr_squelch: unknown type {other}"),
+        }
+    }
+
+    fn threshold(&self) -> Result<&'i str> {
+        match self.clone().into_inner().nth(1) {
+            Some(threshold) if threshold.as_rule() != Rule::fifo_opt => Ok(threshold.as_str()),
+            _ => bail!("missing mandatory <threshold_db> parameter for pwr_squelch"),
+        }
+    }
+
+    fn fifo(&self) -> Result<Option<&'i str>> {
+        match self.clone().into_inner().nth(1) {
+            Some(opt) if opt.as_rule() == Rule::fifo_opt => {
+                let path = opt.into_inner().next().context("fifo filepath expected")?;
+                Ok(Some(path.as_str()))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn gate(&self) -> bool {
+        self.clone()
+            .into_inner()
+            .any(|arg| arg.as_rule() == Rule::squelch_gate_opt)
+    }
+}
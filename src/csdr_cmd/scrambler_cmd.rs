@@ -0,0 +1,86 @@
+use super::CmdOptions;
+use crate::blocks::Lfsr;
+use crate::cmd_grammar::Rule;
+use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
+use anyhow::{anyhow, bail, Context, Result};
+use pest::iterators::Pair;
+
+pub trait ScramblerCmd<'i>: CmdOptions<'i> {
+    /// Mask, seed and length of the shift register
+    fn lfsr(&self) -> Result<(String, String, String)>;
+
+    /// `digital_scrambler_bb` or `digital_descrambler_bb`
+    fn build_scrambler(
+        &self,
+        grc: GrcBuilder<GraphLevel>,
+        block_type: &str,
+    ) -> Result<GrcBuilder<GraphLevel>> {
+        let (mask, seed, len) = self.lfsr()?;
+        let mut grc = grc;
+        grc = grc
+            .ensure_source(GrcItemType::U8)?
+            .create_block_instance(block_type)
+            .with_parameter("mask", mask)
+            .with_parameter("seed", seed)
+            .with_parameter("len", len)
+            .assert_output(GrcItemType::U8)
+            .push_and_link()?;
+        Ok(grc)
+    }
+
+    fn build_additive_scrambler(
+        &self,
+        grc: GrcBuilder<GraphLevel>,
+    ) -> Result<GrcBuilder<GraphLevel>> {
+        let (mask, seed, len) = self.lfsr()?;
+        let mut grc = grc;
+        grc = grc
+            .ensure_source(GrcItemType::U8)?
+            .create_block_instance("digital_additive_scrambler_bb")
+            .with_parameter("mask", mask)
+            .with_parameter("seed", seed)
+            .with_parameter("len", len)
+            .with_parameter(
+                "count",
+                self.option_value(Rule::scrambler_count_param)?
+                    .unwrap_or("0"),
+            )
+            .with_parameter(
+                "bits_per_byte",
+                self.option_value(Rule::scrambler_bits_param)?
+                    .unwrap_or("1"),
+            )
+            .assert_output(GrcItemType::U8)
+            .push_and_link()?;
+        Ok(grc)
+    }
+}
+
+impl<'i> ScramblerCmd<'i> for Pair<'i, Rule> {
+    fn lfsr(&self) -> Result<(String, String, String)> {
+        let arg = self
+            .clone()
+            .into_inner()
+            .next()
+            .context("scrambler: <mask> <seed> <len> expected")?;
+        let (mask, seed, len) = match arg.as_rule() {
+            Rule::scrambler_g3ruh_opt => Lfsr::G3RUH,
+            Rule::scrambler_ccsds_opt => Lfsr::CCSDS,
+            Rule::lfsr_params => {
+                let params: Vec<String> = arg
+                    .into_inner()
+                    .map(|param| param.as_str().to_string())
+                    .collect();
+                let [mask, seed, len] = <[String; 3]>::try_from(params)
+                    .map_err(|_| anyhow!("scrambler: <mask> <seed> <len> expected"))?;
+                return Ok((mask, seed, len));
+            }
+            rule => bail!("scrambler: unexpected {rule:?}"),
+        };
+        Ok((
+            format!("0x{mask:X}"),
+            format!("0x{seed:X}"),
+            len.to_string(),
+        ))
+    }
+}
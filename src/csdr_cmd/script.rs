@@ -0,0 +1,159 @@
+use super::eval_cmd::EvalCmd;
+use super::macro_cmd::parse_macro_def;
+use super::AnyCmd;
+use crate::cmd_grammar::{CommandsParser, Rule};
+use crate::grc::builder::{GraphLevel, GrcBuilder};
+use crate::grc::Grc;
+use anyhow::{bail, Context, Result};
+use pest::iterators::Pair;
+use pest::Parser;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Load pipeline scripts (`fsdr-cli run pipeline.fsdr`).
+///
+/// A script is a csdr pipeline spread over several lines, with:
+/// * `#` comments,
+/// * `\` at the end of a line to continue on next one,
+/// * `let name = expr` variables usable in any later parameter,
+/// * `macro name param1 param2 = cmd1 ! cmd2` macros usable as later commands,
+/// * `include other.fsdr`, relative to the including script.
+#[derive(Default)]
+pub struct ScriptParser {
+    /// Scripts being loaded, to detect include cycles
+    stack: Vec<PathBuf>,
+}
+
+impl ScriptParser {
+    pub fn load(path: impl AsRef<Path>) -> Result<Grc> {
+        let mut parser = ScriptParser::default();
+        let mut grc = parser.include(path.as_ref(), GrcBuilder::new())?;
+        grc.ensure_sink()?;
+        grc.build()
+    }
+
+    pub fn parse(script: &str) -> Result<Grc> {
+        let mut parser = ScriptParser::default();
+        let mut grc = parser.parse_script(script, Path::new("."), GrcBuilder::new())?;
+        grc.ensure_sink()?;
+        grc.build()
+    }
+
+    /// Append a single line to a pipeline in progress, eg from the REPL.
+    /// Included scripts are relative to the current directory.
+    pub fn parse_line(
+        &mut self,
+        line: &str,
+        grc: GrcBuilder<GraphLevel>,
+    ) -> Result<GrcBuilder<GraphLevel>> {
+        let mut grc = grc;
+        let pairs = CommandsParser::parse(Rule::script_line, line).context("syntax error")?;
+        for pair in pairs {
+            grc = self.parse_statement(&pair, Path::new("."), grc)?;
+        }
+        Ok(grc)
+    }
+
+    fn include(
+        &mut self,
+        path: &Path,
+        grc: GrcBuilder<GraphLevel>,
+    ) -> Result<GrcBuilder<GraphLevel>> {
+        let canonical = fs::canonicalize(path)
+            .with_context(|| format!("cannot find script {}", path.display()))?;
+        if self.stack.contains(&canonical) {
+            bail!("include cycle on {}", path.display());
+        }
+        let script = fs::read_to_string(&canonical)
+            .with_context(|| format!("cannot read script {}", path.display()))?;
+        let base_dir = canonical
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        self.stack.push(canonical);
+        let grc = self
+            .parse_script(&script, &base_dir, grc)
+            .with_context(|| format!("in script {}", path.display()));
+        self.stack.pop();
+        grc
+    }
+
+    fn parse_script(
+        &mut self,
+        script: &str,
+        base_dir: &Path,
+        grc: GrcBuilder<GraphLevel>,
+    ) -> Result<GrcBuilder<GraphLevel>> {
+        let mut grc = grc;
+        for (line_number, line) in Self::logical_lines(script) {
+            let pairs = CommandsParser::parse(Rule::script_line, &line)
+                .with_context(|| format!("line {line_number}: syntax error"))?;
+            for pair in pairs {
+                grc = self
+                    .parse_statement(&pair, base_dir, grc)
+                    .with_context(|| format!("line {line_number}: {}", pair.as_str()))?;
+            }
+        }
+        Ok(grc)
+    }
+
+    fn parse_statement(
+        &mut self,
+        pair: &Pair<'_, Rule>,
+        base_dir: &Path,
+        grc: GrcBuilder<GraphLevel>,
+    ) -> Result<GrcBuilder<GraphLevel>> {
+        let mut grc = grc;
+        match pair.as_rule() {
+            Rule::EOI => {}
+            Rule::let_stmt => {
+                let mut inner = pair.clone().into_inner();
+                let name = inner.next().context("variable name expected")?.as_str();
+                let expr = inner.next().context("variable value expected")?;
+                let value = expr.eval_with(grc.variables())?;
+                grc.with_variable(name, expr.as_str(), value)?;
+            }
+            Rule::macro_def => {
+                let (name, definition) = parse_macro_def(pair)?;
+                grc.with_macro(name, definition)?;
+            }
+            Rule::include_stmt => {
+                let filename = pair
+                    .clone()
+                    .into_inner()
+                    .next()
+                    .context("included filepath expected")?
+                    .as_str();
+                grc = self.include(&base_dir.join(filename), grc)?;
+            }
+            _ => {
+                grc = AnyCmd::parse(pair, grc)?;
+            }
+        }
+        Ok(grc)
+    }
+
+    /// Join continued lines, returning each logical line with the number of its first line.
+    fn logical_lines(script: &str) -> Vec<(usize, String)> {
+        let mut lines = Vec::new();
+        let mut current = String::new();
+        let mut first_line_number = 1;
+        for (index, line) in script.lines().enumerate() {
+            if current.is_empty() {
+                first_line_number = index + 1;
+            }
+            let line = line.trim_end();
+            if let Some(line) = line.strip_suffix('\\') {
+                current.push_str(line);
+                current.push(' ');
+            } else {
+                current.push_str(line);
+                lines.push((first_line_number, std::mem::take(&mut current)));
+            }
+        }
+        if !current.is_empty() {
+            lines.push((first_line_number, current));
+        }
+        lines
+    }
+}
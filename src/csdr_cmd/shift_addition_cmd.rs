@@ -1,14 +1,21 @@
 use crate::cmd_grammar::Rule;
 use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use pest::iterators::Pair;
 
 pub trait ShiftAdditionCmd<'i> {
     fn phase_rate(&self) -> Result<&str>;
+    fn fifo(&self) -> Result<Option<&str>>;
 
     fn build_shift_addition(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
         let mut grc = grc;
-        let phase_rate = self.phase_rate()?;
+        let fifo = self.fifo()?;
+        let phase_rate = if fifo.is_some() {
+            // csdr waits for the first value, we simply start without shifting
+            "0"
+        } else {
+            self.phase_rate()?
+        };
         grc = grc
             .ensure_source(GrcItemType::C32)?
             .create_block_instance("blocks_freqshift_cc")
@@ -16,6 +23,12 @@ pub trait ShiftAdditionCmd<'i> {
             .with_parameter("sample_rate", "1.0")
             .assert_output(GrcItemType::C32)
             .push_and_link()?;
+        if let Some(fifo) = fifo {
+            grc = grc
+                .create_block_instance("fifo_reader")
+                .with_parameter("file", fifo)
+                .push_as_message_source("freq")?;
+        }
         Ok(grc)
     }
 }
@@ -23,9 +36,22 @@ pub trait ShiftAdditionCmd<'i> {
 impl<'i> ShiftAdditionCmd<'i> for Pair<'i, Rule> {
     fn phase_rate(&self) -> Result<&'i str> {
         if let Some(rate) = self.clone().into_inner().next() {
+            if rate.as_rule() == Rule::fifo_opt {
+                bail!("no <rate> parameter when reading from a fifo for shift_addition_cc")
+            }
             Ok(rate.as_str())
         } else {
             bail!("missing mandatory <rate> parameters for shift_addition_cc")
         }
     }
+
+    fn fifo(&self) -> Result<Option<&'i str>> {
+        match self.clone().into_inner().next() {
+            Some(opt) if opt.as_rule() == Rule::fifo_opt => {
+                let path = opt.into_inner().next().context("fifo filepath expected")?;
+                Ok(Some(path.as_str()))
+            }
+            _ => Ok(None),
+        }
+    }
 }
@@ -0,0 +1,79 @@
+use super::CmdOptions;
+use crate::cmd_grammar::Rule;
+use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
+use anyhow::{Context, Result};
+use pest::iterators::Pair;
+
+pub trait SymbolSyncCmd<'i>: CmdOptions<'i> {
+    fn item_type(&self) -> Result<GrcItemType>;
+    fn samples_per_symbol(&self) -> Result<&str>;
+
+    fn build_symbol_sync(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        let item_type = self.item_type()?;
+        let grc_type = match item_type {
+            GrcItemType::C32 => "cc",
+            _ => "ff",
+        };
+        let ted_type = self
+            .param(Rule::symbol_sync_ted_param)?
+            .unwrap_or("gardner");
+        let mut grc = grc;
+        grc = grc
+            .ensure_source(item_type)?
+            .create_block_instance("digital_symbol_sync_xx")
+            .with_parameter("type", grc_type)
+            .with_parameter("ted_type", ted_type)
+            .with_parameter("sps", self.samples_per_symbol()?)
+            .with_parameter(
+                "loop_bw",
+                self.option_value(Rule::symbol_sync_loop_bw_param)?
+                    .unwrap_or("0.045"),
+            )
+            .with_parameter(
+                "damping",
+                self.option_value(Rule::symbol_sync_damping_param)?
+                    .unwrap_or("1.0"),
+            )
+            .with_parameter(
+                "ted_gain",
+                self.option_value(Rule::symbol_sync_ted_gain_param)?
+                    .unwrap_or("1.0"),
+            )
+            .with_parameter(
+                "max_dev",
+                self.option_value(Rule::symbol_sync_max_dev_param)?
+                    .unwrap_or("1.5"),
+            )
+            .with_parameter("osps", "1")
+            .with_parameter(
+                "constellation",
+                self.option_value(Rule::symbol_sync_constellation_param)?
+                    .unwrap_or("bpsk"),
+            )
+            .assert_output(item_type)
+            .push_and_link()?;
+        Ok(grc)
+    }
+}
+
+impl<'i> SymbolSyncCmd<'i> for Pair<'i, Rule> {
+    fn item_type(&self) -> Result<GrcItemType> {
+        let item_type = self
+            .clone()
+            .into_inner()
+            .next()
+            .context("symbol_sync item type expected")?;
+        match item_type.as_str() {
+            "cc" => Ok(GrcItemType::C32),
+            _ => Ok(GrcItemType::F32),
+        }
+    }
+
+    fn samples_per_symbol(&self) -> Result<&'i str> {
+        self.clone()
+            .into_inner()
+            .nth(1)
+            .map(|arg| arg.as_str())
+            .context("missing mandatory <samples_per_symbol> parameter for symbol_sync")
+    }
+}
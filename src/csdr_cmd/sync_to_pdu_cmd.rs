@@ -0,0 +1,62 @@
+use crate::cmd_grammar::Rule;
+use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
+use anyhow::{bail, Context, Result};
+use pest::iterators::Pair;
+
+pub trait SyncToPduCmd<'i> {
+    fn input_type(&self) -> Result<&'i str>;
+    fn packet_len(&self) -> Result<&'i str>;
+    fn sync_word(&self) -> Result<&'i str>;
+    fn threshold(&self) -> Option<&'i str>;
+
+    fn build_sync_to_pdu(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        let (block_type, item_type) = match self.input_type()? {
+            "u8" => ("satellites_sync_to_pdu", GrcItemType::U8),
+            "packed_u8" => ("satellites_sync_to_pdu_packed", GrcItemType::U8),
+            "f" => ("satellites_sync_to_pdu_soft", GrcItemType::F32),
+            other => bail!("sync_to_pdu: unknown input type {other}"),
+        };
+        let mut grc = grc;
+        grc = grc
+            .ensure_source(item_type)?
+            .create_block_instance(block_type)
+            .with_parameter("packlen", self.packet_len()?)
+            .with_parameter("sync", self.sync_word()?)
+            .with_parameter("threshold", self.threshold().unwrap_or("0"))
+            .push_and_link()?;
+        Ok(grc)
+    }
+}
+
+impl<'i> SyncToPduCmd<'i> for Pair<'i, Rule> {
+    fn input_type(&self) -> Result<&'i str> {
+        Ok(self
+            .clone()
+            .into_inner()
+            .next()
+            .context("sync_to_pdu: input type expected")?
+            .as_str())
+    }
+
+    fn packet_len(&self) -> Result<&'i str> {
+        Ok(self
+            .clone()
+            .into_inner()
+            .nth(1)
+            .context("sync_to_pdu: packet length expected")?
+            .as_str())
+    }
+
+    fn sync_word(&self) -> Result<&'i str> {
+        Ok(self
+            .clone()
+            .into_inner()
+            .find(|arg| arg.as_rule() == Rule::sync_word)
+            .context("sync_to_pdu: sync word expected")?
+            .as_str())
+    }
+
+    fn threshold(&self) -> Option<&'i str> {
+        self.clone().into_inner().nth(3).map(|arg| arg.as_str())
+    }
+}
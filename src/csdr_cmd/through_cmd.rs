@@ -0,0 +1,31 @@
+use crate::cmd_grammar::Rule;
+use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
+use anyhow::Result;
+use pest::iterators::Pair;
+
+pub trait ThroughCmd<'i> {
+    fn item_type(&self, grc: &GrcBuilder<GraphLevel>) -> Result<GrcItemType>;
+
+    fn build_through(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        let mut grc = grc;
+        let item_type = self.item_type(&grc)?;
+        grc = grc
+            .ensure_source(item_type)?
+            .create_block_instance("through")
+            .with_parameter("type", item_type.as_grc())
+            .assert_output(item_type)
+            .push_and_link()?;
+        Ok(grc)
+    }
+}
+
+impl<'i> ThroughCmd<'i> for Pair<'i, Rule> {
+    /// `pv_c`, `pv_f` or `pv_u8` set the type, while `through` keeps the one of the previous command.
+    fn item_type(&self, grc: &GrcBuilder<GraphLevel>) -> Result<GrcItemType> {
+        if let Some(item_type) = self.clone().into_inner().next() {
+            GrcItemType::try_from(item_type.as_str())
+        } else {
+            Ok(grc.last_output_type().unwrap_or(GrcItemType::U8))
+        }
+    }
+}
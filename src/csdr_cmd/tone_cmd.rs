@@ -0,0 +1,64 @@
+use crate::cmd_grammar::Rule;
+use crate::grc::builder::{GraphLevel, GrcBuilder, GrcItemType};
+use anyhow::{bail, Context, Result};
+use pest::iterators::Pair;
+
+pub trait ToneCmd<'i> {
+    fn output_type(&self) -> Result<GrcItemType>;
+    fn frequency(&self) -> Result<&str>;
+    fn sample_rate(&self) -> Result<&str>;
+    fn amplitude(&self) -> &str;
+
+    fn build_tone(&self, grc: GrcBuilder<GraphLevel>) -> Result<GrcBuilder<GraphLevel>> {
+        if grc.last_output_type().is_some() {
+            bail!("tone_c and tone_f must start the pipeline");
+        }
+        let mut grc = grc;
+        let output_type = self.output_type()?;
+        grc = grc
+            .create_block_instance("analog_sig_source_x")
+            .with_parameter("type", output_type.as_grc())
+            .with_parameter("waveform", "analog.GR_COS_WAVE")
+            .with_parameter("freq", self.frequency()?)
+            .with_parameter("samp_rate", self.sample_rate()?)
+            .with_parameter("amp", self.amplitude())
+            .with_parameter("offset", "0")
+            .assert_output(output_type)
+            .push()?;
+        Ok(grc)
+    }
+}
+
+impl<'i> ToneCmd<'i> for Pair<'i, Rule> {
+    fn output_type(&self) -> Result<GrcItemType> {
+        let output_type = self
+            .clone()
+            .into_inner()
+            .next()
+            .context("tone output type expected")?;
+        GrcItemType::try_from(output_type.as_str())
+    }
+
+    fn frequency(&self) -> Result<&'i str> {
+        self.clone()
+            .into_inner()
+            .nth(1)
+            .map(|arg| arg.as_str())
+            .context("missing mandatory <frequency> parameter for tone")
+    }
+
+    fn sample_rate(&self) -> Result<&'i str> {
+        self.clone()
+            .into_inner()
+            .nth(2)
+            .map(|arg| arg.as_str())
+            .context("missing mandatory <sample_rate> parameter for tone")
+    }
+
+    fn amplitude(&self) -> &'i str {
+        self.clone()
+            .into_inner()
+            .nth(3)
+            .map_or("1.0", |arg| arg.as_str())
+    }
+}
@@ -1,3 +1,5 @@
+use crate::csdr_cmd::eval_cmd::Variables;
+use crate::csdr_cmd::macro_cmd::{Macro, Macros};
 use crate::grc::{BlockInstance, Grc, Metadata, Options, States};
 use anyhow::{bail, Context, Result};
 use std::collections::BTreeMap;
@@ -107,6 +109,10 @@ pub struct GrcBuilderActualState {
     connections: Vec<[String; 4]>,
     last_output_type: Option<GrcItemType>,
     last_block_name: Option<String>,
+    variables: Variables,
+    macros: Macros,
+    /// Number of macros being expanded, to detect infinite recursion
+    macro_depth: usize,
 }
 
 #[derive(Clone)]
@@ -130,6 +136,9 @@ impl GrcBuilder<GraphLevel> {
             connections: Vec::<[String; 4]>::new(),
             last_output_type: None,
             last_block_name: None,
+            variables: Variables::new(),
+            macros: Macros::new(),
+            macro_depth: 0,
         };
         let actual_state = Box::new(actual_state);
         GrcBuilder {
@@ -216,6 +225,66 @@ impl GrcBuilder<GraphLevel> {
         Ok(self)
     }
 
+    /// Declare a GRC `variable` block that can be used in expressions of next blocks' parameters.
+    /// `value` is the already evaluated `expr`, eg to be used by `=` commands.
+    pub fn with_variable(
+        &mut self,
+        name: impl Into<String>,
+        expr: impl Into<String>,
+        value: f64,
+    ) -> Result<&mut Self> {
+        let name = name.into();
+        if self.state.variables.contains_key(&name) {
+            bail!("variable {name} is already defined");
+        }
+        let mut variable = GrcBlockInstanceBuilder::new();
+        variable
+            .with_name(name.clone())
+            .with_block_type("variable")
+            .with_parameter("value", expr);
+        self.state.blocks.push(variable.build()?);
+        self.state.block_count += 1;
+        self.state.variables.insert(name, value);
+        Ok(self)
+    }
+
+    /// Type of the items produced by the last command, if any
+    pub fn last_output_type(&self) -> Option<GrcItemType> {
+        self.state.last_output_type
+    }
+
+    pub fn variables(&self) -> &Variables {
+        &self.state.variables
+    }
+
+    /// Declare a macro usable as a command in the rest of the pipeline.
+    pub fn with_macro(&mut self, name: impl Into<String>, definition: Macro) -> Result<&mut Self> {
+        let name = name.into();
+        if self.state.macros.contains_key(&name) {
+            bail!("macro {name} is already defined");
+        }
+        self.state.macros.insert(name, definition);
+        Ok(self)
+    }
+
+    pub fn macros(&self) -> &Macros {
+        &self.state.macros
+    }
+
+    pub fn macro_definition(&self, name: &str) -> Option<&Macro> {
+        self.state.macros.get(name)
+    }
+
+    /// Return the current depth of nested macro expansions, including the new one.
+    pub fn enter_macro(&mut self) -> usize {
+        self.state.macro_depth += 1;
+        self.state.macro_depth
+    }
+
+    pub fn exit_macro(&mut self) {
+        self.state.macro_depth = self.state.macro_depth.saturating_sub(1);
+    }
+
     pub fn create_block_instance(&self, block_type: impl Into<String>) -> GrcBuilder<BlockLevel> {
         let mut block_builder = GrcBlockInstanceBuilder::new();
         block_builder.with_block_type(block_type);
@@ -294,6 +363,43 @@ impl GrcBuilder<BlockLevel> {
         grc_builder.push_and_link_block(&mut blk_builder)?;
         Ok(grc_builder)
     }
+
+    /// Push a block whose output messages feed the `tgt_port_name` message input of the last block.
+    /// The new block also reads the stream output of the last block, typed by its `type`
+    /// parameter, to finish with it.
+    /// The stream chain is left untouched so that next commands still link to the last block.
+    pub fn push_as_message_source(
+        &self,
+        tgt_port_name: impl Into<String>,
+    ) -> Result<GrcBuilder<GraphLevel>> {
+        let mut blk_builder = self.extra.block_builder.clone();
+        let item_type = self
+            .state
+            .last_output_type
+            .context("No stream to follow for the message source")?;
+        blk_builder.with_parameter("type", item_type.as_grc());
+        let gl = GraphLevel {};
+        let mut grc_builder = GrcBuilder {
+            state: self.state.clone(),
+            extra: gl,
+        };
+        let target_block_name = grc_builder
+            .state
+            .last_block_name
+            .clone()
+            .context("No block to send messages to")?;
+        let last_output_type = grc_builder.state.last_output_type;
+        grc_builder.push_block(&mut blk_builder)?;
+        let this_block_name = grc_builder
+            .state
+            .last_block_name
+            .replace(target_block_name.clone())
+            .context("Current block name not set")?;
+        grc_builder.state.last_output_type = last_output_type;
+        grc_builder.connect(target_block_name.clone(), "0", this_block_name.clone(), "0");
+        grc_builder.connect(this_block_name, "out", target_block_name, tgt_port_name);
+        Ok(grc_builder)
+    }
 }
 
 #[derive(Clone)]
@@ -0,0 +1,32 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::{Afc, AfcReport};
+use anyhow::{Context, Result};
+use futuresdr::runtime::Flowgraph;
+
+/// Not a GNU Radio block: automatic frequency correction of `afc_cc`
+pub struct AfcConverter {}
+
+impl BlockConverter for AfcConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let averaging = Grc2FutureSdr::parameter_as_f64(blk, "averaging", "4096")?;
+        let report = blk
+            .parameter_or("report", "True")
+            .to_lowercase()
+            .parse::<bool>()
+            .context("afc_cc: report")?;
+        let report = match (report, blk.parameter("samp_rate")) {
+            (false, _) => AfcReport::None,
+            (true, Some(_)) => AfcReport::Hertz {
+                sample_rate: Grc2FutureSdr::parameter_as_f64(blk, "samp_rate", "0")?,
+            },
+            (true, None) => AfcReport::Normalized { interval: 65536 },
+        };
+        let blk = fg.add_block(Afc::new(averaging, report));
+        Ok(Box::new(DefaultPortAdapter::new(blk.into())))
+    }
+}
@@ -0,0 +1,31 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter};
+use super::digital_hdlc_framer_pb::HdlcFramerPortAdapter;
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::AfskModulator;
+use anyhow::Result;
+use futuresdr::prelude::DefaultCpuWriter;
+use futuresdr::runtime::Flowgraph;
+
+/// Not in GNU Radio nor gr-satellites: AX.25 frames to Bell 202 audio by default
+pub struct AfskModulatorConverter {}
+
+impl BlockConverter for AfskModulatorConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let samp_rate = Grc2FutureSdr::parameter_as_f64(blk, "samp_rate", "48000")?;
+        let baudrate = Grc2FutureSdr::parameter_as_f64(blk, "baudrate", "1200")?;
+        let mark = Grc2FutureSdr::parameter_as_f64(blk, "mark", "1200")?;
+        let space = Grc2FutureSdr::parameter_as_f64(blk, "space", "2200")?;
+        let preamble = Grc2FutureSdr::parameter_as_f32(blk, "preamble", "32")? as usize;
+        let tail = Grc2FutureSdr::parameter_as_f32(blk, "tail", "3")? as usize;
+        let block = AfskModulator::<DefaultCpuWriter<f32>>::new(
+            samp_rate, baudrate, mark, space, preamble, tail,
+        )?;
+        Ok(Box::new(HdlcFramerPortAdapter::new(
+            fg.add_block(block).into(),
+        )))
+    }
+}
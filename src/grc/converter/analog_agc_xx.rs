@@ -1,4 +1,4 @@
-use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, TunablePortAdapter};
 use super::{BlockInstance, Grc2FutureSdr};
 use anyhow::{bail, Context, Result};
 use fsdr_blocks::AgcBuilder;
@@ -30,7 +30,16 @@ impl BlockConverter for AnalogAgcXxConverter {
             _ => bail!("analog_agc_xx: Unhandled type {item_type}"),
         };
         let blk = fg.add_block(blk);
-        let blk = DefaultPortAdapter::new(blk.into());
+        // Message inputs provided by fsdr-blocks AGC
+        let blk = TunablePortAdapter::new(
+            blk.into(),
+            &[
+                "gain_locked",
+                "max_gain",
+                "adjustment_rate",
+                "reference_power",
+            ],
+        );
         let blk = Box::new(blk);
         Ok(blk)
     }
@@ -0,0 +1,50 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::{NoiseGenerator, NoiseSample, NoiseType, RepeatSource};
+use anyhow::{bail, Context, Result};
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::{BlockId, Flowgraph};
+
+pub struct AnalogFastNoiseSourceConverter {}
+
+impl AnalogFastNoiseSourceConverter {
+    fn add_source<T: NoiseSample>(
+        fg: &mut Flowgraph,
+        noise_type: NoiseType,
+        amp: f32,
+        seed: u64,
+        samples: usize,
+    ) -> Result<BlockId> {
+        let mut generator = NoiseGenerator::new(seed, noise_type);
+        let pool = (0..samples.max(1))
+            .map(|_| T::noise(&mut generator, amp))
+            .collect();
+        Ok(fg.add_block(RepeatSource::<T>::new(pool)?).into())
+    }
+}
+
+impl BlockConverter for AnalogFastNoiseSourceConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let item_type = blk
+            .parameters
+            .get("type")
+            .context("analog_fastnoise_source_x: item type must be defined")?;
+        let noise_type = NoiseType::try_from(blk.parameter_or("noise_type", "analog.GR_GAUSSIAN"))
+            .context("analog_fastnoise_source_x")?;
+        let amp = Grc2FutureSdr::parameter_as_f32(blk, "amp", "1.0")?;
+        let seed = Grc2FutureSdr::parameter_as_f32(blk, "seed", "0")? as u64;
+        let samples = Grc2FutureSdr::parameter_as_f32(blk, "samples", "8192")? as usize;
+        let blk = match &(item_type[..]) {
+            "complex" => Self::add_source::<Complex32>(fg, noise_type, amp, seed, samples)?,
+            "float" => Self::add_source::<f32>(fg, noise_type, amp, seed, samples)?,
+            "short" => Self::add_source::<i16>(fg, noise_type, amp, seed, samples)?,
+            "byte" => Self::add_source::<u8>(fg, noise_type, amp, seed, samples)?,
+            _ => bail!("analog_fastnoise_source_x: unhandled type {item_type}"),
+        };
+        Ok(Box::new(DefaultPortAdapter::new(blk)))
+    }
+}
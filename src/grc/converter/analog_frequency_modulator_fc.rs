@@ -0,0 +1,30 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use anyhow::Result;
+use futuresdr::blocks::Apply;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Flowgraph;
+use std::f32::consts::PI;
+
+pub struct AnalogFrequencyModulatorConverter {}
+
+impl BlockConverter for AnalogFrequencyModulatorConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let sensitivity = Grc2FutureSdr::parameter_as_f32(blk, "sensitivity", "pi")?;
+        // The phase advances by `sensitivity` radians per sample for an input of 1
+        let mut phase = 0.0f32;
+        let blk: Apply<_, f32, Complex32> = Apply::new(move |v: &f32| -> Complex32 {
+            phase += sensitivity * v;
+            // Wrap phase to [-π, π]
+            if phase > PI || phase < -PI {
+                phase = (phase + PI).rem_euclid(2.0 * PI) - PI;
+            }
+            Complex32::from_polar(1.0, phase)
+        });
+        Ok(Box::new(DefaultPortAdapter::new(fg.add_block(blk).into())))
+    }
+}
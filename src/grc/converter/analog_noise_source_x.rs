@@ -0,0 +1,41 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::{NoiseSource, NoiseType};
+use anyhow::{bail, Context, Result};
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::{BlockId, Flowgraph};
+
+pub struct AnalogNoiseSourceConverter {}
+
+impl BlockConverter for AnalogNoiseSourceConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let item_type = blk
+            .parameters
+            .get("type")
+            .context("analog_noise_source_x: item type must be defined")?;
+        let noise_type = NoiseType::try_from(blk.parameter_or("noise_type", "analog.GR_GAUSSIAN"))
+            .context("analog_noise_source_x")?;
+        let amp = Grc2FutureSdr::parameter_as_f32(blk, "amp", "1.0")?;
+        let seed = Grc2FutureSdr::parameter_as_f32(blk, "seed", "0")? as u64;
+        let blk: BlockId = match &(item_type[..]) {
+            "complex" => fg
+                .add_block(NoiseSource::<Complex32>::new(noise_type, amp, seed))
+                .into(),
+            "float" => fg
+                .add_block(NoiseSource::<f32>::new(noise_type, amp, seed))
+                .into(),
+            "short" => fg
+                .add_block(NoiseSource::<i16>::new(noise_type, amp, seed))
+                .into(),
+            "byte" => fg
+                .add_block(NoiseSource::<u8>::new(noise_type, amp, seed))
+                .into(),
+            _ => bail!("analog_noise_source_x: unhandled type {item_type}"),
+        };
+        Ok(Box::new(DefaultPortAdapter::new(blk)))
+    }
+}
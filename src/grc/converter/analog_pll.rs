@@ -0,0 +1,33 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::synchronizers::{PllCarrierTracking, PllFreqDet};
+use anyhow::{bail, Result};
+use futuresdr::runtime::{BlockId, Flowgraph};
+
+/// `analog_pll_carriertracking_cc` and `analog_pll_freqdet_cf`
+pub struct AnalogPllConverter {}
+
+impl BlockConverter for AnalogPllConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let loop_bw = Grc2FutureSdr::parameter_as_f32(blk, "w", "2*pi/100")?;
+        let max_freq = Grc2FutureSdr::parameter_as_f32(blk, "max_freq", "1")?;
+        let min_freq = Grc2FutureSdr::parameter_as_f32(blk, "min_freq", "-1")?;
+        if min_freq > max_freq {
+            bail!("{}: min_freq must not exceed max_freq", blk.id);
+        }
+        let pll: BlockId = match &blk.id[..] {
+            "analog_pll_carriertracking_cc" => fg
+                .add_block(PllCarrierTracking::new(loop_bw, max_freq, min_freq))
+                .into(),
+            "analog_pll_freqdet_cf" => fg
+                .add_block(PllFreqDet::new(loop_bw, max_freq, min_freq))
+                .into(),
+            kind => bail!("Unhandled PLL {kind}"),
+        };
+        Ok(Box::new(DefaultPortAdapter::new(pll)))
+    }
+}
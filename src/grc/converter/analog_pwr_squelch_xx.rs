@@ -0,0 +1,40 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, TunablePortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::PowerSquelch;
+use anyhow::{bail, Context, Result};
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::{BlockId, Flowgraph};
+
+/// GNU Radio's `analog_pwr_squelch_xx`, without ramp
+pub struct AnalogPwrSquelchConverter {}
+
+impl BlockConverter for AnalogPwrSquelchConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let threshold = Grc2FutureSdr::parameter_as_f64(blk, "threshold", "-40")?;
+        let alpha = Grc2FutureSdr::parameter_as_f64(blk, "alpha", "1e-4")? as f32;
+        let ramp = Grc2FutureSdr::parameter_as_f64(blk, "ramp", "0")?;
+        if ramp != 0.0 {
+            bail!("analog_pwr_squelch_xx: ramp is not supported, it must be 0");
+        }
+        let gate = blk
+            .parameter_or("gate", "False")
+            .to_lowercase()
+            .parse::<bool>()
+            .context("analog_pwr_squelch_xx: gate")?;
+        let item_type = blk.parameter_or("type", "complex");
+        let squelch: BlockId = match item_type {
+            "complex" => fg
+                .add_block(PowerSquelch::<Complex32>::new(threshold, alpha, gate))
+                .into(),
+            "float" => fg
+                .add_block(PowerSquelch::<f32>::new(threshold, alpha, gate))
+                .into(),
+            _ => bail!("analog_pwr_squelch_xx: Unhandled type {item_type}"),
+        };
+        Ok(Box::new(TunablePortAdapter::new(squelch, &["threshold"])))
+    }
+}
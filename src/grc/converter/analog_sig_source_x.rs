@@ -0,0 +1,41 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, TunablePortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::{SignalSource, Waveform};
+use anyhow::{bail, Context, Result};
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::{BlockId, Flowgraph};
+
+pub struct AnalogSigSourceConverter {}
+
+impl BlockConverter for AnalogSigSourceConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let item_type = blk
+            .parameters
+            .get("type")
+            .context("analog_sig_source_x: item type must be defined")?;
+        let waveform = Waveform::try_from(blk.parameter_or("waveform", "analog.GR_COS_WAVE"))
+            .context("analog_sig_source_x")?;
+        let freq = Grc2FutureSdr::parameter_as_f64(blk, "freq", "1000")?;
+        let samp_rate = Grc2FutureSdr::parameter_as_f64(blk, "samp_rate", "48000")?;
+        let amp = Grc2FutureSdr::parameter_as_f32(blk, "amp", "1.0")?;
+        let offset = Grc2FutureSdr::parameter_as_f32(blk, "offset", "0")?;
+        let blk: BlockId = match &(item_type[..]) {
+            "complex" => fg
+                .add_block(SignalSource::<Complex32>::new(
+                    waveform, freq, samp_rate, amp, offset,
+                ))
+                .into(),
+            "float" => fg
+                .add_block(SignalSource::<f32>::new(
+                    waveform, freq, samp_rate, amp, offset,
+                ))
+                .into(),
+            _ => bail!("analog_sig_source_x: unhandled type {item_type}"),
+        };
+        Ok(Box::new(TunablePortAdapter::new(blk, &["freq", "amp"])))
+    }
+}
@@ -0,0 +1,42 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter};
+use super::BlockInstance;
+use crate::blocks::{Ax25Format, Ax25Print};
+use anyhow::{bail, Result};
+use futuresdr::runtime::{BlockId, Flowgraph};
+
+#[derive(Clone, Copy)]
+pub struct Ax25PrintPortAdapter {
+    blk: BlockId,
+}
+
+impl ConnectorAdapter for Ax25PrintPortAdapter {
+    fn adapt_input_port(&self, port_name: &str) -> Result<(BlockId, &str)> {
+        match port_name {
+            "0" | "in" | "in_port" => Ok((self.blk, "in_port")),
+            _ => bail!("Unknown input port name {port_name}"),
+        }
+    }
+
+    fn adapt_output_port(&self, port_name: &str) -> Result<(BlockId, &str)> {
+        match port_name {
+            "0" | "out" => Ok((self.blk, "out")),
+            _ => bail!("Ax25Print unknown output port: {port_name}"),
+        }
+    }
+}
+
+pub struct Ax25PrintConverter {}
+
+impl BlockConverter for Ax25PrintConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let format = Ax25Format::try_from(blk.parameter_or("format", "tnc2"))?;
+        let block = Ax25Print::new(format);
+        Ok(Box::new(Ax25PrintPortAdapter {
+            blk: fg.add_block(block).into(),
+        }))
+    }
+}
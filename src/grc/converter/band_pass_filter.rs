@@ -1,12 +1,19 @@
-use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::super::converter_helper::{
+    BlockConverter, ConnectorAdapter, DefaultPortAdapter, TunablePortAdapter,
+};
 use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::ComplexBandPass;
 use anyhow::{bail, Context, Result};
 use futuresdr::blocks::FirBuilder;
 use futuresdr::futuredsp::{firdes, windows};
 use futuresdr::num_complex::Complex32;
 use futuresdr::runtime::Flowgraph;
 
-pub struct BandPassFilterConverter {}
+pub struct BandPassFilterConverter {
+    /// Not in GNU Radio: the band can be changed through the `cutoff` message input,
+    /// when it is connected, eg by `bandpass_fir_fft_cc --fifo`
+    pub tunable: bool,
+}
 
 impl BlockConverter for BandPassFilterConverter {
     fn convert(
@@ -24,7 +31,8 @@ impl BlockConverter for BandPassFilterConverter {
             .parameters
             .get("type")
             .context("band_pass_filter: type must be defined")?;
-        let transition_bw = Grc2FutureSdr::parameter_as_f64(blk, "width", "1.0")?; // Transition width between stop-band and pass-band in Hz
+        // Transition width between stop-band and pass-band in Hz, a float in csdr's bandpass_fir_fft_cc
+        let transition_bw = Grc2FutureSdr::parameter_as_f32(blk, "width", "1.0")? as f64;
         let window = blk
             .parameters
             .get("win")
@@ -59,6 +67,15 @@ impl BlockConverter for BandPassFilterConverter {
                 );
                 Box::new(DefaultPortAdapter::new(fg.add_block(blk).into()))
             }
+            "fir_filter_ccc" if self.tunable && interp == 1 && decimation == 1 => {
+                // Complex taps, band can be changed at runtime through the `cutoff` message input
+                let blk: ComplexBandPass =
+                    ComplexBandPass::new(low_cutoff_freq, high_cutoff_freq, rect_win);
+                Box::new(TunablePortAdapter::new(
+                    fg.add_block(blk).into(),
+                    &["cutoff"],
+                ))
+            }
             "fir_filter_ccc" => {
                 let taps = firdes::bandpass::<f32>(low_cutoff_freq, high_cutoff_freq, &rect_win);
                 let blk = FirBuilder::resampling_with_taps::<Complex32, Complex32, Vec<f32>>(
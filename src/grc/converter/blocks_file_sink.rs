@@ -1,6 +1,6 @@
 use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
 use super::BlockInstance;
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use fsdr_blocks::stdinout::StdInOutBuilder;
 use futuresdr::blocks::FileSink;
 use futuresdr::num_complex::Complex32;
@@ -25,10 +25,14 @@ impl BlockConverter for FileSinkConverter {
             .context("blocks_file_sink: item type must be defined")?;
         let blk = if "-" == filename {
             match &(item_type[..]) {
-                "u8" => {
+                "u8" | "byte" => {
                     let blk = StdInOutBuilder::<u8>::stdout().as_ne().build();
                     Box::new(DefaultPortAdapter::new(fg.add_block(blk).into()))
                 }
+                "s8" | "char" => {
+                    let blk = StdInOutBuilder::<i8>::stdout().as_ne().build();
+                    Box::new(DefaultPortAdapter::new(fg.add_block(blk).into()))
+                }
                 "i16" | "ishort" | "short" => {
                     let blk = StdInOutBuilder::<i16>::stdout().as_ne().build();
                     Box::new(DefaultPortAdapter::new(fg.add_block(blk).into()))
@@ -41,14 +45,18 @@ impl BlockConverter for FileSinkConverter {
                     let blk = StdInOutBuilder::<Complex32>::stdout().as_ne().build();
                     Box::new(DefaultPortAdapter::new(fg.add_block(blk).into()))
                 }
-                _ => todo!("Unhandled StdIn FileSink Type {item_type}"),
+                _ => bail!("blocks_file_sink: unhandled stdout type {item_type}"),
             }
         } else {
             match &(item_type[..]) {
-                "u8" => {
+                "u8" | "byte" => {
                     let blk = FileSink::<u8>::new(filename);
                     Box::new(DefaultPortAdapter::new(fg.add_block(blk).into()))
                 }
+                "s8" | "char" => {
+                    let blk = FileSink::<i8>::new(filename);
+                    Box::new(DefaultPortAdapter::new(fg.add_block(blk).into()))
+                }
                 "i16" | "short" => {
                     let blk = FileSink::<i16>::new(filename);
                     Box::new(DefaultPortAdapter::new(fg.add_block(blk).into()))
@@ -61,7 +69,7 @@ impl BlockConverter for FileSinkConverter {
                     let blk = FileSink::<Complex32>::new(filename);
                     Box::new(DefaultPortAdapter::new(fg.add_block(blk).into()))
                 }
-                _ => todo!("Unhandled FileSink Type {item_type}"),
+                _ => bail!("blocks_file_sink: unhandled type {item_type}"),
             }
         };
         Ok(blk)
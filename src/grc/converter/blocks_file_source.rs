@@ -1,11 +1,38 @@
 use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
 use super::BlockInstance;
-use anyhow::{Context, Result};
+use crate::blocks::ProgressMeter;
+use anyhow::{bail, Context, Result};
 use futuresdr::blocks::FileSource;
 use futuresdr::num_complex::Complex32;
-use futuresdr::runtime::Flowgraph;
+use futuresdr::prelude::CpuSample;
+use futuresdr::runtime::{BlockId, Flowgraph};
 
-pub struct FileSourceConverter {}
+pub struct FileSourceConverter {
+    /// Follow the source by a [`ProgressMeter`], with an ETA for regular files
+    pub progress: bool,
+}
+
+impl FileSourceConverter {
+    fn add_source<T: CpuSample>(
+        &self,
+        fg: &mut Flowgraph,
+        filename: &str,
+        repeat: bool,
+    ) -> Result<BlockId> {
+        let src: BlockId = fg.add_block(FileSource::<T>::new(filename, repeat)).into();
+        if !self.progress {
+            return Ok(src);
+        }
+        let total = std::fs::metadata(filename)
+            .ok()
+            .filter(|metadata| metadata.is_file() && !repeat)
+            .map(|metadata| metadata.len() / std::mem::size_of::<T>() as u64);
+        let meter: BlockId = fg.add_block(ProgressMeter::<T>::new(total)).into();
+        fg.connect_dyn(src, "output", meter, "input")
+            .context("blocks_file_source: connecting progress meter")?;
+        Ok(meter)
+    }
+}
 
 impl BlockConverter for FileSourceConverter {
     fn convert(
@@ -33,25 +60,15 @@ impl BlockConverter for FileSourceConverter {
         } else {
             filename
         };
-        let blk: Box<dyn ConnectorAdapter> = match &(item_type[..]) {
-            "u8" | "uchar" | "byte" => {
-                let blk = FileSource::<u8>::new(filename, repeat);
-                Box::new(DefaultPortAdapter::new(fg.add_block(blk).into()))
-            }
-            "s8" | "char" => {
-                let blk = FileSource::<i8>::new(filename, repeat);
-                Box::new(DefaultPortAdapter::new(fg.add_block(blk).into()))
-            }
-            "f32" | "float" => {
-                let blk = FileSource::<f32>::new(filename, repeat);
-                Box::new(DefaultPortAdapter::new(fg.add_block(blk).into()))
-            }
-            "c32" | "complex" => {
-                let blk = FileSource::<Complex32>::new(filename, repeat);
-                Box::new(DefaultPortAdapter::new(fg.add_block(blk).into()))
-            }
-            _ => todo!("Unhandled FileSource Type {item_type}"),
+        let blk = match &(item_type[..]) {
+            "u8" | "uchar" | "byte" => self.add_source::<u8>(fg, filename, repeat)?,
+            "s8" | "char" => self.add_source::<i8>(fg, filename, repeat)?,
+            "i16" | "short" => self.add_source::<i16>(fg, filename, repeat)?,
+            "f32" | "float" => self.add_source::<f32>(fg, filename, repeat)?,
+            "c32" | "complex" => self.add_source::<Complex32>(fg, filename, repeat)?,
+            _ => bail!("blocks_file_source: unhandled type {item_type}"),
         };
+        let blk: Box<dyn ConnectorAdapter> = Box::new(DefaultPortAdapter::new(blk));
         Ok(blk)
     }
 }
@@ -1,8 +1,7 @@
-use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, TunablePortAdapter};
 use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::FrequencyShifter;
 use anyhow::Result;
-use fsdr_blocks::math::FrequencyShifter;
-use futuresdr::num_complex::Complex32;
 use futuresdr::runtime::Flowgraph;
 
 pub struct FreqShiftCcConverter {}
@@ -15,9 +14,9 @@ impl BlockConverter for FreqShiftCcConverter {
     ) -> Result<Box<dyn ConnectorAdapter>> {
         let sample_rate = Grc2FutureSdr::parameter_as_f64(blk, "sample_rate", "48000")? as f32;
         let freq = Grc2FutureSdr::parameter_as_f64(blk, "freq", "1.0")? as f32;
-        let blk = FrequencyShifter::<Complex32>::new(freq, sample_rate);
+        let blk: FrequencyShifter = FrequencyShifter::new(freq, sample_rate);
         let blk = fg.add_block(blk);
-        let blk = DefaultPortAdapter::new(blk.into());
+        let blk = TunablePortAdapter::new(blk.into(), &["freq"]);
         let blk = Box::new(blk);
         Ok(blk)
     }
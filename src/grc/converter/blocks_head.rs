@@ -0,0 +1,30 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use anyhow::{bail, Context, Result};
+use futuresdr::blocks::Head;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::{BlockId, Flowgraph};
+
+pub struct HeadConverter {}
+
+impl BlockConverter for HeadConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let item_type = blk
+            .parameters
+            .get("type")
+            .context("blocks_head: item type must be defined")?;
+        let num_items = Grc2FutureSdr::parameter_as_f64(blk, "num_items", "1024")? as u64;
+        let blk: BlockId = match &(item_type[..]) {
+            "byte" | "char" => fg.add_block(Head::<u8>::new(num_items)).into(),
+            "short" => fg.add_block(Head::<i16>::new(num_items)).into(),
+            "float" => fg.add_block(Head::<f32>::new(num_items)).into(),
+            "complex" => fg.add_block(Head::<Complex32>::new(num_items)).into(),
+            _ => bail!("blocks_head: unhandled type {item_type}"),
+        };
+        Ok(Box::new(DefaultPortAdapter::new(blk)))
+    }
+}
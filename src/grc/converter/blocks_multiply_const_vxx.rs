@@ -1,5 +1,8 @@
-use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::super::converter_helper::{
+    BlockConverter, ConnectorAdapter, DefaultPortAdapter, TunablePortAdapter,
+};
 use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::Gain;
 use anyhow::{Context, Result};
 use futuresdr::blocks::Apply;
 use futuresdr::runtime::Flowgraph;
@@ -25,8 +28,8 @@ impl BlockConverter for MulConstVxConverter {
                 Box::new(DefaultPortAdapter::new(fg.add_block(blk).into()))
             }
             "float" => {
-                let blk: Apply<_, f32, f32> = Apply::new(move |v: &f32| -> f32 { v * constant });
-                Box::new(DefaultPortAdapter::new(fg.add_block(blk).into()))
+                let blk: Gain = Gain::new(constant);
+                Box::new(TunablePortAdapter::new(fg.add_block(blk).into(), &["gain"]))
             }
             _ => todo!("Unhandled blocks_multiply_const_vxx Type {item_type}"),
         };
@@ -18,7 +18,7 @@ impl BlockConverter for NullSinkConverter {
             .get("type")
             .expect("item type must be defined");
         let blk: Box<dyn ConnectorAdapter> = match &(item_type[..]) {
-            "char" => {
+            "byte" | "char" => {
                 let blk = NullSink::<u8>::new();
                 Box::new(DefaultPortAdapter::new(fg.add_block(blk).into()))
             }
@@ -1,9 +1,12 @@
 use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
 use super::{BlockInstance, Grc2FutureSdr};
-use anyhow::Result;
-use futuresdr::blocks::ApplyNM;
+use crate::blocks::{Endianness, RepackBits};
+use anyhow::{bail, Result};
+use futuresdr::prelude::{DefaultCpuReader, DefaultCpuWriter};
 use futuresdr::runtime::Flowgraph;
 
+/// `blocks_pack_k_bits_bb`, `blocks_unpack_k_bits_bb`, `blocks_packed_to_unpacked_xx`,
+/// `blocks_unpacked_to_packed_xx` and `blocks_repack_bits_bb`, all repacking bits
 pub struct PackBitsConverter {}
 
 impl BlockConverter for PackBitsConverter {
@@ -12,20 +15,47 @@ impl BlockConverter for PackBitsConverter {
         blk: &BlockInstance,
         fg: &mut Flowgraph,
     ) -> Result<Box<dyn ConnectorAdapter>> {
-        let _k = Grc2FutureSdr::parameter_as_f32(blk, "k", "8")? as usize;
-        // todo!("handle different value of k than 8")
-        let blk = ApplyNM::<_, u8, u8, 8, 1>::new(move |v: &[u8], d: &mut [u8]| {
-            d[0] = v
-                .iter()
-                .rev()
-                .enumerate()
-                .map(|(i, u)| (*u) << i)
-                .reduce(|a, b| a | b)
-                .expect("guarantee to not be empty due to ApplyNM");
-        });
-        let blk = fg.add_block(blk);
-        let blk = DefaultPortAdapter::new(blk.into());
-        let blk = Box::new(blk);
-        Ok(blk)
+        let (k, l, endianness) = match &blk.id[..] {
+            // One bit per input byte, the first one being the most significant
+            "blocks_pack_k_bits_bb" => {
+                let k = Grc2FutureSdr::parameter_as_f32(blk, "k", "8")? as usize;
+                (1, k, Endianness::MsbFirst)
+            }
+            "blocks_unpack_k_bits_bb" => {
+                let k = Grc2FutureSdr::parameter_as_f32(blk, "k", "8")? as usize;
+                (k, 1, Endianness::MsbFirst)
+            }
+            "blocks_packed_to_unpacked_xx" | "blocks_unpacked_to_packed_xx" => {
+                let item_type = blk.parameter_or("type", "byte");
+                if item_type != "byte" {
+                    bail!("{}: only bytes are supported, not {item_type}", blk.id);
+                }
+                let bits_per_chunk =
+                    Grc2FutureSdr::parameter_as_f32(blk, "bits_per_chunk", "2")? as usize;
+                let endianness =
+                    Endianness::try_from(blk.parameter_or("endianness", "gr.GR_MSB_FIRST"))?;
+                if blk.id == "blocks_packed_to_unpacked_xx" {
+                    (8, bits_per_chunk, endianness)
+                } else {
+                    (bits_per_chunk, 8, endianness)
+                }
+            }
+            "blocks_repack_bits_bb" => {
+                let len_tag_key = blk.parameter_or("len_tag_key", "\"\"");
+                if !len_tag_key.trim_matches(['"', '\'']).is_empty() {
+                    bail!(
+                        "blocks_repack_bits_bb: tags are not supported, len_tag_key must be empty"
+                    );
+                }
+                (
+                    Grc2FutureSdr::parameter_as_f32(blk, "k", "1")? as usize,
+                    Grc2FutureSdr::parameter_as_f32(blk, "l", "8")? as usize,
+                    Endianness::try_from(blk.parameter_or("endianness", "gr.GR_LSB_FIRST"))?,
+                )
+            }
+            _ => bail!("Unknown bit packing block: {}", blk.id),
+        };
+        let blk = RepackBits::<DefaultCpuReader<u8>, DefaultCpuWriter<u8>>::new(k, l, endianness)?;
+        Ok(Box::new(DefaultPortAdapter::new(fg.add_block(blk).into())))
     }
 }
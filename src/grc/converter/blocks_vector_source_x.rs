@@ -0,0 +1,54 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::RepeatSource;
+use anyhow::{bail, Context, Result};
+use futuresdr::blocks::VectorSource;
+use futuresdr::num_complex::Complex32;
+use futuresdr::prelude::CpuSample;
+use futuresdr::runtime::{BlockId, Flowgraph};
+
+pub struct BlocksVectorSourceConverter {}
+
+impl BlocksVectorSourceConverter {
+    fn add_source<T: CpuSample>(
+        fg: &mut Flowgraph,
+        items: Vec<T>,
+        repeat: bool,
+    ) -> Result<BlockId> {
+        if repeat && !items.is_empty() {
+            Ok(fg.add_block(RepeatSource::<T>::new(items)?).into())
+        } else {
+            Ok(fg.add_block(VectorSource::<T>::new(items)).into())
+        }
+    }
+}
+
+impl BlockConverter for BlocksVectorSourceConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let item_type = blk
+            .parameters
+            .get("type")
+            .context("blocks_vector_source_x: item type must be defined")?;
+        let vector = Grc2FutureSdr::parameter_as_vector(blk, "vector", "(0, 0, 0)")
+            .context("blocks_vector_source_x: vector")?;
+        let repeat = blk
+            .parameter_or("repeat", "True")
+            .to_lowercase()
+            .parse::<bool>()
+            .context("blocks_vector_source_x: repeat")?;
+        let reals = || vector.iter().map(|v| v.re);
+        let blk = match &(item_type[..]) {
+            "complex" => Self::add_source(fg, vector.clone(), repeat)?,
+            "float" => Self::add_source(fg, reals().collect::<Vec<f32>>(), repeat)?,
+            "int" => Self::add_source(fg, reals().map(|v| v as i32).collect(), repeat)?,
+            "short" => Self::add_source(fg, reals().map(|v| v as i16).collect(), repeat)?,
+            "byte" => Self::add_source(fg, reals().map(|v| v as u8).collect(), repeat)?,
+            _ => bail!("blocks_vector_source_x: unhandled type {item_type}"),
+        };
+        Ok(Box::new(DefaultPortAdapter::new(blk)))
+    }
+}
@@ -0,0 +1,27 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::{ChannelModel, ChannelParameters};
+use anyhow::{Context, Result};
+use futuresdr::runtime::Flowgraph;
+
+pub struct ChannelsChannelModelConverter {}
+
+impl BlockConverter for ChannelsChannelModelConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let parameters = ChannelParameters {
+            noise_voltage: Grc2FutureSdr::parameter_as_f32(blk, "noise_voltage", "0.0")?,
+            frequency_offset: Grc2FutureSdr::parameter_as_f64(blk, "freq_offset", "0.0")?,
+            epsilon: Grc2FutureSdr::parameter_as_f64(blk, "epsilon", "1.0")?,
+            taps: Grc2FutureSdr::parameter_as_vector(blk, "taps", "1.0")
+                .context("channels_channel_model: taps")?,
+            noise_seed: Grc2FutureSdr::parameter_as_u64(blk, "seed", "0")?,
+        };
+        let blk = ChannelModel::new(parameters).context("channels_channel_model")?;
+        let blk = fg.add_block(blk);
+        Ok(Box::new(DefaultPortAdapter::new(blk.into())))
+    }
+}
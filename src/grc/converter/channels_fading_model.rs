@@ -0,0 +1,30 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::FadingModel;
+use anyhow::Result;
+use futuresdr::runtime::Flowgraph;
+
+pub struct ChannelsFadingModelConverter {}
+
+impl BlockConverter for ChannelsFadingModelConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let sinusoids = Grc2FutureSdr::parameter_as_f32(blk, "N", "8")? as usize;
+        let doppler = Grc2FutureSdr::parameter_as_f64(blk, "fDTs", "0.01")?;
+        let los = blk
+            .parameter_or("LOS", "False")
+            .to_lowercase()
+            .parse::<bool>()?;
+        let k = if los {
+            Grc2FutureSdr::parameter_as_f64(blk, "K", "4.0")?
+        } else {
+            0.0
+        };
+        let seed = Grc2FutureSdr::parameter_as_u64(blk, "seed", "0")?;
+        let blk = fg.add_block(FadingModel::new(sinusoids, doppler, k, seed));
+        Ok(Box::new(DefaultPortAdapter::new(blk.into())))
+    }
+}
@@ -0,0 +1,41 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::ChirpSource;
+use anyhow::{bail, Context, Result};
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::{BlockId, Flowgraph};
+
+/// Not a GNU Radio block: linear chirp repeated every `duration` seconds.
+pub struct ChirpSourceConverter {}
+
+impl BlockConverter for ChirpSourceConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let item_type = blk
+            .parameters
+            .get("type")
+            .context("chirp_source_x: item type must be defined")?;
+        let start_freq = Grc2FutureSdr::parameter_as_f64(blk, "start_freq", "0")?;
+        let stop_freq = Grc2FutureSdr::parameter_as_f64(blk, "stop_freq", "1000")?;
+        let duration = Grc2FutureSdr::parameter_as_f64(blk, "duration", "1.0")?;
+        let samp_rate = Grc2FutureSdr::parameter_as_f64(blk, "samp_rate", "48000")?;
+        let amp = Grc2FutureSdr::parameter_as_f32(blk, "amp", "1.0")?;
+        let blk: BlockId = match &(item_type[..]) {
+            "complex" => fg
+                .add_block(ChirpSource::<Complex32>::new(
+                    start_freq, stop_freq, duration, samp_rate, amp,
+                ))
+                .into(),
+            "float" => fg
+                .add_block(ChirpSource::<f32>::new(
+                    start_freq, stop_freq, duration, samp_rate, amp,
+                ))
+                .into(),
+            _ => bail!("chirp_source_x: unhandled type {item_type}"),
+        };
+        Ok(Box::new(DefaultPortAdapter::new(blk)))
+    }
+}
@@ -0,0 +1,24 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::ConstellationDecoder;
+use anyhow::{Context, Result};
+use futuresdr::runtime::Flowgraph;
+
+pub struct DigitalConstellationDecoderConverter {}
+
+impl BlockConverter for DigitalConstellationDecoderConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let constellation = Grc2FutureSdr::parameter_as_constellation(
+            blk,
+            "constellation",
+            "digital.constellation_bpsk().base()",
+        )
+        .context("digital_constellation_decoder_cb")?;
+        let blk = fg.add_block(ConstellationDecoder::new(constellation));
+        Ok(Box::new(DefaultPortAdapter::new(blk.into())))
+    }
+}
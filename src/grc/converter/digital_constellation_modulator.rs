@@ -0,0 +1,36 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::modulators::ConstellationModulator;
+use anyhow::{bail, Context, Result};
+use futuresdr::runtime::Flowgraph;
+
+pub struct DigitalConstellationModulatorConverter {}
+
+impl BlockConverter for DigitalConstellationModulatorConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let constellation = Grc2FutureSdr::parameter_as_constellation(
+            blk,
+            "constellation",
+            "digital.constellation_bpsk().base()",
+        )
+        .context("digital_constellation_modulator")?;
+        let differential = blk
+            .parameter_or("differential", "True")
+            .to_lowercase()
+            .parse::<bool>()
+            .context("digital_constellation_modulator: differential")?;
+        let sps = Grc2FutureSdr::parameter_as_f32(blk, "samples_per_symbol", "2")?;
+        if sps.fract() != 0.0 {
+            bail!("digital_constellation_modulator: only integer samples per symbol are supported");
+        }
+        let excess_bw = Grc2FutureSdr::parameter_as_f64(blk, "excess_bw", "0.35")?;
+        let blk = ConstellationModulator::new(constellation, differential, sps as usize, excess_bw)
+            .context("digital_constellation_modulator")?;
+        let blk = fg.add_block(blk);
+        Ok(Box::new(DefaultPortAdapter::new(blk.into())))
+    }
+}
@@ -0,0 +1,24 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::ConstellationSoftDecoder;
+use anyhow::{Context, Result};
+use futuresdr::runtime::Flowgraph;
+
+pub struct DigitalConstellationSoftDecoderConverter {}
+
+impl BlockConverter for DigitalConstellationSoftDecoderConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let constellation = Grc2FutureSdr::parameter_as_constellation(
+            blk,
+            "constellation",
+            "digital.constellation_bpsk().base()",
+        )
+        .context("digital_constellation_soft_decoder_cf")?;
+        let blk = fg.add_block(ConstellationSoftDecoder::new(constellation));
+        Ok(Box::new(DefaultPortAdapter::new(blk.into())))
+    }
+}
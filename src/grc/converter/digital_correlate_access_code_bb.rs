@@ -0,0 +1,31 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::{AccessCode, CorrelateAccessCode, CorrelationOutput, Correlator};
+use anyhow::{Context, Result};
+use futuresdr::runtime::Flowgraph;
+
+pub struct DigitalCorrelateAccessCodeConverter {}
+
+impl BlockConverter for DigitalCorrelateAccessCodeConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let access_code = AccessCode::try_from(blk.parameter_or("access_code", "0x1ACFFC1D"))
+            .context("digital_correlate_access_code_bb")?;
+        let threshold = Grc2FutureSdr::parameter_as_f32(blk, "threshold", "0")?;
+        // Not in GNU Radio
+        let invert = blk
+            .parameter_or("invert", "False")
+            .to_lowercase()
+            .parse::<bool>()
+            .context("digital_correlate_access_code_bb: invert")?;
+        let correlator = Correlator::new(access_code, threshold as u32, invert);
+        let blk = fg.add_block(CorrelateAccessCode::new(
+            correlator,
+            CorrelationOutput::Flag,
+        ));
+        Ok(Box::new(DefaultPortAdapter::new(blk.into())))
+    }
+}
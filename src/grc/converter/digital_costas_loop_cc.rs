@@ -0,0 +1,41 @@
+use super::super::converter_helper::{
+    BlockConverter, ConnectorAdapter, MultipleOutputsPortAdapter,
+};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::synchronizers::CostasLoop;
+use anyhow::{bail, Context, Result};
+use futuresdr::blocks::NullSink;
+use futuresdr::runtime::{BlockId, Flowgraph};
+
+pub struct DigitalCostasLoopConverter {}
+
+impl BlockConverter for DigitalCostasLoopConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let loop_bw = Grc2FutureSdr::parameter_as_f32(blk, "w", "2*pi/100")?;
+        let order = Grc2FutureSdr::parameter_as_f32(blk, "order", "2")?;
+        // Not in GNU Radio, whose loop is always damped by √2/2
+        let damping = Grc2FutureSdr::parameter_as_f32(blk, "damping", "0.7071")?;
+        let use_snr = blk
+            .parameter_or("use_snr", "False")
+            .to_lowercase()
+            .parse::<bool>()
+            .context("digital_costas_loop_cc: use_snr")?;
+        if use_snr {
+            bail!("digital_costas_loop_cc: SNR based phase detectors are not supported");
+        }
+        let costas =
+            CostasLoop::new(order as usize, loop_bw, damping).context("digital_costas_loop_cc")?;
+        let costas: BlockId = fg.add_block(costas).into();
+        // The frequency output is optional
+        let null_sink = fg.add_block(NullSink::<f32>::new());
+        fg.connect_dyn(costas, "frequency", &null_sink, "input")?;
+        Ok(Box::new(MultipleOutputsPortAdapter::new(
+            costas,
+            &["output", "frequency"],
+        )))
+    }
+}
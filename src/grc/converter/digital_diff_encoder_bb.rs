@@ -0,0 +1,62 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::hdlc::{NrziDecoder, NrziEncoder};
+use anyhow::{bail, Result};
+use futuresdr::blocks::Apply;
+use futuresdr::runtime::Flowgraph;
+
+/// GNU Radio's `digital_diff_encoder_bb` and `digital_diff_decoder_bb`: with the
+/// differential coding, each output symbol is the sum (encoder) or the difference
+/// (decoder) modulo `modulus` of the input symbol and the previous one. With the NRZI
+/// coding, only for bits, a 0 is a transition and a 1 no transition.
+pub struct DigitalDiffCoderConverter {}
+
+impl BlockConverter for DigitalDiffCoderConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let decode = match &blk.id[..] {
+            "digital_diff_encoder_bb" => false,
+            "digital_diff_decoder_bb" => true,
+            _ => bail!("Unknown differential coder: {}", blk.id),
+        };
+        let modulus = Grc2FutureSdr::parameter_as_f32(blk, "modulus", "2")? as u64;
+        if !(2..=256).contains(&modulus) {
+            bail!("{}: modulus must be within [2, 256]", blk.id);
+        }
+        let coding = blk.parameter_or("coding", "digital.DIFF_DIFFERENTIAL");
+        let nrzi = match coding.strip_prefix("digital.").unwrap_or(coding) {
+            "DIFF_DIFFERENTIAL" => false,
+            "DIFF_NRZI" if modulus == 2 => true,
+            "DIFF_NRZI" => bail!("{}: NRZI coding needs a modulus of 2", blk.id),
+            _ => bail!("{}: unknown coding {coding}", blk.id),
+        };
+        let mut nrzi_encoder = NrziEncoder::default();
+        let mut nrzi_decoder = NrziDecoder::default();
+        // Last symbol output by the encoder or input to the decoder
+        let mut last = 0u64;
+        let block: Apply<_, u8, u8> = Apply::new(move |v: &u8| -> u8 {
+            if nrzi {
+                return if decode {
+                    nrzi_decoder.decode(v & 1)
+                } else {
+                    nrzi_encoder.encode(v & 1)
+                };
+            }
+            let v = *v as u64 % modulus;
+            if decode {
+                let out = (v + modulus - last) % modulus;
+                last = v;
+                out as u8
+            } else {
+                last = (v + last) % modulus;
+                last as u8
+            }
+        });
+        Ok(Box::new(DefaultPortAdapter::new(
+            fg.add_block(block).into(),
+        )))
+    }
+}
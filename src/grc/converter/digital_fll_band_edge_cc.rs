@@ -0,0 +1,37 @@
+use super::super::converter_helper::{
+    BlockConverter, ConnectorAdapter, MultipleOutputsPortAdapter,
+};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::synchronizers::FllBandEdge;
+use anyhow::{bail, Context, Result};
+use futuresdr::blocks::NullSink;
+use futuresdr::runtime::{BlockId, Flowgraph};
+
+pub struct DigitalFllBandEdgeConverter {}
+
+impl BlockConverter for DigitalFllBandEdgeConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let item_type = blk.parameter_or("type", "cc");
+        if item_type != "cc" {
+            bail!("digital_fll_band_edge_cc: unhandled type {item_type}");
+        }
+        let sps = Grc2FutureSdr::parameter_as_f32(blk, "samps_per_sym", "2")?;
+        let rolloff = Grc2FutureSdr::parameter_as_f32(blk, "rolloff", "0.35")?;
+        let filter_size = Grc2FutureSdr::parameter_as_f32(blk, "filter_size", "45")?;
+        let loop_bw = Grc2FutureSdr::parameter_as_f32(blk, "w", "2*pi/100")?;
+        let fll = FllBandEdge::new(sps, rolloff, filter_size as usize, loop_bw)
+            .context("digital_fll_band_edge_cc")?;
+        let fll: BlockId = fg.add_block(fll).into();
+        // The frequency output is optional
+        let null_sink = fg.add_block(NullSink::<f32>::new());
+        fg.connect_dyn(fll, "frequency", &null_sink, "input")?;
+        Ok(Box::new(MultipleOutputsPortAdapter::new(
+            fll,
+            &["output", "frequency"],
+        )))
+    }
+}
@@ -0,0 +1,49 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter};
+use super::satellites_fixedlen_to_pdu::FixedlenToPduPortAdapter;
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::HdlcDeframer;
+use anyhow::{bail, Context, Result};
+use futuresdr::runtime::Flowgraph;
+
+/// GNU Radio's `digital_hdlc_deframer_bp` and gr-satellites' `satellites_hdlc_deframer`
+pub struct DigitalHdlcDeframerConverter {}
+
+impl BlockConverter for DigitalHdlcDeframerConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let (length_min, length_max, crc_check) = match &blk.id[..] {
+            "digital_hdlc_deframer_bp" => (
+                Grc2FutureSdr::parameter_as_f32(blk, "length_min", "32")? as usize,
+                Grc2FutureSdr::parameter_as_f32(blk, "length_max", "500")? as usize,
+                // Not in GNU Radio
+                blk.parameter_or("crc_check", "True"),
+            ),
+            "satellites_hdlc_deframer" => (
+                3,
+                Grc2FutureSdr::parameter_as_f32(blk, "max_length", "10000")? as usize,
+                blk.parameter_or("check_fcs", "True"),
+            ),
+            _ => bail!("Unknown HDLC deframer: {}", blk.id),
+        };
+        let crc_check = crc_check
+            .to_lowercase()
+            .parse::<bool>()
+            .context("hdlc_deframer: crc_check")?;
+        // Not in GNU Radio
+        let nrzi = blk
+            .parameter_or("nrzi", "False")
+            .to_lowercase()
+            .parse::<bool>()
+            .context("hdlc_deframer: nrzi")?;
+        if length_min > length_max {
+            bail!("hdlc_deframer: minimum length {length_min} above maximum length {length_max}");
+        }
+        let block = HdlcDeframer::new(length_min, length_max, crc_check, nrzi);
+        Ok(Box::new(FixedlenToPduPortAdapter::new(
+            fg.add_block(block).into(),
+        )))
+    }
+}
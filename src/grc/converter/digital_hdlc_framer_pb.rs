@@ -0,0 +1,55 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::HdlcFramer;
+use anyhow::{bail, Context, Result};
+use futuresdr::runtime::{BlockId, Flowgraph};
+
+/// Message input and stream output
+#[derive(Clone, Copy)]
+pub struct HdlcFramerPortAdapter {
+    blk: BlockId,
+}
+
+impl HdlcFramerPortAdapter {
+    pub fn new(blk: BlockId) -> HdlcFramerPortAdapter {
+        HdlcFramerPortAdapter { blk }
+    }
+}
+
+impl ConnectorAdapter for HdlcFramerPortAdapter {
+    fn adapt_input_port(&self, port_name: &str) -> Result<(BlockId, &str)> {
+        match port_name {
+            "0" | "in" | "in_port" => Ok((self.blk, "in_port")),
+            _ => bail!("Unknown input port name {port_name}"),
+        }
+    }
+
+    fn adapt_output_port(&self, port_name: &str) -> Result<(BlockId, &str)> {
+        match port_name {
+            "0" | "out" | "output" => Ok((self.blk, "output")),
+            _ => bail!("HdlcFramer unknown output port: {port_name}"),
+        }
+    }
+}
+
+pub struct DigitalHdlcFramerConverter {}
+
+impl BlockConverter for DigitalHdlcFramerConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        // Not in GNU Radio, which sends a single flag before each frame
+        let flags = Grc2FutureSdr::parameter_as_f32(blk, "flags", "1")? as usize;
+        let nrzi = blk
+            .parameter_or("nrzi", "False")
+            .to_lowercase()
+            .parse::<bool>()
+            .context("digital_hdlc_framer_pb: nrzi")?;
+        let block = HdlcFramer::new(flags, nrzi);
+        Ok(Box::new(HdlcFramerPortAdapter::new(
+            fg.add_block(block).into(),
+        )))
+    }
+}
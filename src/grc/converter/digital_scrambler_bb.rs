@@ -0,0 +1,80 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::Lfsr;
+use anyhow::{bail, Context, Result};
+use futuresdr::blocks::Apply;
+use futuresdr::runtime::Flowgraph;
+
+/// Shift register of the `mask`, `seed` and `len` parameters
+fn lfsr(blk: &BlockInstance) -> Result<Lfsr> {
+    let mask = Grc2FutureSdr::parameter_as_u64(blk, "mask", "0x8A")?;
+    let seed = Grc2FutureSdr::parameter_as_u64(blk, "seed", "0x7F")?;
+    let len = Grc2FutureSdr::parameter_as_u64(blk, "len", "7")?;
+    Lfsr::new(mask, seed, len as u32).context(blk.id.clone())
+}
+
+/// GNU Radio's `digital_scrambler_bb` and `digital_descrambler_bb`: multiplicative
+/// (self-synchronizing) scrambling of unpacked bits
+pub struct DigitalScramblerConverter {}
+
+impl BlockConverter for DigitalScramblerConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let descramble = match &blk.id[..] {
+            "digital_scrambler_bb" => false,
+            "digital_descrambler_bb" => true,
+            _ => bail!("Unknown scrambler: {}", blk.id),
+        };
+        let mut lfsr = lfsr(blk)?;
+        let block: Apply<_, u8, u8> = Apply::new(move |v: &u8| -> u8 {
+            if descramble {
+                lfsr.next_bit_descramble(*v)
+            } else {
+                lfsr.next_bit_scramble(*v)
+            }
+        });
+        Ok(Box::new(DefaultPortAdapter::new(
+            fg.add_block(block).into(),
+        )))
+    }
+}
+
+/// GNU Radio's `digital_additive_scrambler_bb`: the pseudo-random sequence is added to
+/// the input, `bits_per_byte` bits at a time, least significant first, the register
+/// being reset every `count` bytes when positive.
+pub struct DigitalAdditiveScramblerConverter {}
+
+impl BlockConverter for DigitalAdditiveScramblerConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let mut lfsr = lfsr(blk)?;
+        let count = Grc2FutureSdr::parameter_as_u64(blk, "count", "0")?;
+        let bits_per_byte = Grc2FutureSdr::parameter_as_u64(blk, "bits_per_byte", "1")?;
+        if !(1..=8).contains(&bits_per_byte) {
+            bail!("digital_additive_scrambler_bb: bits_per_byte must be within [1, 8]");
+        }
+        let reset_tag_key = blk.parameter_or("reset_tag_key", "\"\"");
+        if !reset_tag_key.trim_matches(['"', '\'']).is_empty() {
+            bail!("digital_additive_scrambler_bb: tags are not supported, reset_tag_key must be empty");
+        }
+        let mut bytes = 0;
+        let block: Apply<_, u8, u8> = Apply::new(move |v: &u8| -> u8 {
+            let sequence = (0..bits_per_byte).fold(0, |acc, k| acc | (lfsr.next_bit() << k));
+            bytes += 1;
+            if count > 0 && bytes >= count {
+                lfsr.reset();
+                bytes = 0;
+            }
+            v ^ sequence
+        });
+        Ok(Box::new(DefaultPortAdapter::new(
+            fg.add_block(block).into(),
+        )))
+    }
+}
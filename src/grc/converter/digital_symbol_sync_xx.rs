@@ -0,0 +1,70 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::synchronizers::{SymbolSync, TedType};
+use anyhow::{bail, Context, Result};
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::{BlockId, Flowgraph};
+
+pub struct DigitalSymbolSyncConverter {}
+
+impl BlockConverter for DigitalSymbolSyncConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let ted_type =
+            TedType::try_from(blk.parameter_or("ted_type", "digital.TED_MUELLER_AND_MULLER"))
+                .context("digital_symbol_sync_xx")?;
+        let constellation = Grc2FutureSdr::parameter_as_constellation(
+            blk,
+            "constellation",
+            "digital.constellation_bpsk().base()",
+        )
+        .context("digital_symbol_sync_xx")?;
+        let sps = Grc2FutureSdr::parameter_as_f32(blk, "sps", "2")?;
+        let loop_bw = Grc2FutureSdr::parameter_as_f32(blk, "loop_bw", "0.045")?;
+        let damping = Grc2FutureSdr::parameter_as_f32(blk, "damping", "1.0")?;
+        let ted_gain = Grc2FutureSdr::parameter_as_f32(blk, "ted_gain", "1.0")?;
+        let max_dev = Grc2FutureSdr::parameter_as_f32(blk, "max_dev", "1.5")?;
+        let osps = Grc2FutureSdr::parameter_as_f32(blk, "osps", "1")?;
+        if loop_bw < 0.0 || damping < 0.0 || ted_gain <= 0.0 {
+            bail!("digital_symbol_sync_xx: loop bandwidth and damping must be >= 0, TED gain > 0");
+        }
+        if osps != 1.0 {
+            bail!("digital_symbol_sync_xx: only one output sample per symbol is supported");
+        }
+        let blk: BlockId = match blk.parameter_or("type", "cc") {
+            "cc" => fg
+                .add_block(
+                    SymbolSync::<Complex32>::new(
+                        ted_type,
+                        constellation,
+                        sps,
+                        loop_bw,
+                        damping,
+                        ted_gain,
+                        max_dev,
+                    )
+                    .context("digital_symbol_sync_xx")?,
+                )
+                .into(),
+            "ff" => fg
+                .add_block(
+                    SymbolSync::<f32>::new(
+                        ted_type,
+                        constellation,
+                        sps,
+                        loop_bw,
+                        damping,
+                        ted_gain,
+                        max_dev,
+                    )
+                    .context("digital_symbol_sync_xx")?,
+                )
+                .into(),
+            item_type => bail!("digital_symbol_sync_xx: unhandled type {item_type}"),
+        };
+        Ok(Box::new(DefaultPortAdapter::new(blk)))
+    }
+}
@@ -0,0 +1,51 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter};
+use super::BlockInstance;
+use crate::blocks::FifoReader;
+use anyhow::{bail, Context, Result};
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::{BlockId, Flowgraph};
+
+#[derive(Clone, Copy)]
+pub struct FifoReaderPortAdapter {
+    blk: BlockId,
+}
+
+impl ConnectorAdapter for FifoReaderPortAdapter {
+    fn adapt_input_port(&self, port_name: &str) -> Result<(BlockId, &str)> {
+        match port_name {
+            "0" | "in" | "input" => Ok((self.blk, "input")),
+            _ => bail!("Unknown input port name {port_name}"),
+        }
+    }
+
+    fn adapt_output_port(&self, port_name: &str) -> Result<(BlockId, &str)> {
+        match port_name {
+            "0" | "out" | "output" => Ok((self.blk, "out")),
+            _ => bail!("Unknown output port name {port_name}"),
+        }
+    }
+}
+
+pub struct FifoReaderConverter {}
+
+impl BlockConverter for FifoReaderConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let filename = blk
+            .parameters
+            .get("file")
+            .context("fifo_reader: file must be defined")?;
+        // Type of the stream of the controlled block
+        let item_type = blk.parameter_or("type", "complex");
+        let block: BlockId = match item_type {
+            "complex" => fg.add_block(FifoReader::<Complex32>::new(filename)?).into(),
+            "float" => fg.add_block(FifoReader::<f32>::new(filename)?).into(),
+            "byte" => fg.add_block(FifoReader::<u8>::new(filename)?).into(),
+            _ => bail!("fifo_reader: Unhandled type {item_type}"),
+        };
+        Ok(Box::new(FifoReaderPortAdapter { blk: block }))
+    }
+}
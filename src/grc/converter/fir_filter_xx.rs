@@ -26,7 +26,8 @@ impl BlockConverter for FirFilterXxConverter {
         let taps: Vec<f32> = if taps.is_empty() {
             // This block definition was from csdr
             // so use dedicated parameters
-            let transition_bw = Grc2FutureSdr::parameter_as_f64(blk, "transition_bw", "1")?;
+            // A float in csdr, eg 0.05 gives 79 taps and not 81
+            let transition_bw = Grc2FutureSdr::parameter_as_f32(blk, "transition_bw", "1")? as f64;
             let window = blk
                 .parameters
                 .get("window")
@@ -44,7 +45,10 @@ impl BlockConverter for FirFilterXxConverter {
                 //"GAUSSIAN" => windows::gaussian(taps_length, alpha),
                 _ => bail!("fir_filter_xxx: Unknown window: {window}"),
             };
-            let taps = firdes::lowpass::<f32>(transition_bw, rect_win.as_slice());
+            if decimation < 2 {
+                bail!("fir_filter_xxx: decimation must be at least 2, not {decimation}");
+            }
+            let taps = firdes::lowpass::<f32>(0.5 / decimation as f64, rect_win.as_slice());
             taps
         } else {
             bail!("fir_filter_xxx: Unhandled taps definition")
@@ -5,25 +5,44 @@
 //! defined in the corresponding GRC block YAML files. This ensures that `fsdr-cli`
 //! can execute native `.grc` files without modification.
 
-use crate::cmd_grammar::CommandsParser;
-use crate::csdr_cmd::eval_cmd::EvalCmd;
+use crate::blocks::modulators::DigitalConstellation;
+use crate::cmd_grammar::{CommandsParser, Rule};
+use crate::csdr_cmd::eval_cmd::{EvalCmd, Variables};
 use crate::grc::Grc;
+use crate::stats::{FlowgraphStats, StatsFormat};
 use anyhow::bail;
 use anyhow::Context;
 use anyhow::Result;
-use futuresdr::runtime::Flowgraph;
-use std::collections::BTreeMap;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::{BlockId, Flowgraph};
 use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use super::converter_helper::*;
+pub mod afc;
+use self::afc::AfcConverter;
+pub mod afsk_modulator;
+use self::afsk_modulator::AfskModulatorConverter;
+pub mod ax25_print;
+use self::ax25_print::Ax25PrintConverter;
 pub mod analog_agc_xx;
 use self::analog_agc_xx::AnalogAgcXxConverter;
+pub mod analog_fastnoise_source_x;
+use self::analog_fastnoise_source_x::AnalogFastNoiseSourceConverter;
 pub mod analog_fm_deemph;
+pub mod analog_noise_source_x;
 use self::analog_fm_deemph::AnalogFmDeemphConverter;
+use self::analog_noise_source_x::AnalogNoiseSourceConverter;
 pub mod analog_nfm_deemph;
 use self::analog_nfm_deemph::DeemphasisNfmConverter;
+pub mod analog_pll;
+use self::analog_pll::AnalogPllConverter;
+pub mod analog_pwr_squelch_xx;
+use self::analog_pwr_squelch_xx::AnalogPwrSquelchConverter;
 pub mod analog_rail_ff;
 use self::analog_rail_ff::AnalogRailFfConverter;
+pub mod analog_sig_source_x;
+use self::analog_sig_source_x::AnalogSigSourceConverter;
 pub mod audio_sink;
 use self::audio_sink::AudioSinkConverter;
 use super::BlockInstance;
@@ -35,6 +54,28 @@ pub mod blocks_deinterleave;
 use self::blocks_deinterleave::DeinterleaveBlockConverter;
 pub mod digital_binary_slicer;
 use self::digital_binary_slicer::DigitalBinarySlicerConverter;
+pub mod digital_constellation_decoder_cb;
+use self::digital_constellation_decoder_cb::DigitalConstellationDecoderConverter;
+pub mod digital_constellation_modulator;
+use self::digital_constellation_modulator::DigitalConstellationModulatorConverter;
+pub mod digital_constellation_soft_decoder_cf;
+use self::digital_constellation_soft_decoder_cf::DigitalConstellationSoftDecoderConverter;
+pub mod digital_correlate_access_code_bb;
+use self::digital_correlate_access_code_bb::DigitalCorrelateAccessCodeConverter;
+pub mod digital_costas_loop_cc;
+use self::digital_costas_loop_cc::DigitalCostasLoopConverter;
+pub mod digital_diff_encoder_bb;
+use self::digital_diff_encoder_bb::DigitalDiffCoderConverter;
+pub mod digital_fll_band_edge_cc;
+use self::digital_fll_band_edge_cc::DigitalFllBandEdgeConverter;
+pub mod digital_hdlc_deframer_bp;
+use self::digital_hdlc_deframer_bp::DigitalHdlcDeframerConverter;
+pub mod digital_hdlc_framer_pb;
+use self::digital_hdlc_framer_pb::DigitalHdlcFramerConverter;
+pub mod digital_scrambler_bb;
+use self::digital_scrambler_bb::{DigitalAdditiveScramblerConverter, DigitalScramblerConverter};
+pub mod digital_symbol_sync_xx;
+use self::digital_symbol_sync_xx::DigitalSymbolSyncConverter;
 pub mod dsb;
 use self::dsb::DsbConverter;
 pub mod blocks_complex_to_real;
@@ -49,6 +90,8 @@ pub mod blocks_float_to_complex;
 use self::blocks_float_to_complex::FloatToComplexConverter;
 pub mod blocks_freqshift_cc;
 use self::blocks_freqshift_cc::FreqShiftCcConverter;
+pub mod blocks_head;
+use self::blocks_head::HeadConverter;
 pub mod blocks_multiply_const_vxx;
 use self::blocks_multiply_const_vxx::MulConstVxConverter;
 pub mod blocks_null_sink;
@@ -57,16 +100,28 @@ pub mod blocks_pack_k_bits;
 use self::blocks_pack_k_bits::PackBitsConverter;
 pub mod blocks_throttle;
 use self::blocks_throttle::ThrottleConverter;
+pub mod blocks_vector_source_x;
+use self::blocks_vector_source_x::BlocksVectorSourceConverter;
 pub mod blocks_complex_to_mag;
 use self::blocks_complex_to_mag::ComplexToMagConverter;
+pub mod channels_channel_model;
+use self::channels_channel_model::ChannelsChannelModelConverter;
+pub mod channels_fading_model;
+use self::channels_fading_model::ChannelsFadingModelConverter;
+pub mod chirp_source_x;
+use self::chirp_source_x::ChirpSourceConverter;
 pub mod clipdetect_ff;
 use self::clipdetect_ff::ClipDetectFfConverter;
 pub mod dc_bloker_xx;
 use self::dc_bloker_xx::DcBlockerXx;
 pub mod dump;
 use self::dump::DumpConverter;
+pub mod fifo_reader;
+use self::fifo_reader::FifoReaderConverter;
 pub mod fir_filter_xx;
 use self::fir_filter_xx::FirFilterXxConverter;
+pub mod analog_frequency_modulator_fc;
+use self::analog_frequency_modulator_fc::AnalogFrequencyModulatorConverter;
 pub mod analog_quadrature_demod;
 use self::analog_quadrature_demod::AnalogQuadratureDemoConverter;
 pub mod low_pass_filter;
@@ -77,6 +132,8 @@ pub mod pattern_search;
 use self::pattern_search::PatternSearchConverter;
 pub mod rational_resampler_xxx;
 use self::rational_resampler_xxx::RationalResamplerXxConverter;
+pub mod through;
+use self::through::ThroughConverter;
 pub mod timing_recovery;
 use self::timing_recovery::TimingRecoveryConverter;
 pub mod weaver_ssb;
@@ -91,19 +148,44 @@ pub mod satellites_kiss_server_sink;
 use self::satellites_kiss_server_sink::SatellitesKissServerSinkConverter;
 pub mod satellites_kiss_client_source;
 use self::satellites_kiss_client_source::SatellitesKissClientSourceConverter;
+pub mod satellites_sync_to_pdu;
+use self::satellites_sync_to_pdu::SatellitesSyncToPduConverter;
+pub mod satellites_afsk_demodulator;
+use self::satellites_afsk_demodulator::SatellitesAfskDemodulatorConverter;
+pub mod satellites_ax25_deframer;
+use self::satellites_ax25_deframer::SatellitesAx25DeframerConverter;
 
 #[derive(Default)]
 pub struct Grc2FutureSdr {
     specific_converter: HashMap<String, Box<dyn MutBlockConverter>>,
+    stats: Option<FlowgraphStats>,
+    progress: bool,
 }
 
 impl Grc2FutureSdr {
     pub fn new() -> Grc2FutureSdr {
         Grc2FutureSdr {
             specific_converter: HashMap::new(),
+            stats: None,
+            progress: false,
         }
     }
 
+    /// Insert probes on stream connections of the next converted flowgraph,
+    /// to report per block statistics (see [`FlowgraphStats`]).
+    pub fn with_stats(&mut self, format: StatsFormat) {
+        self.stats = Some(FlowgraphStats::new(format));
+    }
+
+    pub fn take_stats(&mut self) -> Option<FlowgraphStats> {
+        self.stats.take()
+    }
+
+    /// Report progress of the file sources on stderr (see [`crate::blocks::ProgressMeter`]).
+    pub fn with_progress(&mut self) {
+        self.progress = true;
+    }
+
     #[allow(dead_code)]
     pub fn take(&mut self, k: &str) -> std::option::Option<Box<dyn MutBlockConverter + 'static>> {
         self.specific_converter.remove(k)
@@ -121,19 +203,53 @@ impl Grc2FutureSdr {
     fn block_converter(blk_def: &BlockInstance) -> Result<Box<dyn BlockConverter>> {
         let blk_type = &(blk_def.id[..]);
         let cvter: Box<dyn BlockConverter> = match blk_type {
+            "afc_cc" => Box::new(AfcConverter {}),
+            "afsk_modulator" => Box::new(AfskModulatorConverter {}),
             "analog_agc_xx" => Box::new(AnalogAgcXxConverter {}),
+            "ax25_print" => Box::new(Ax25PrintConverter {}),
+            "analog_fastnoise_source_x" => Box::new(AnalogFastNoiseSourceConverter {}),
+            "analog_noise_source_x" => Box::new(AnalogNoiseSourceConverter {}),
+            "analog_pll_carriertracking_cc" | "analog_pll_freqdet_cf" => {
+                Box::new(AnalogPllConverter {})
+            }
+            "analog_frequency_modulator_fc" => Box::new(AnalogFrequencyModulatorConverter {}),
             "analog_quadrature_demod_cf" => Box::new(AnalogQuadratureDemoConverter {}),
+            "analog_pwr_squelch_xx" => Box::new(AnalogPwrSquelchConverter {}),
             "analog_rail_ff" => Box::new(AnalogRailFfConverter {}),
-            "band_pass_filter" => Box::new(BandPassFilterConverter {}),
+            "analog_sig_source_x" => Box::new(AnalogSigSourceConverter {}),
+            "band_pass_filter" => Box::new(BandPassFilterConverter { tunable: false }),
             "audio_sink" => Box::new(AudioSinkConverter {}),
             "blocks_add_const_vxx" => Box::new(AddConstVxConverter {}),
             "blocks_deinterleave" => Box::new(DeinterleaveBlockConverter {}),
             "digital_binary_slicer_fb" => Box::new(DigitalBinarySlicerConverter {}),
+            "digital_constellation_decoder_cb" => Box::new(DigitalConstellationDecoderConverter {}),
+            "digital_constellation_modulator" => {
+                Box::new(DigitalConstellationModulatorConverter {})
+            }
+            "digital_constellation_soft_decoder_cf" => {
+                Box::new(DigitalConstellationSoftDecoderConverter {})
+            }
+            "digital_correlate_access_code_bb" => Box::new(DigitalCorrelateAccessCodeConverter {}),
+            "digital_costas_loop_cc" => Box::new(DigitalCostasLoopConverter {}),
+            "digital_diff_encoder_bb" | "digital_diff_decoder_bb" => {
+                Box::new(DigitalDiffCoderConverter {})
+            }
+            "digital_fll_band_edge_cc" => Box::new(DigitalFllBandEdgeConverter {}),
+            "digital_hdlc_deframer_bp" | "satellites_hdlc_deframer" => {
+                Box::new(DigitalHdlcDeframerConverter {})
+            }
+            "digital_hdlc_framer_pb" => Box::new(DigitalHdlcFramerConverter {}),
+            "digital_scrambler_bb" | "digital_descrambler_bb" => {
+                Box::new(DigitalScramblerConverter {})
+            }
+            "digital_additive_scrambler_bb" => Box::new(DigitalAdditiveScramblerConverter {}),
+            "digital_symbol_sync_xx" => Box::new(DigitalSymbolSyncConverter {}),
             "dsb" => Box::new(DsbConverter {}),
             "blocks_file_sink" => Box::new(FileSinkConverter {}),
-            "blocks_file_source" => Box::new(FileSourceConverter {}),
+            "blocks_file_source" => Box::new(FileSourceConverter { progress: false }),
             "blocks_float_to_complex" => Box::new(FloatToComplexConverter {}),
             "blocks_freqshift_cc" => Box::new(FreqShiftCcConverter {}),
+            "blocks_head" => Box::new(HeadConverter {}),
             "blocks_multiply_const_vxx" => Box::new(MulConstVxConverter {}),
             "blocks_uchar_to_float"
             | "blocks_char_to_float"
@@ -144,6 +260,10 @@ impl Grc2FutureSdr {
             | "convert_ff_c"
             | "blocks_short_to_float" => Box::new(ConvertBlockConverter {}),
             "blocks_null_sink" => Box::new(NullSinkConverter {}),
+            "blocks_vector_source_x" => Box::new(BlocksVectorSourceConverter {}),
+            "channels_channel_model" => Box::new(ChannelsChannelModelConverter {}),
+            "channels_fading_model" => Box::new(ChannelsFadingModelConverter {}),
+            "chirp_source_x" => Box::new(ChirpSourceConverter {}),
             "dump_u8" | "dump_f" | "dump_c" => Box::new(DumpConverter {}),
             "throttle_ff" | "blocks_throttle" => Box::new(ThrottleConverter {}),
             "realpart_cf" | "blocks_complex_to_real" => Box::new(RealpartCfConverter {}),
@@ -152,17 +272,28 @@ impl Grc2FutureSdr {
             "dc_blocker_xx" => Box::new(DcBlockerXx {}),
             "deemphasis_nfm_ff" | "analog_nfm_deemph" => Box::new(DeemphasisNfmConverter {}),
             "analog_fm_deemph" => Box::new(AnalogFmDeemphConverter {}),
+            "fifo_reader" => Box::new(FifoReaderConverter {}),
             "fir_filter_xxx" => Box::new(FirFilterXxConverter {}),
             "low_pass_filter" => Box::new(LowPassFilterConverter {}),
             "octave_complex_c" => Box::new(OctaveComplexConverter {}),
-            "blocks_pack_k_bits_bb" => Box::new(PackBitsConverter {}),
+            "blocks_pack_k_bits_bb"
+            | "blocks_unpack_k_bits_bb"
+            | "blocks_packed_to_unpacked_xx"
+            | "blocks_unpacked_to_packed_xx"
+            | "blocks_repack_bits_bb" => Box::new(PackBitsConverter {}),
             "pattern_search" => Box::new(PatternSearchConverter {}),
             "rational_resampler_xxx" => Box::new(RationalResamplerXxConverter {}),
+            "satellites_afsk_demodulator" => Box::new(SatellitesAfskDemodulatorConverter {}),
+            "satellites_ax25_deframer" => Box::new(SatellitesAx25DeframerConverter {}),
             "satellites_kiss_file_source" => Box::new(SatellitesKissFileSourceConverter {}),
             "satellites_fixedlen_to_pdu" => Box::new(SatellitesFixedlenToPduConverter {}),
             "satellites_kiss_file_sink" => Box::new(SatellitesKissFileSinkConverter {}),
             "satellites_kiss_server_sink" => Box::new(SatellitesKissServerSinkConverter {}),
             "satellites_kiss_client_source" => Box::new(SatellitesKissClientSourceConverter {}),
+            "satellites_sync_to_pdu"
+            | "satellites_sync_to_pdu_packed"
+            | "satellites_sync_to_pdu_soft" => Box::new(SatellitesSyncToPduConverter {}),
+            "through" => Box::new(ThroughConverter {}),
             "timing_recovery" => Box::new(TimingRecoveryConverter {}),
             "weaver_usb_cf" | "weaver_lsb_cf" => Box::new(WeaverSsbConverter {}),
             _ => bail!("Unknown GNU Radio block {blk_type}"),
@@ -182,18 +313,58 @@ impl Grc2FutureSdr {
             } else {
                 Grc2FutureSdr::block_converter(blk)?.convert(blk, fg)
             }
+        } else if self.progress && blk.id == "blocks_file_source" {
+            FileSourceConverter { progress: true }.convert(blk, fg)
         } else {
             Grc2FutureSdr::block_converter(blk)?.convert(blk, fg)
         }
     }
 
     pub fn convert_grc(&mut self, grc: Grc) -> Result<Flowgraph> {
+        let (fg, _ids) = self.convert_grc_with_ids(grc)?;
+        Ok(fg)
+    }
+
+    /// Same as [`Self::convert_grc`] but also return the FutureSDR block id of each GRC block,
+    /// eg to address them through the control port.
+    pub fn convert_grc_with_ids(
+        &mut self,
+        grc: Grc,
+    ) -> Result<(Flowgraph, BTreeMap<String, BlockId>)> {
         let mut fg = Flowgraph::new();
-        let fsdr_blocks = grc
+        let variables = Self::evaluate_variables(&grc)?;
+        let unevaluated: BTreeSet<&str> = grc
+            .blocks
+            .iter()
+            .filter(|blk| blk.id == "variable" && !variables.contains_key(&blk.name))
+            .map(|blk| &blk.name[..])
+            .collect();
+        let blocks: Vec<BlockInstance> = grc
             .blocks
             .iter()
-            .map(|blk| -> Result<Box<dyn ConnectorAdapter>> { self.convert_block(&mut fg, blk) });
-        let names: Vec<String> = grc.blocks.iter().map(|blk| blk.name.clone()).collect();
+            .filter(|blk| blk.id != "variable")
+            .map(|blk| Self::resolve_variables(blk, &variables, &unevaluated))
+            .collect::<Result<_>>()?;
+        if let Some(stats) = self.stats.as_mut() {
+            blocks.iter().for_each(|blk| stats.add_block(blk));
+        }
+        // Band pass filters whose cutoff is controlled, eg by a fifo_reader
+        let tunable: BTreeSet<String> = grc
+            .connections
+            .iter()
+            .filter(|connection| connection[3] == "cutoff")
+            .map(|connection| connection[2].clone())
+            .collect();
+        let fsdr_blocks = blocks
+            .iter()
+            .map(|blk| -> Result<Box<dyn ConnectorAdapter>> {
+                if blk.id == "band_pass_filter" && tunable.contains(&blk.name) {
+                    BandPassFilterConverter { tunable: true }.convert(blk, &mut fg)
+                } else {
+                    self.convert_block(&mut fg, blk)
+                }
+            });
+        let names: Vec<String> = blocks.iter().map(|blk| blk.name.clone()).collect();
         let mut names_to_adapter = BTreeMap::<String, Box<dyn ConnectorAdapter>>::new();
 
         for (name, adapter) in names.iter().zip(fsdr_blocks) {
@@ -215,7 +386,22 @@ impl Grc2FutureSdr {
             let tgt_port = connection[3].clone();
             let (tgt_blk, tgt_port) = tgt_blk.adapt_input_port(&tgt_port)?;
 
-            if fg
+            let probe = match self.stats.as_mut() {
+                // Message ports are named while stream ones are numbered
+                Some(stats) if connection[1].chars().all(|c| c.is_ascii_digit()) => {
+                    let src = blocks.iter().find(|blk| blk.name == connection[0]);
+                    let tgt = blocks.iter().find(|blk| blk.name == connection[2]);
+                    src.zip(tgt)
+                        .and_then(|(src, tgt)| stats.add_probe(&mut fg, src, tgt))
+                }
+                _ => None,
+            };
+            if let Some(probe) = probe {
+                fg.connect_dyn(src_blk, src_port, probe, "input")
+                    .context(format!("probing connection {connection:?}"))?;
+                fg.connect_dyn(probe, "output", tgt_blk, tgt_port)
+                    .context(format!("probing connection {connection:?}"))?;
+            } else if fg
                 .connect_dyn(src_blk, src_port, tgt_blk, tgt_port)
                 .is_err()
             {
@@ -223,7 +409,95 @@ impl Grc2FutureSdr {
                     .context("connecting message {connection}")?;
             }
         }
-        Ok(fg)
+        let ids = names_to_adapter
+            .iter()
+            .filter_map(|(name, adapter)| {
+                adapter
+                    .adapt_output_port("0")
+                    .or_else(|_| adapter.adapt_input_port("0"))
+                    .ok()
+                    .map(|(id, _)| (name.clone(), id))
+            })
+            .collect();
+        Ok((fg, ids))
+    }
+
+    /// Evaluate the GRC `variable` blocks.
+    /// Variables may refer to each other whatever their order, as long as there is no cycle.
+    /// Variables that cannot be evaluated, eg strings, lists or Python calls, are left out:
+    /// only the blocks using them fail to be converted.
+    pub fn evaluate_variables(grc: &Grc) -> Result<Variables> {
+        let mut variables = Variables::new();
+        let mut pending: Vec<&BlockInstance> = grc
+            .blocks
+            .iter()
+            .filter(|blk| blk.id == "variable")
+            .collect();
+        loop {
+            let before = pending.len();
+            pending.retain(|blk| {
+                let value = CommandsParser::parse_expr(blk.parameter_or("value", "0"))
+                    .and_then(|expr| expr.eval_with(&variables));
+                match value {
+                    Ok(value) => {
+                        variables.insert(blk.name.clone(), value);
+                        false
+                    }
+                    Err(_) => true,
+                }
+            });
+            if pending.len() == before {
+                return Ok(variables);
+            }
+        }
+    }
+
+    /// Replace parameters that are expressions using variables by their actual value.
+    fn resolve_variables(
+        blk: &BlockInstance,
+        variables: &Variables,
+        unevaluated: &BTreeSet<&str>,
+    ) -> Result<BlockInstance> {
+        let mut blk = blk.clone();
+        if variables.is_empty() && unevaluated.is_empty() {
+            return Ok(blk);
+        }
+        for (key, value) in blk.parameters.iter_mut() {
+            let resolved = Self::resolve_expr(value.trim(), variables, unevaluated)
+                .with_context(|| format!("{}: cannot evaluate {key}", blk.name))?;
+            if let Some(resolved) = resolved {
+                *value = resolved.to_string();
+            }
+        }
+        Ok(blk)
+    }
+
+    fn resolve_expr(
+        expr: &str,
+        variables: &Variables,
+        unevaluated: &BTreeSet<&str>,
+    ) -> Result<Option<f64>> {
+        let Ok(pair) = CommandsParser::parse_expr(expr) else {
+            return Ok(None);
+        };
+        if pair.as_str().len() != expr.len() {
+            // Not an expression, eg window.WIN_HAMMING
+            return Ok(None);
+        }
+        let identifiers: Vec<&str> = pair
+            .clone()
+            .into_inner()
+            .flatten()
+            .filter(|p| p.as_rule() == Rule::ident)
+            .map(|p| p.as_str())
+            .collect();
+        if let Some(name) = identifiers.iter().find(|name| unevaluated.contains(*name)) {
+            bail!("variable {name} cannot be evaluated");
+        }
+        if !identifiers.iter().any(|name| variables.contains_key(*name)) {
+            return Ok(None);
+        }
+        Ok(pair.eval_with(variables).ok())
     }
 
     pub fn parameter_as_f32<'i>(
@@ -231,17 +505,100 @@ impl Grc2FutureSdr {
         key: &'i str,
         default_value: impl Into<&'i str>,
     ) -> Result<f32> {
+        Ok(Self::parameter_as_f64(blk_def, key, default_value)? as f32)
+    }
+
+    pub fn parameter_as_f64<'i>(
+        blk_def: &'i BlockInstance,
+        key: &'i str,
+        default_value: impl Into<&'i str>,
+    ) -> Result<f64> {
         let expr = blk_def.parameter_or(key, default_value);
         let expr = CommandsParser::parse_expr(expr)?;
         EvalCmd::eval(&expr)
     }
 
-    pub fn parameter_as_f64<'i>(
+    /// Integer parameter, decimal or hexadecimal like `0x21`, eg a mask too long
+    /// to be exactly represented as a float.
+    pub fn parameter_as_u64<'i>(
         blk_def: &'i BlockInstance,
         key: &'i str,
         default_value: impl Into<&'i str>,
-    ) -> Result<f64> {
-        let value = Self::parameter_as_f32(blk_def, key, default_value)?;
-        Ok(value as f64)
+    ) -> Result<u64> {
+        let value = blk_def.parameter_or(key, default_value).trim();
+        let parsed = match value
+            .strip_prefix("0x")
+            .or_else(|| value.strip_prefix("0X"))
+        {
+            Some(hex) => u64::from_str_radix(hex, 16),
+            None => value.parse::<u64>(),
+        };
+        parsed.with_context(|| format!("{}: {key} must be an integer, not {value}", blk_def.id))
+    }
+
+    /// Parameter holding a Python list or tuple of real or complex values, eg FIR taps.
+    pub fn parameter_as_vector<'i>(
+        blk_def: &'i BlockInstance,
+        key: &'i str,
+        default_value: impl Into<&'i str>,
+    ) -> Result<Vec<Complex32>> {
+        Self::parse_vector(blk_def.parameter_or(key, default_value))
+    }
+
+    /// Parameter holding either the name of a constellation (eg `digital.constellation_qpsk().base()`)
+    /// or its points, indexed by symbol (eg `[-1-1j, 1-1j, -1+1j, 1+1j]`).
+    pub fn parameter_as_constellation<'i>(
+        blk_def: &'i BlockInstance,
+        key: &'i str,
+        default_value: impl Into<&'i str>,
+    ) -> Result<DigitalConstellation> {
+        let value = blk_def.parameter_or(key, default_value);
+        if value.trim_start().starts_with(['[', '(']) {
+            DigitalConstellation::custom(Self::parse_vector(value)?)
+        } else {
+            DigitalConstellation::try_from(value)
+        }
+    }
+
+    /// Parse a Python list or tuple like `(1, 0.5, -1j, 2+3j)`.
+    pub fn parse_vector(vector: &str) -> Result<Vec<Complex32>> {
+        vector
+            .trim()
+            .trim_start_matches(['[', '('])
+            .trim_end_matches([']', ')'])
+            .split(',')
+            .map(|item| item.split_whitespace().collect::<String>())
+            .filter(|item| !item.is_empty())
+            .map(|item| Self::parse_complex(&item))
+            .collect()
+    }
+
+    fn parse_complex(item: &str) -> Result<Complex32> {
+        let Some(item) = item.strip_suffix('j') else {
+            return Ok(Complex32::new(Self::parse_real(item)?, 0.0));
+        };
+        // Split `a+bj` before the sign of the imaginary part, but not inside an exponent like 1e-3
+        let bytes = item.as_bytes();
+        let split = (1..bytes.len()).rev().find(|&i| {
+            (bytes[i] == b'+' || bytes[i] == b'-') && !matches!(bytes[i - 1], b'e' | b'E')
+        });
+        let (re, im) = match split {
+            Some(i) => (Self::parse_real(&item[..i])?, &item[i..]),
+            None => (0.0, item),
+        };
+        let im = match im {
+            "" | "+" => 1.0,
+            "-" => -1.0,
+            im => Self::parse_real(im)?,
+        };
+        Ok(Complex32::new(re, im))
+    }
+
+    fn parse_real(item: &str) -> Result<f32> {
+        let expr = CommandsParser::parse_expr(item)?;
+        if expr.as_str().len() != item.len() {
+            bail!("invalid value {item}");
+        }
+        Ok(expr.eval()? as f32)
     }
 }
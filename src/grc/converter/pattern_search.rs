@@ -1,8 +1,9 @@
-use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, TunablePortAdapter};
 use super::{BlockInstance, Grc2FutureSdr};
 use crate::blocks::pattern_search::PatternSearch;
-use anyhow::Result;
-use futuresdr::runtime::Flowgraph;
+use crate::blocks::{AccessCode, CorrelateAccessCode, CorrelationOutput, Correlator};
+use anyhow::{Context, Result};
+use futuresdr::runtime::{BlockId, Flowgraph};
 
 pub struct PatternSearchConverter {}
 
@@ -18,9 +19,26 @@ impl BlockConverter for PatternSearchConverter {
             .split(",")
             .map(|x| x.parse::<u8>().unwrap())
             .collect();
-        let blk = PatternSearch::<u8>::new(values_after, pattern_values);
-        let blk = fg.add_block(blk);
-        let blk = DefaultPortAdapter::new(blk.into());
+        // With a threshold, the pattern is a sync word of bits allowing for bit errors
+        let blk: BlockId = if blk.parameter("threshold").is_some() {
+            let threshold = Grc2FutureSdr::parameter_as_f32(blk, "threshold", "0")?;
+            let invert = blk
+                .parameter_or("invert", "False")
+                .to_lowercase()
+                .parse::<bool>()
+                .context("pattern_search: invert")?;
+            let access_code = AccessCode::from_bits(&pattern_values).context("pattern_search")?;
+            let correlator = Correlator::new(access_code, threshold as u32, invert);
+            fg.add_block(CorrelateAccessCode::new(
+                correlator,
+                CorrelationOutput::Frame(values_after),
+            ))
+            .into()
+        } else {
+            fg.add_block(PatternSearch::<u8>::new(values_after, pattern_values))
+                .into()
+        };
+        let blk = TunablePortAdapter::new(blk, &["values_after"]);
         let blk = Box::new(blk);
         Ok(blk)
     }
@@ -0,0 +1,40 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::AfskDemodulator;
+use anyhow::{bail, Context, Result};
+use futuresdr::prelude::{DefaultCpuReader, DefaultCpuWriter};
+use futuresdr::runtime::Flowgraph;
+
+/// gr-satellites' `afsk_demodulator`, for real audio only
+pub struct SatellitesAfskDemodulatorConverter {}
+
+impl BlockConverter for SatellitesAfskDemodulatorConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let baudrate = Grc2FutureSdr::parameter_as_f32(blk, "baudrate", "1200")?;
+        let samp_rate = Grc2FutureSdr::parameter_as_f32(blk, "samp_rate", "48000")?;
+        let af_carrier = Grc2FutureSdr::parameter_as_f32(blk, "af_carrier", "1700")?;
+        // Negative when the mark tone is below the space one, as in Bell 202
+        let deviation = Grc2FutureSdr::parameter_as_f32(blk, "deviation", "-500")?;
+        let iq = blk
+            .parameter_or("iq", "False")
+            .to_lowercase()
+            .parse::<bool>()
+            .context("afsk_demodulator: iq")?;
+        if iq {
+            bail!("afsk_demodulator: IQ input is not supported");
+        }
+        let block = AfskDemodulator::<DefaultCpuReader<f32>, DefaultCpuWriter<f32>>::new(
+            samp_rate as f64,
+            baudrate as f64,
+            (af_carrier + deviation) as f64,
+            (af_carrier - deviation) as f64,
+        )?;
+        Ok(Box::new(DefaultPortAdapter::new(
+            fg.add_block(block).into(),
+        )))
+    }
+}
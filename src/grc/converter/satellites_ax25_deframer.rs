@@ -0,0 +1,68 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter};
+use super::BlockInstance;
+use crate::blocks::{HdlcDeframer, Lfsr};
+use anyhow::{bail, Context, Result};
+use futuresdr::blocks::Apply;
+use futuresdr::runtime::{BlockId, Flowgraph};
+
+/// Soft symbols are sliced, and descrambled, before being deframed
+#[derive(Clone, Copy)]
+pub struct Ax25DeframerPortAdapter {
+    slicer: BlockId,
+    deframer: BlockId,
+}
+
+impl ConnectorAdapter for Ax25DeframerPortAdapter {
+    fn adapt_input_port(&self, port_name: &str) -> Result<(BlockId, &str)> {
+        match port_name {
+            "0" | "in" | "input" => Ok((self.slicer, "input")),
+            _ => bail!("Unknown input port name {port_name}"),
+        }
+    }
+
+    fn adapt_output_port(&self, port_name: &str) -> Result<(BlockId, &str)> {
+        match port_name {
+            "0" | "out" | "pdus" => Ok((self.deframer, "pdus")),
+            _ => bail!("ax25_deframer unknown output port: {port_name}"),
+        }
+    }
+}
+
+/// gr-satellites' `ax25_deframer`: NRZI encoded HDLC frames of AX.25 length,
+/// optionally G3RUH scrambled as in 9600 baud packet radio
+pub struct SatellitesAx25DeframerConverter {}
+
+impl BlockConverter for SatellitesAx25DeframerConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let g3ruh_scrambler = blk
+            .parameter_or("g3ruh_scrambler", "False")
+            .to_lowercase()
+            .parse::<bool>()
+            .context("ax25_deframer: g3ruh_scrambler")?;
+        let mut descrambler = if g3ruh_scrambler {
+            let (mask, seed, len) = Lfsr::G3RUH;
+            Some(Lfsr::new(mask, seed, len)?)
+        } else {
+            None
+        };
+        let slicer: Apply<_, f32, u8> = Apply::new(move |x: &f32| -> u8 {
+            let bit = (*x >= 0.0) as u8;
+            match &mut descrambler {
+                Some(lfsr) => lfsr.next_bit_descramble(bit),
+                None => bit,
+            }
+        });
+        let slicer: BlockId = fg.add_block(slicer).into();
+        // Same bounds as gr-satellites, FCS included
+        let deframer = fg.add_block(HdlcDeframer::new(17, 330, true, true));
+        fg.connect_dyn(slicer, "output", &deframer, "input")?;
+        Ok(Box::new(Ax25DeframerPortAdapter {
+            slicer,
+            deframer: deframer.into(),
+        }))
+    }
+}
@@ -10,6 +10,12 @@ pub struct FixedlenToPduPortAdapter {
     blk: BlockId,
 }
 
+impl FixedlenToPduPortAdapter {
+    pub fn new(blk: BlockId) -> FixedlenToPduPortAdapter {
+        FixedlenToPduPortAdapter { blk }
+    }
+}
+
 impl ConnectorAdapter for FixedlenToPduPortAdapter {
     fn adapt_input_port(&self, port_name: &str) -> Result<(BlockId, &str)> {
         match port_name {
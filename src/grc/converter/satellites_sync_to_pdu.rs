@@ -0,0 +1,42 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter};
+use super::satellites_fixedlen_to_pdu::FixedlenToPduPortAdapter;
+use super::{BlockInstance, Grc2FutureSdr};
+use crate::blocks::{AccessCode, Correlator, SyncToPdu};
+use anyhow::{bail, Context, Result};
+use futuresdr::runtime::{BlockId, Flowgraph};
+
+/// `satellites_sync_to_pdu` (unpacked bits), `satellites_sync_to_pdu_packed`
+/// (packed bytes) and `satellites_sync_to_pdu_soft` (soft symbols)
+pub struct SatellitesSyncToPduConverter {}
+
+impl BlockConverter for SatellitesSyncToPduConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let packet_len = Grc2FutureSdr::parameter_as_f32(blk, "packlen", "0")? as usize;
+        if packet_len == 0 {
+            bail!("{}: packlen must be a positive number of bits", blk.id);
+        }
+        let sync = blk
+            .parameter("sync")
+            .with_context(|| format!("{}: sync must be defined", blk.id))?;
+        let access_code = AccessCode::try_from(&sync[..]).context(blk.id.clone())?;
+        let threshold = Grc2FutureSdr::parameter_as_f32(blk, "threshold", "0")?;
+        let correlator = Correlator::new(access_code, threshold as u32, false);
+        let block: BlockId = match &blk.id[..] {
+            "satellites_sync_to_pdu" => fg
+                .add_block(SyncToPdu::<u8>::new(correlator, packet_len))
+                .into(),
+            "satellites_sync_to_pdu_packed" => fg
+                .add_block(SyncToPdu::<u8>::packed(correlator, packet_len))
+                .into(),
+            "satellites_sync_to_pdu_soft" => fg
+                .add_block(SyncToPdu::<f32>::new(correlator, packet_len))
+                .into(),
+            _ => bail!("Unknown sync to PDU block: {}", blk.id),
+        };
+        Ok(Box::new(FixedlenToPduPortAdapter::new(block)))
+    }
+}
@@ -0,0 +1,29 @@
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::BlockInstance;
+use crate::blocks::ProgressMeter;
+use anyhow::{bail, Context, Result};
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Flowgraph;
+
+pub struct ThroughConverter {}
+
+impl BlockConverter for ThroughConverter {
+    fn convert(
+        &self,
+        blk: &BlockInstance,
+        fg: &mut Flowgraph,
+    ) -> Result<Box<dyn ConnectorAdapter>> {
+        let item_type = blk
+            .parameters
+            .get("type")
+            .context("through: item type must be defined")?;
+
+        let blk = match &(item_type[..]) {
+            "u8" | "uchar" | "byte" => fg.add_block(ProgressMeter::<u8>::new(None)).into(),
+            "f32" | "float" => fg.add_block(ProgressMeter::<f32>::new(None)).into(),
+            "c32" | "complex" => fg.add_block(ProgressMeter::<Complex32>::new(None)).into(),
+            _ => bail!("Unhandled through Type {item_type}"),
+        };
+        Ok(Box::new(DefaultPortAdapter::new(blk)))
+    }
+}
@@ -1,4 +1,4 @@
-use super::super::converter_helper::{BlockConverter, ConnectorAdapter, DefaultPortAdapter};
+use super::super::converter_helper::{BlockConverter, ConnectorAdapter, TunablePortAdapter};
 use super::{BlockInstance, Grc2FutureSdr};
 use crate::blocks::synchronizers::{TimingAlgorithm, TimingRecovery};
 use anyhow::{bail, Result};
@@ -27,7 +27,7 @@ impl BlockConverter for TimingRecoveryConverter {
         };
         let blk = TimingRecovery::<Complex32>::new(algo, decim, mu, max_error);
         let blk = fg.add_block(blk);
-        let blk = DefaultPortAdapter::new(blk.into());
+        let blk = TunablePortAdapter::new(blk.into(), &["mu", "max_error"]);
         let blk = Box::new(blk);
         Ok(blk)
     }
@@ -63,6 +63,77 @@ impl ConnectorAdapter for DefaultPortAdapter {
     }
 }
 
+/// Same as [`DefaultPortAdapter`] but also expose message inputs
+/// used to change parameters of the block at runtime.
+#[derive(Clone, Copy)]
+pub struct TunablePortAdapter {
+    blk: BlockId,
+    message_inputs: &'static [&'static str],
+}
+
+impl TunablePortAdapter {
+    pub fn new(blk: BlockId, message_inputs: &'static [&'static str]) -> TunablePortAdapter {
+        TunablePortAdapter {
+            blk,
+            message_inputs,
+        }
+    }
+}
+
+impl ConnectorAdapter for TunablePortAdapter {
+    fn adapt_input_port(&self, port_name: &str) -> Result<(BlockId, &str)> {
+        if let Some(port) = self.message_inputs.iter().find(|p| **p == port_name) {
+            return Ok((self.blk, port));
+        }
+        match port_name {
+            "0" | "in" | "input" => Ok((self.blk, "input")),
+            _ => bail!("Unknown input port name {port_name}"),
+        }
+    }
+
+    fn adapt_output_port(&self, port_name: &str) -> Result<(BlockId, &str)> {
+        match port_name {
+            "0" | "out" | "output" => Ok((self.blk, "output")),
+            _ => bail!("Unknown output port name {port_name}"),
+        }
+    }
+}
+
+/// Same as [`DefaultPortAdapter`] for blocks with several outputs:
+/// GNU Radio's output port `n` is the `n`th of `outputs`.
+#[derive(Clone, Copy)]
+pub struct MultipleOutputsPortAdapter {
+    blk: BlockId,
+    outputs: &'static [&'static str],
+}
+
+impl MultipleOutputsPortAdapter {
+    pub fn new(blk: BlockId, outputs: &'static [&'static str]) -> MultipleOutputsPortAdapter {
+        MultipleOutputsPortAdapter { blk, outputs }
+    }
+}
+
+impl ConnectorAdapter for MultipleOutputsPortAdapter {
+    fn adapt_input_port(&self, port_name: &str) -> Result<(BlockId, &str)> {
+        match port_name {
+            "0" | "in" | "input" => Ok((self.blk, "input")),
+            _ => bail!("Unknown input port name {port_name}"),
+        }
+    }
+
+    fn adapt_output_port(&self, port_name: &str) -> Result<(BlockId, &str)> {
+        let port = match port_name.parse::<usize>() {
+            Ok(index) => self.outputs.get(index),
+            Err(_) => self.outputs.iter().find(|p| **p == port_name),
+        };
+        match port {
+            Some(port) => Ok((self.blk, port)),
+            None if port_name == "out" => Ok((self.blk, "output")),
+            None => bail!("Unknown output port name {port_name}"),
+        }
+    }
+}
+
 pub type BlockFactory = Box<dyn FnOnce(&mut Flowgraph) -> BlockId>;
 
 pub struct PredefinedBlockConverter {
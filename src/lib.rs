@@ -2,6 +2,7 @@ pub extern crate async_trait;
 #[macro_use]
 extern crate pest_derive;
 
+pub mod bench_cmd;
 pub mod cmd_grammar;
 pub mod cmd_line;
 // pub mod csdr;
@@ -11,6 +12,10 @@ pub mod grc;
 pub mod grc_cmd;
 pub mod iqengine_blockconverter;
 pub mod iqengine_userdef;
+pub mod repl;
+pub mod run_cmd;
+pub mod selftest;
+pub mod stats;
 
 pub fn join(iter: impl Iterator<Item = String>) -> String {
     iter.fold(String::new(), |mut a, b| {
@@ -9,13 +9,17 @@ extern crate axum;
 
 use cmd_grammar::Rule;
 use pest::error::ErrorVariant;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use std::{eprintln, println};
 
 use self::grc::GrcParser;
 use anyhow::{bail, Context, Ok, Result};
 use cmd_line::HighLevelCmdLine;
-use futuresdr::runtime::Flowgraph;
-use futuresdr::runtime::Runtime;
+use futures::executor::block_on;
+use futuresdr::runtime::{Flowgraph, Runtime};
 use itertools::join;
 mod grc;
 use grc::Grc;
@@ -23,29 +27,88 @@ use grc::Grc;
 // use csdr::CsdrParser;
 use grc::converter::Grc2FutureSdr;
 
+pub mod bench_cmd;
+use bench_cmd::BenchCmd;
 pub mod cmd_line;
 pub mod csdr_cmd;
-use crate::csdr_cmd::CsdrCmd;
+use crate::csdr_cmd::macro_cmd::Macro;
+use crate::csdr_cmd::{CsdrCmd, ScriptParser};
 pub mod cmd_grammar;
 pub mod grc_cmd;
 use grc_cmd::GrcCmd;
+pub mod repl;
+pub mod run_cmd;
+use run_cmd::RunCmd;
+pub mod selftest;
+pub mod stats;
+use stats::{StatsFormat, StatsReporter};
 pub mod iqengine_cmd;
 use iqengine_cmd::IQEngineCmd;
 pub mod blocks;
 pub mod iqengine_blockconverter;
 mod iqengine_plugin;
 
+/// Period of the statistics printed while the flowgraph runs with `--stats`
+const STATS_PERIOD: Duration = Duration::from_secs(5);
+
+/// Exit status when the flowgraph was stopped by SIGINT or SIGTERM, like shells do for SIGINT
+const INTERRUPTED_EXIT_CODE: i32 = 130;
+
 fn usage() -> Result<Grc> {
     let msg = "Usage:\n\
     \tfsdr-cli grc file.grc\n\
+    \tfsdr-cli run [--output file.grc] pipeline.fsdr\n\
+    \tfsdr-cli repl\n\
+    \tfsdr-cli bench [--samples N] [--seconds S] [--json] \"csdr ... \\| [csdr] ....\"\n\
+    \tfsdr-cli selftest [command]\n\
     \tfsdr-cli iqengine [conf.yml]\n\
     \tfsdr-cli  function_name <function_param1> <function_param2> [optional_param] ....\n\
     \tfsdr-cli \"csdr ... \\| [csdr] ....\" \n\
     \tfsdr-cli \"csdr ... ! [csdr] ....\" \n\
+\n\
+Options:\n\
+    \t--ctrlport[=<host>:<port>]\tenable FutureSDR control port to tune blocks at runtime\n\
+    \t--stats[=table|json]\t\tprint per block statistics on stderr while running\n\
+    \t--progress\t\t\tprint progress of file sources on stderr\n\
 \n";
+    let mut msg = msg.to_string();
+    msg.push_str(&Macro::help(Macro::from_config()));
     bail!(msg);
 }
 
+/// FutureSDR reads its configuration from `config.toml` and `FUTURESDR_*` environment variables
+/// when the runtime is first created, so it must be called before.
+fn enable_ctrlport(bind: Option<&str>) {
+    std::env::set_var("FUTURESDR_CTRLPORT_ENABLE", "true");
+    if let Some(bind) = bind {
+        std::env::set_var("FUTURESDR_CTRLPORT_BIND", bind);
+    }
+}
+
+/// Run the flowgraph until it finishes or SIGINT/SIGTERM is received.
+/// In the later case, the flowgraph is terminated so that sinks are flushed and sockets closed
+/// before returning `true`. A second signal exits immediately.
+fn run_until_signal(fg: Flowgraph) -> Result<bool> {
+    let runtime = Runtime::new();
+    let (task, handle) = runtime.start_sync(fg)?;
+    let interrupted = Arc::new(AtomicBool::new(false));
+    let signaled = interrupted.clone();
+    ctrlc::set_handler(move || {
+        if signaled.swap(true, Ordering::SeqCst) {
+            std::process::exit(INTERRUPTED_EXIT_CODE);
+        }
+        eprintln!("Terminating flowgraph...");
+        let mut handle = handle.clone();
+        if let Err(err) = block_on(handle.terminate()) {
+            eprintln!("Failed to terminate flowgraph: {err}");
+            std::process::exit(INTERRUPTED_EXIT_CODE);
+        }
+    })
+    .context("failed to install signal handler")?;
+    block_on(task)?;
+    Ok(interrupted.load(Ordering::SeqCst))
+}
+
 fn main() -> Result<()> {
     let mut input = std::env::args();
     input.next(); // skip binary name
@@ -60,7 +123,7 @@ fn main() -> Result<()> {
     // });
     let one_liner = one_liner.trim();
     //println!("actual input: '{input}'");
-    let input = cmd_grammar::CommandsParser::parse_main(one_liner);
+    let input = cmd_grammar::CommandsParser::parse_main_with_options(one_liner);
 
     if let Err(err) = input {
         match err.downcast_ref::<pest::error::Error<Rule>>() {
@@ -94,12 +157,46 @@ fn main() -> Result<()> {
         }
         std::process::exit(1);
     }
-    let input = input?;
+    let (options, input) = input?;
+    let mut ctrlport = false;
+    let mut stats_format = None;
+    let mut progress = false;
+    for option in options {
+        match option.as_rule() {
+            Rule::ctrlport_opt => {
+                ctrlport = true;
+                enable_ctrlport(option.into_inner().next().map(|bind| bind.as_str()));
+            }
+            Rule::progress_opt => progress = true,
+            Rule::stats_opt => {
+                let format = option.into_inner().next().map_or("table", |f| f.as_str());
+                stats_format = Some(StatsFormat::try_from(format)?);
+            }
+            _ => {}
+        }
+    }
 
     if input.is_help_cmd() {
         usage()?;
         return Ok(());
     }
+    if input.is_repl_cmd() {
+        return repl::Repl::new().interact();
+    }
+    if let Some(selftest_cmd) = input.as_selftest_cmd() {
+        let filter = selftest_cmd.clone().into_inner().next();
+        return selftest::run_selftest(filter.map(|name| name.as_str()));
+    }
+    if let Some(bench_cmd) = input.as_bench_cmd() {
+        let options = bench_cmd.bench_options()?;
+        let report = bench_cmd::run_bench(bench_cmd.bench_grc()?, &options)?;
+        if options.json {
+            println!("{}", report.to_json()?);
+        } else {
+            print!("{}", report.to_table());
+        }
+        return Ok(());
+    }
     let mut fg: Option<Grc> = None;
     if let Some(iqengine_cmd) = input.as_iqengine_cmd() {
         #[cfg(not(feature = "iqengine"))]
@@ -115,6 +212,14 @@ fn main() -> Result<()> {
         let filename = grc_cmd.filename();
         // println!("Loading {filename}...");
         fg = Some(grc::GrcParser::load(filename)?);
+    } else if let Some(run_cmd) = input.as_run_cmd() {
+        let script = ScriptParser::load(run_cmd.filename()?)?;
+        if let Some(output) = run_cmd.output()? {
+            GrcParser::save(output, &script).context("failed to save GRC file")?;
+            println!("Flowgraph saved into {output:?}");
+            return Ok(());
+        }
+        fg = Some(script);
     } else if let Some(csdr_cmd) = input.as_csdr_cmd() {
         fg = csdr_cmd.parse()?;
         if fg.is_none() {
@@ -131,7 +236,29 @@ fn main() -> Result<()> {
     }
 
     let fg = fg.context("No flowgraph was defined. Please check your command line arguments.")?;
-    let fg: Flowgraph = Grc2FutureSdr::new().convert_grc(fg)?;
-    Runtime::new().run(fg)?;
+    let mut converter = Grc2FutureSdr::new();
+    if let Some(format) = stats_format {
+        converter.with_stats(format);
+    }
+    if progress {
+        converter.with_progress();
+    }
+    let (fg, ids) = converter.convert_grc_with_ids(fg)?;
+    if ctrlport {
+        for (name, id) in ids.iter() {
+            eprintln!("ctrlport: {name} is /api/fg/0/block/{}/", id.0);
+        }
+    }
+    let reporter = converter
+        .take_stats()
+        .map(|stats| StatsReporter::start(stats, STATS_PERIOD));
+    let interrupted = run_until_signal(fg)?;
+    if let Some(reporter) = reporter {
+        reporter.finish()?;
+    }
+    if interrupted {
+        std::io::stdout().flush()?;
+        std::process::exit(INTERRUPTED_EXIT_CODE);
+    }
     Ok(())
 }
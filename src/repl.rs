@@ -0,0 +1,282 @@
+use crate::csdr_cmd::macro_cmd::Macro;
+use crate::csdr_cmd::ScriptParser;
+use crate::grc::builder::{GraphLevel, GrcBuilder};
+use crate::grc::converter::Grc2FutureSdr;
+use crate::grc::{Grc, GrcParser};
+use anyhow::{bail, Context, Result};
+use futures::executor::block_on;
+use futuresdr::runtime::{Flowgraph, FlowgraphHandle, Runtime};
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Editor, Helper};
+use std::collections::BTreeSet;
+use std::thread::{self, JoinHandle};
+
+const GRAMMAR: &str = include_str!("cmd_line.pest");
+
+const REPL_COMMANDS: [&str; 7] = ["help", "show", "save", "undo", "run", "stop", "quit"];
+
+const REPL_HELP: &str = "Each line is appended to the pipeline, like in a pipeline script.\n\
+    \tshow\t\tprint the current flowgraph\n\
+    \tsave file.grc\tsave the current flowgraph\n\
+    \tundo\t\tremove the last line\n\
+    \trun\t\tstart the current flowgraph in background\n\
+    \tstop\t\tstop the running flowgraph\n\
+    \t= expr\t\tevaluate an expression\n\
+    \tquit\t\texit (or Ctrl-D)";
+
+pub enum ReplAction {
+    Continue,
+    Quit,
+}
+
+/// Flowgraph started by `run`, executed by its own runtime in a background thread.
+struct RunningFlowgraph {
+    handle: FlowgraphHandle,
+    thread: JoinHandle<Result<()>>,
+}
+
+impl RunningFlowgraph {
+    fn start(fg: Flowgraph) -> Result<RunningFlowgraph> {
+        let (handle_tx, handle_rx) = std::sync::mpsc::channel();
+        let thread = thread::spawn(move || -> Result<()> {
+            let runtime = Runtime::new();
+            let (task, handle) = runtime.start_sync(fg)?;
+            let _ = handle_tx.send(handle);
+            block_on(task)?;
+            Ok(())
+        });
+        match handle_rx.recv() {
+            Ok(handle) => Ok(RunningFlowgraph { handle, thread }),
+            Err(_) => {
+                let res = thread
+                    .join()
+                    .map_err(|_| anyhow::anyhow!("flowgraph thread panicked"))?;
+                res.context("failed to start flowgraph")?;
+                bail!("failed to start flowgraph")
+            }
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    fn stop(mut self) -> Result<()> {
+        if !self.thread.is_finished() {
+            block_on(self.handle.terminate())?;
+        }
+        self.join()
+    }
+
+    fn join(self) -> Result<()> {
+        self.thread
+            .join()
+            .map_err(|_| anyhow::anyhow!("flowgraph thread panicked"))?
+    }
+}
+
+/// Interactive shell building a pipeline one line at a time (`fsdr-cli repl`).
+#[derive(Default)]
+pub struct Repl {
+    parser: ScriptParser,
+    grc: GrcBuilder<GraphLevel>,
+    /// Previous states of the pipeline, for `undo`
+    history: Vec<GrcBuilder<GraphLevel>>,
+    running: Option<RunningFlowgraph>,
+}
+
+impl Repl {
+    pub fn new() -> Repl {
+        Repl::default()
+    }
+
+    pub fn execute(&mut self, line: &str) -> Result<ReplAction> {
+        self.check_running();
+        let line = line.trim();
+        let (cmd, arg) = match line.split_once(char::is_whitespace) {
+            Some((cmd, arg)) => (cmd, Some(arg.trim())),
+            None => (line, None),
+        };
+        match (cmd, arg) {
+            ("", None) => {}
+            ("help", None) => println!("{REPL_HELP}"),
+            ("show", None) => print!("{}", self.describe()?),
+            ("save", Some(filename)) => {
+                GrcParser::save(filename, &self.current_grc()?)
+                    .context("failed to save GRC file")?;
+                println!("Flowgraph saved into {filename:?}");
+            }
+            ("save", None) => bail!("usage: save file.grc"),
+            ("undo", None) => {
+                self.grc = self.history.pop().context("nothing to undo")?;
+            }
+            ("run", None) => {
+                if self.running.is_some() {
+                    bail!("a flowgraph is already running, stop it first");
+                }
+                let fg = Grc2FutureSdr::new().convert_grc(self.current_grc()?)?;
+                self.running = Some(RunningFlowgraph::start(fg)?);
+            }
+            ("stop", None) => {
+                self.running
+                    .take()
+                    .context("no flowgraph is running")?
+                    .stop()?;
+            }
+            ("quit" | "exit", None) => {
+                if let Some(running) = self.running.take() {
+                    running.stop()?;
+                }
+                return Ok(ReplAction::Quit);
+            }
+            _ => {
+                let grc = self.parser.parse_line(line, self.grc.clone())?;
+                // Comments and `=` evaluations leave the pipeline unchanged
+                if !line.starts_with('#') && !line.starts_with('=') {
+                    self.history.push(std::mem::replace(&mut self.grc, grc));
+                }
+            }
+        }
+        Ok(ReplAction::Continue)
+    }
+
+    /// Current pipeline, completed with a sink if needed.
+    pub fn current_grc(&self) -> Result<Grc> {
+        let mut grc = self.grc.clone();
+        grc.ensure_sink()?;
+        grc.build()
+    }
+
+    /// Human readable description of the pipeline in progress.
+    pub fn describe(&self) -> Result<String> {
+        let grc = self.grc.build()?;
+        let mut description = String::new();
+        for blk in grc.blocks.iter() {
+            let parameters = blk
+                .parameters
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<String>>()
+                .join(" ");
+            description.push_str(&format!("{} ({}) {parameters}\n", blk.name, blk.id));
+        }
+        for [src, src_port, tgt, tgt_port] in grc.connections.iter() {
+            description.push_str(&format!("{src}:{src_port} -> {tgt}:{tgt_port}\n"));
+        }
+        Ok(description)
+    }
+
+    /// Words that can be completed: commands and keywords of the grammar,
+    /// REPL commands, variables and macros.
+    pub fn words(&self) -> BTreeSet<String> {
+        let mut words = grammar_keywords();
+        words.extend(REPL_COMMANDS.iter().map(|cmd| cmd.to_string()));
+        words.extend(self.grc.variables().keys().cloned());
+        words.extend(self.grc.macros().keys().cloned());
+        words.extend(Macro::from_config().keys().cloned());
+        words
+    }
+
+    fn check_running(&mut self) {
+        if self
+            .running
+            .as_ref()
+            .is_some_and(RunningFlowgraph::is_finished)
+        {
+            if let Some(running) = self.running.take() {
+                match running.join() {
+                    Ok(()) => println!("flowgraph finished"),
+                    Err(err) => eprintln!("flowgraph failed: {err:#}"),
+                }
+            }
+        }
+    }
+
+    /// Read and execute lines from the terminal until `quit` or Ctrl-D.
+    pub fn interact(&mut self) -> Result<()> {
+        let mut editor: Editor<ReplHelper, DefaultHistory> = Editor::new()?;
+        editor.set_helper(Some(ReplHelper {
+            words: self.words(),
+        }));
+        loop {
+            let line = match editor.readline("fsdr> ") {
+                Ok(line) => line,
+                Err(ReadlineError::Interrupted) => continue,
+                Err(ReadlineError::Eof) => {
+                    self.execute("quit")?;
+                    return Ok(());
+                }
+                Err(err) => return Err(err.into()),
+            };
+            let _ = editor.add_history_entry(line.as_str());
+            match self.execute(&line) {
+                Ok(ReplAction::Quit) => return Ok(()),
+                Ok(ReplAction::Continue) => {}
+                Err(err) => eprintln!("\x1b[0;31mError:\x1b[0m {err:#}"),
+            }
+            if let Some(helper) = editor.helper_mut() {
+                helper.words = self.words();
+            }
+        }
+    }
+}
+
+/// Identifier-like string literals of the grammar, ie commands and keywords.
+fn grammar_keywords() -> BTreeSet<String> {
+    GRAMMAR
+        .split('"')
+        .skip(1)
+        .step_by(2)
+        .filter(|literal| {
+            literal.len() > 1
+                && literal.starts_with(|c: char| c.is_ascii_alphabetic())
+                && literal
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+struct ReplHelper {
+    words: BTreeSet<String>,
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &rustyline::Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let start = line[..pos]
+            .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .map(|sep| sep + 1)
+            .unwrap_or(0);
+        let prefix = &line[start..pos];
+        let candidates = self
+            .words
+            .iter()
+            .filter(|word| word.starts_with(prefix))
+            .cloned()
+            .collect();
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
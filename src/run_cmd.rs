@@ -0,0 +1,31 @@
+use crate::cmd_grammar::Rule;
+use anyhow::{Context, Result};
+use pest::iterators::Pair;
+
+pub trait RunCmd<'i> {
+    fn filename(&self) -> Result<&'i str>;
+    fn output(&self) -> Result<Option<&'i str>>;
+}
+
+impl<'i> RunCmd<'i> for Pair<'i, Rule> {
+    fn filename(&self) -> Result<&'i str> {
+        self.clone()
+            .into_inner()
+            .find(|arg| arg.as_rule() == Rule::filepath)
+            .map(|arg| arg.as_str())
+            .context("missing filepath to pipeline script")
+    }
+
+    fn output(&self) -> Result<Option<&'i str>> {
+        match self.clone().into_inner().next() {
+            Some(opt) if opt.as_rule() == Rule::csdr_save_opt => {
+                let filename = opt
+                    .into_inner()
+                    .next()
+                    .context("output filepath expected")?;
+                Ok(Some(filename.as_str()))
+            }
+            _ => Ok(None),
+        }
+    }
+}
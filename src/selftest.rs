@@ -0,0 +1,244 @@
+use crate::csdr_cmd::CsdrParser;
+use crate::grc::converter::Grc2FutureSdr;
+use crate::grc::Grc;
+use anyhow::{bail, Context, Result};
+use futuresdr::runtime::Runtime;
+use std::path::Path;
+
+/// A csdr command run on a checked-in input, whose output is compared with
+/// the one of csdr. See `tests/vectors/generate.py` to regenerate them.
+pub struct SelfTestCase {
+    pub name: &'static str,
+    pub command: &'static str,
+    /// Largest difference accepted on each value, eg 1 for a float to integer conversion
+    pub tolerance: f64,
+    pub input: &'static [u8],
+    pub reference: &'static [u8],
+    /// Why the command is known to diverge from csdr beyond its tolerance, if it does
+    pub known_divergence: Option<&'static str>,
+}
+
+macro_rules! selftest_case {
+    ($name:literal, $command:literal, $tolerance:expr) => {
+        selftest_case!($name, $command, $tolerance, None)
+    };
+    ($name:literal, $command:literal, $tolerance:expr, known_divergence: $why:literal) => {
+        selftest_case!($name, $command, $tolerance, Some($why))
+    };
+    ($name:literal, $command:literal, $tolerance:expr, $known_divergence:expr) => {
+        SelfTestCase {
+            name: $name,
+            command: $command,
+            tolerance: $tolerance,
+            input: include_bytes!(concat!("../tests/vectors/", $name, ".in")),
+            reference: include_bytes!(concat!("../tests/vectors/", $name, ".ref")),
+            known_divergence: $known_divergence,
+        }
+    };
+}
+
+pub const SELFTEST_CASES: &[SelfTestCase] = &[
+    selftest_case!("convert_u8_f", "convert_u8_f", 1e-6),
+    selftest_case!("convert_s8_f", "convert_s8_f", 1e-6),
+    selftest_case!("convert_s16_f", "convert_s16_f", 1e-6),
+    selftest_case!("convert_f_u8", "convert_f_u8", 1.0),
+    selftest_case!("convert_f_s8", "convert_f_s8", 1.0),
+    selftest_case!("convert_f_s16", "convert_f_s16", 1.0),
+    selftest_case!("realpart_cf", "realpart_cf", 0.0),
+    selftest_case!("amdemod_cf", "amdemod_cf", 1e-6),
+    selftest_case!("dsb_fc", "dsb_fc", 0.0),
+    selftest_case!("gain_ff", "gain_ff 0.5", 1e-6),
+    selftest_case!("limit_ff", "limit_ff 0.5", 0.0),
+    selftest_case!("clipdetect_ff", "clipdetect_ff", 0.0),
+    selftest_case!("shift_addition_cc", "shift_addition_cc 0.1", 1e-5),
+    selftest_case!("fir_decimate_cc", "fir_decimate_cc 2 0.05", 1e-5),
+    selftest_case!(
+        "fmdemod_quadri_cf",
+        "fmdemod_quadri_cf",
+        1e-5,
+        known_divergence: "csdr divides by the squared magnitude instead of taking the phase difference"
+    ),
+    selftest_case!("deemphasis_wfm_ff", "deemphasis_wfm_ff 48000 50e-6", 1e-5),
+    selftest_case!("fastdcblock_ff", "fastdcblock_ff", 1e-5),
+];
+
+/// Outcome of the comparison of an output with its reference
+#[derive(Debug, PartialEq)]
+pub enum Comparison {
+    Identical,
+    /// All values within tolerance, with the largest difference seen
+    WithinTolerance(f64),
+    LengthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    /// First value out of tolerance
+    Diverge {
+        index: usize,
+        expected: f64,
+        actual: f64,
+    },
+}
+
+impl Comparison {
+    pub fn passed(&self) -> bool {
+        matches!(self, Comparison::Identical | Comparison::WithinTolerance(_))
+    }
+}
+
+impl std::fmt::Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Comparison::Identical => write!(f, "identical"),
+            Comparison::WithinTolerance(error) => write!(f, "max error {error:e}"),
+            Comparison::LengthMismatch { expected, actual } => {
+                write!(f, "{actual} values instead of {expected}")
+            }
+            Comparison::Diverge {
+                index,
+                expected,
+                actual,
+            } => write!(f, "value #{index} is {actual} instead of {expected}"),
+        }
+    }
+}
+
+/// Decode raw samples of a GRC item type into scalar values, complex being interleaved I/Q.
+/// Like csdr, samples are in the native byte order.
+pub fn decode(item_type: &str, data: &[u8]) -> Result<Vec<f64>> {
+    let values = match item_type {
+        "byte" | "u8" => data.iter().map(|v| *v as f64).collect(),
+        "char" | "s8" => data.iter().map(|v| *v as i8 as f64).collect(),
+        "short" | "s16" => data
+            .chunks_exact(2)
+            .map(|v| i16::from_ne_bytes([v[0], v[1]]) as f64)
+            .collect(),
+        "float" | "complex" | "f32" | "c32" => data
+            .chunks_exact(4)
+            .map(|v| f32::from_ne_bytes([v[0], v[1], v[2], v[3]]) as f64)
+            .collect(),
+        _ => bail!("selftest: unhandled item type {item_type}"),
+    };
+    Ok(values)
+}
+
+pub fn compare(expected: &[f64], actual: &[f64], tolerance: f64) -> Comparison {
+    if expected.len() != actual.len() {
+        return Comparison::LengthMismatch {
+            expected: expected.len(),
+            actual: actual.len(),
+        };
+    }
+    let mut max_error: f64 = 0.0;
+    for (index, (expected, actual)) in expected.iter().zip(actual.iter()).enumerate() {
+        let error = (expected - actual).abs();
+        if error > tolerance || error.is_nan() {
+            return Comparison::Diverge {
+                index,
+                expected: *expected,
+                actual: *actual,
+            };
+        }
+        max_error = max_error.max(error);
+    }
+    if max_error == 0.0 {
+        Comparison::Identical
+    } else {
+        Comparison::WithinTolerance(max_error)
+    }
+}
+
+impl SelfTestCase {
+    /// Whether the comparison is the expected one: a match, or a divergence when it is known
+    pub fn as_expected(&self, comparison: &Comparison) -> bool {
+        comparison.passed() == self.known_divergence.is_none()
+    }
+
+    /// Run the command on the input vector and compare its output with the reference.
+    pub fn run(&self) -> Result<Comparison> {
+        let mut grc = CsdrParser::parse_multiple_commands(&format!("csdr {}", self.command))?
+            .context("selftest: no flowgraph")?;
+        let dir = std::env::temp_dir();
+        let prefix = format!("fsdr-cli-selftest-{}-{}", std::process::id(), self.name);
+        let input = dir.join(format!("{prefix}.in"));
+        let output = dir.join(format!("{prefix}.out"));
+        std::fs::write(&input, self.input)?;
+
+        let mut output_type = None;
+        for blk in grc.blocks.iter_mut() {
+            let file = match &blk.id[..] {
+                "blocks_file_source" => &input,
+                "blocks_file_sink" => {
+                    output_type = blk.parameter("type").cloned();
+                    &output
+                }
+                _ => continue,
+            };
+            blk.parameters
+                .insert("file".to_string(), file.display().to_string());
+        }
+        let output_type = output_type.context("selftest: command without output")?;
+
+        let result = Self::execute(grc, &output);
+        let _ = std::fs::remove_file(&input);
+        let data = result?;
+        let _ = std::fs::remove_file(&output);
+
+        let expected = decode(&output_type, self.reference)?;
+        let actual = decode(&output_type, &data)?;
+        Ok(compare(&expected, &actual, self.tolerance))
+    }
+
+    fn execute(grc: Grc, output: &Path) -> Result<Vec<u8>> {
+        let fg = Grc2FutureSdr::new().convert_grc(grc)?;
+        Runtime::new().run(fg)?;
+        std::fs::read(output).context("selftest: reading output")
+    }
+}
+
+/// Run the cases whose name starts with `filter` (all if `None`),
+/// printing a line per case, and fail if any diverges from csdr,
+/// except for known divergences which must still diverge.
+pub fn run_selftest(filter: Option<&str>) -> Result<()> {
+    let cases: Vec<&SelfTestCase> = SELFTEST_CASES
+        .iter()
+        .filter(|case| filter.is_none_or(|filter| case.name.starts_with(filter)))
+        .collect();
+    if cases.is_empty() {
+        bail!("no selftest matching {}", filter.unwrap_or_default());
+    }
+    let mut failures = Vec::new();
+    for case in cases {
+        match (case.run(), case.known_divergence) {
+            (Ok(comparison), None) if comparison.passed() => {
+                println!("ok      {:<16} {comparison}", case.command);
+            }
+            (Ok(comparison), None) => {
+                println!("DIVERGE {:<16} {comparison}", case.command);
+                failures.push(case.name);
+            }
+            (Ok(comparison), Some(why)) if !comparison.passed() => {
+                println!("XFAIL   {:<16} {comparison} ({why})", case.command);
+            }
+            (Ok(comparison), Some(_)) => {
+                println!(
+                    "XPASS   {:<16} {comparison}, no longer a known divergence",
+                    case.command
+                );
+                failures.push(case.name);
+            }
+            (Err(err), _) => {
+                println!("ERROR   {:<16} {err:#}", case.command);
+                failures.push(case.name);
+            }
+        }
+    }
+    if !failures.is_empty() {
+        bail!(
+            "{} command(s) diverge from csdr: {}",
+            failures.len(),
+            failures.join(", ")
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,243 @@
+use crate::blocks::{Probe, ProbeCounters};
+use crate::grc::builder::GrcItemType;
+use crate::grc::BlockInstance;
+use anyhow::Result;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::{BlockId, Flowgraph};
+use serde::Serialize;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StatsFormat {
+    Table,
+    Json,
+}
+
+impl TryFrom<&str> for StatsFormat {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        match value {
+            "table" => Ok(Self::Table),
+            "json" => Ok(Self::Json),
+            _ => anyhow::bail!("Unknown stats format: {value}"),
+        }
+    }
+}
+
+/// A probe inserted on a stream connection between two GRC blocks
+struct ConnectionProbe {
+    src: String,
+    tgt: String,
+    counters: Arc<ProbeCounters>,
+}
+
+/// Statistics of one GRC block, as measured on its stream connections
+#[derive(Debug, Serialize)]
+pub struct BlockStats {
+    pub name: String,
+    pub block_type: String,
+    /// Items delivered to its inputs
+    pub consumed: u64,
+    /// Items produced on its outputs
+    pub produced: u64,
+    /// Items per second, produced or consumed for sinks
+    pub throughput: f64,
+    /// Average fill of its input buffers, between 0.0 and 1.0
+    pub input_fill: Option<f64>,
+}
+
+/// Per block statistics of a running flowgraph (`--stats`).
+///
+/// FutureSDR does not report what happens inside blocks, so a [`Probe`]
+/// is inserted on each stream connection. A block whose input buffers are
+/// full while its output buffers are empty is the bottleneck of the chain.
+pub struct FlowgraphStats {
+    format: StatsFormat,
+    blocks: Vec<(String, String)>,
+    probes: Vec<ConnectionProbe>,
+}
+
+impl FlowgraphStats {
+    pub fn new(format: StatsFormat) -> FlowgraphStats {
+        FlowgraphStats {
+            format,
+            blocks: Vec::new(),
+            probes: Vec::new(),
+        }
+    }
+
+    pub fn add_block(&mut self, blk: &BlockInstance) {
+        self.blocks.push((blk.name.clone(), blk.id.clone()));
+    }
+
+    /// Add a probe for the stream going out of `src` into `tgt`, if its item type is known.
+    pub fn add_probe(
+        &mut self,
+        fg: &mut Flowgraph,
+        src: &BlockInstance,
+        tgt: &BlockInstance,
+    ) -> Option<BlockId> {
+        let counters = Arc::new(ProbeCounters::default());
+        let probe: BlockId = match Self::stream_type(src)? {
+            GrcItemType::U8 => fg.add_block(Probe::<u8>::new(counters.clone())).into(),
+            GrcItemType::F32 => fg.add_block(Probe::<f32>::new(counters.clone())).into(),
+            GrcItemType::C32 => fg
+                .add_block(Probe::<Complex32>::new(counters.clone()))
+                .into(),
+            _ => return None,
+        };
+        self.probes.push(ConnectionProbe {
+            src: src.name.clone(),
+            tgt: tgt.name.clone(),
+            counters,
+        });
+        Some(probe)
+    }
+
+    /// Item type of the output of a block, from its `type` parameter
+    /// or from the GNU Radio naming convention, eg `_cf` outputs floats.
+    fn stream_type(blk: &BlockInstance) -> Option<GrcItemType> {
+        if let Some(item_type) = blk.parameter("type") {
+            if let Ok(item_type) = GrcItemType::try_from(item_type) {
+                return Some(item_type);
+            }
+            // eg fir_filter_ccf
+            return Self::type_from_letter(item_type.chars().last()?);
+        }
+        let (_, suffix) = blk.id.rsplit_once('_')?;
+        if suffix.len() > 3 {
+            return None;
+        }
+        Self::type_from_letter(suffix.chars().last()?)
+    }
+
+    fn type_from_letter(letter: char) -> Option<GrcItemType> {
+        match letter {
+            'b' => Some(GrcItemType::U8),
+            'f' => Some(GrcItemType::F32),
+            'c' => Some(GrcItemType::C32),
+            _ => None,
+        }
+    }
+
+    pub fn blocks(&self, elapsed: Duration) -> Vec<BlockStats> {
+        let seconds = elapsed.as_secs_f64().max(f64::EPSILON);
+        self.blocks
+            .iter()
+            .map(|(name, block_type)| {
+                let inputs: Vec<&ConnectionProbe> =
+                    self.probes.iter().filter(|p| p.tgt == *name).collect();
+                let consumed = inputs.iter().map(|p| p.counters.items()).sum();
+                let produced = self
+                    .probes
+                    .iter()
+                    .filter(|p| p.src == *name)
+                    .map(|p| p.counters.items())
+                    .max()
+                    .unwrap_or(0);
+                let fills: Vec<f64> = inputs
+                    .iter()
+                    .filter_map(|p| p.counters.average_fill())
+                    .collect();
+                let input_fill = if fills.is_empty() {
+                    None
+                } else {
+                    Some(fills.iter().sum::<f64>() / fills.len() as f64)
+                };
+                let items = if produced > 0 { produced } else { consumed };
+                BlockStats {
+                    name: name.clone(),
+                    block_type: block_type.clone(),
+                    consumed,
+                    produced,
+                    throughput: items as f64 / seconds,
+                    input_fill,
+                }
+            })
+            .collect()
+    }
+
+    pub fn report(&self, elapsed: Duration) -> Result<String> {
+        let blocks = self.blocks(elapsed);
+        match self.format {
+            StatsFormat::Json => {
+                #[derive(Serialize)]
+                struct Report<'a> {
+                    elapsed: f64,
+                    blocks: &'a [BlockStats],
+                }
+                let report = Report {
+                    elapsed: elapsed.as_secs_f64(),
+                    blocks: &blocks,
+                };
+                Ok(serde_json::to_string(&report)?)
+            }
+            StatsFormat::Table => {
+                let mut table = format!(
+                    "after {:.1}s\n{:<32} {:<28} {:>12} {:>12} {:>12} {:>8}\n",
+                    elapsed.as_secs_f64(),
+                    "block",
+                    "type",
+                    "consumed",
+                    "produced",
+                    "items/s",
+                    "in fill"
+                );
+                for blk in blocks.iter() {
+                    let fill = blk
+                        .input_fill
+                        .map(|fill| format!("{:.0}%", fill * 100.0))
+                        .unwrap_or_else(|| "-".to_string());
+                    table.push_str(&format!(
+                        "{:<32} {:<28} {:>12} {:>12} {:>12.0} {:>8}\n",
+                        blk.name, blk.block_type, blk.consumed, blk.produced, blk.throughput, fill
+                    ));
+                }
+                Ok(table)
+            }
+        }
+    }
+}
+
+/// Print the statistics on stderr periodically while the flowgraph runs, then once more at the end.
+pub struct StatsReporter {
+    stop: Sender<()>,
+    thread: JoinHandle<FlowgraphStats>,
+    start: Instant,
+}
+
+impl StatsReporter {
+    pub fn start(stats: FlowgraphStats, period: Duration) -> StatsReporter {
+        let (stop, stopped) = mpsc::channel();
+        let start = Instant::now();
+        let thread = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(period) {
+                match stats.report(start.elapsed()) {
+                    Ok(report) => eprintln!("{report}"),
+                    Err(err) => eprintln!("stats: {err}"),
+                }
+            }
+            stats
+        });
+        StatsReporter {
+            stop,
+            thread,
+            start,
+        }
+    }
+
+    pub fn finish(self) -> Result<()> {
+        let elapsed = self.start.elapsed();
+        let _ = self.stop.send(());
+        let stats = self
+            .thread
+            .join()
+            .map_err(|_| anyhow::anyhow!("stats reporter panicked"))?;
+        eprintln!("{}", stats.report(elapsed)?);
+        Ok(())
+    }
+}
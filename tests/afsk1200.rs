@@ -0,0 +1,229 @@
+use anyhow::{bail, Context, Result};
+use fsdr_cli::blocks::{AfskDemodulator, Ax25Frame};
+use fsdr_cli::csdr_cmd::CsdrParser;
+use fsdr_cli::grc::converter::Grc2FutureSdr;
+use futuresdr::blocks::{MessageSource, VectorSink, VectorSource};
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::{Flowgraph, Pmt, Runtime};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+mod common;
+use common::TestMessageSink;
+
+/// Samples of a 16 bits mono WAV file, generated by `tests/vectors/generate_afsk1200.py`
+fn read_wav(path: &str) -> Result<(u32, Vec<f32>)> {
+    let bytes = std::fs::read(path).with_context(|| format!("cannot read {path}"))?;
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        bail!("{path}: not a WAV file");
+    }
+    let mut sample_rate = None;
+    let mut chunks = &bytes[12..];
+    while chunks.len() >= 8 {
+        let len = u32::from_le_bytes(chunks[4..8].try_into()?) as usize;
+        let body = chunks.get(8..8 + len).context("truncated WAV chunk")?;
+        match &chunks[0..4] {
+            b"fmt " => {
+                let channels = u16::from_le_bytes(body[2..4].try_into()?);
+                let bits = u16::from_le_bytes(body[14..16].try_into()?);
+                if channels != 1 || bits != 16 {
+                    bail!("{path}: 16 bits mono expected");
+                }
+                sample_rate = Some(u32::from_le_bytes(body[4..8].try_into()?));
+            }
+            b"data" => {
+                let samples = body
+                    .chunks_exact(2)
+                    .map(|s| i16::from_le_bytes([s[0], s[1]]) as f32 / 32768.0)
+                    .collect();
+                return Ok((sample_rate.context("fmt chunk expected")?, samples));
+            }
+            _ => {}
+        }
+        chunks = &chunks[(8 + len + len % 2).min(chunks.len())..];
+    }
+    bail!("{path}: no data chunk")
+}
+
+#[test]
+fn afsk_demodulator_mark_tone() -> Result<()> {
+    let rate = 48000.0;
+    let input: Vec<f32> = (0..48000)
+        .map(|n| (std::f32::consts::TAU * 1200.0 * n as f32 / rate).cos())
+        .collect();
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSource::<f32>::new(input));
+    let block_under_test = fg.add_block(AfskDemodulator::new(48000.0, 1200.0, 1200.0, 2200.0)?);
+    let snk = fg.add_block(VectorSink::<f32>::new(1200));
+    fg.connect_dyn(&src, "output", &block_under_test, "input")?;
+    fg.connect_dyn(&block_under_test, "output", &snk, "input")?;
+    Runtime::new().run(fg)?;
+    let snk = snk.get().unwrap();
+    let symbols = snk.items();
+    assert!((1199..=1200).contains(&symbols.len()), "{}", symbols.len());
+    // The space correlator only catches a little leakage of the mark tone
+    assert!(symbols.iter().all(|x| *x > 0.8));
+    Ok(())
+}
+
+#[test]
+fn afsk_demodulator_rejects_tone_above_nyquist() {
+    assert!(AfskDemodulator::<_, _>::new(8000.0, 1200.0, 1200.0, 4400.0).is_err());
+}
+
+#[test]
+fn afsk1200_demod_reference_packets() -> Result<()> {
+    let (sample_rate, samples) = read_wav("tests/vectors/afsk1200.wav")?;
+    assert_eq!(22050, sample_rate);
+    let grc = CsdrParser::parse_command(&format!("afsk1200_demod_f {sample_rate}"))
+        .expect("")
+        .unwrap();
+    let demod = &grc.blocks[1];
+    assert_eq!("satellites_afsk_demodulator", demod.id);
+    assert_eq!("22050", demod.parameters["samp_rate"]);
+    assert_eq!("1200", demod.parameters["baudrate"]);
+    let deframer = &grc.blocks[2];
+    assert_eq!("satellites_ax25_deframer", deframer.id);
+
+    let mut fg = Flowgraph::new();
+    let mut converter = Grc2FutureSdr::new();
+    let src = fg.add_block(VectorSource::<f32>::new(samples));
+    let demod = converter.convert_block(&mut fg, demod)?;
+    let deframer = converter.convert_block(&mut fg, deframer)?;
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let snk = fg.add_block(TestMessageSink::new(messages.clone()));
+    let (demod_in, demod_in_name) = demod.adapt_input_port("0")?;
+    let (demod_out, demod_out_name) = demod.adapt_output_port("0")?;
+    let (deframer_in, deframer_in_name) = deframer.adapt_input_port("0")?;
+    let (deframer_out, deframer_out_name) = deframer.adapt_output_port("0")?;
+    fg.connect_dyn(&src, "output", demod_in, demod_in_name)?;
+    fg.connect_dyn(demod_out, demod_out_name, deframer_in, deframer_in_name)?;
+    fg.connect_message(deframer_out, deframer_out_name, &snk, "in_port")?;
+    Runtime::new().run(fg)?;
+
+    let packets = messages
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|frame| Ok(Ax25Frame::parse(frame)?.to_tnc2()))
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(
+        vec![
+            "F4ABC-7>APRS,WIDE1-1:!4903.50N/07201.75W-fsdr-cli AFSK1200 test",
+            "F5XYZ>APZ001,WIDE1-1,WIDE2-2:>Testing the Bell 202 demodulator",
+            "F4ABC-7>APRS::F5XYZ    :Hello world{1",
+        ],
+        packets
+    );
+    Ok(())
+}
+
+#[test]
+fn afsk1200_demod_default_sample_rate() {
+    let grc = CsdrParser::parse_command("afsk1200_demod_f")
+        .expect("")
+        .unwrap();
+    assert_eq!("48000", grc.blocks[1].parameters["samp_rate"]);
+    assert!(CsdrParser::parse_multiple_commands(
+        "csdr fmdemod_quadri_cf ! afsk1200_demod_f 48000 ! save_kiss out.kiss"
+    )
+    .is_ok());
+}
+
+/// Address field of `callsign` and `ssid`, the extension bit set on the last one
+fn address(callsign: &str, ssid: u8, last: bool) -> Vec<u8> {
+    let mut field: Vec<u8> = format!("{callsign:<6}").bytes().map(|c| c << 1).collect();
+    field.push(0x60 | (ssid << 1) | last as u8);
+    field
+}
+
+/// An APRS position, as read from a KISS file with its data frame command
+fn kiss_aprs_frame() -> Vec<u8> {
+    let mut frame = vec![0x00];
+    frame.extend(address("APRS", 0, false));
+    frame.extend(address("F4ABC", 7, true));
+    frame.extend([0x03, 0xF0]);
+    frame.extend(b"!4903.50N/07201.75W-Beacon");
+    frame
+}
+
+#[test]
+fn afsk1200_mod_round_trip() -> Result<()> {
+    let grc = CsdrParser::parse_multiple_commands(
+        "csdr load_kiss in.kiss ! afsk1200_mod 48000 --preamble 16 ! fmmod_fc ! fmdemod_quadri_cf ! afsk1200_demod_f 48000",
+    )
+    .expect("")
+    .unwrap();
+    let ids = [
+        "afsk_modulator",
+        "analog_frequency_modulator_fc",
+        "analog_quadrature_demod_cf",
+        "satellites_afsk_demodulator",
+        "satellites_ax25_deframer",
+    ];
+    let blocks: Vec<_> = ids
+        .iter()
+        .map(|id| grc.blocks.iter().find(|blk| blk.id == *id).unwrap())
+        .collect();
+    assert_eq!("48000", blocks[0].parameters["samp_rate"]);
+    assert_eq!("16", blocks[0].parameters["preamble"]);
+    assert_eq!("3", blocks[0].parameters["tail"]);
+    assert_eq!("pi", blocks[1].parameters["sensitivity"]);
+
+    let mut fg = Flowgraph::new();
+    let mut converter = Grc2FutureSdr::new();
+    let src = fg.add_block(MessageSource::new(
+        Pmt::Blob(kiss_aprs_frame()),
+        Duration::from_millis(10),
+        Some(2),
+    ));
+    let blocks = blocks
+        .iter()
+        .map(|blk| converter.convert_block(&mut fg, blk))
+        .collect::<Result<Vec<_>>>()?;
+    let (modulator, modulator_in) = blocks[0].adapt_input_port("0")?;
+    fg.connect_message(&src, "out", modulator, modulator_in)?;
+    for pair in blocks.windows(2) {
+        let (out_blk, out_name) = pair[0].adapt_output_port("0")?;
+        let (in_blk, in_name) = pair[1].adapt_input_port("0")?;
+        fg.connect_dyn(out_blk, out_name, in_blk, in_name)?;
+    }
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let snk = fg.add_block(TestMessageSink::new(messages.clone()));
+    let (deframer, deframer_out) = blocks[4].adapt_output_port("0")?;
+    fg.connect_message(deframer, deframer_out, &snk, "in_port")?;
+    Runtime::new().run(fg)?;
+
+    // Without the KISS command
+    let frame = kiss_aprs_frame()[1..].to_vec();
+    assert_eq!(vec![frame.clone(), frame], *messages.lock().unwrap());
+    Ok(())
+}
+
+#[test]
+fn fmmod_phase_increment() -> Result<()> {
+    let grc = CsdrParser::parse_command("fmmod_fc 0.5")
+        .expect("")
+        .unwrap();
+    let blk = &grc.blocks[1];
+    assert_eq!("analog_frequency_modulator_fc", blk.id);
+    assert_eq!("0.5", blk.parameters["sensitivity"]);
+
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSource::<f32>::new(vec![1.0; 100]));
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, blk)?;
+    let (but_in, in_name) = block_under_test.adapt_input_port("0")?;
+    let (but_out, out_name) = block_under_test.adapt_output_port("0")?;
+    let snk = fg.add_block(VectorSink::<Complex32>::new(100));
+    fg.connect_dyn(&src, "output", but_in, in_name)?;
+    fg.connect_dyn(but_out, out_name, &snk, "input")?;
+    Runtime::new().run(fg)?;
+    let snk = snk.get().unwrap();
+    let output = snk.items();
+    assert_eq!(100, output.len());
+    for (n, x) in output.iter().enumerate() {
+        let expected = Complex32::from_polar(1.0, 0.5 * (n + 1) as f32);
+        assert!((x - expected).norm() < 1e-3, "{n}: {x} != {expected}");
+    }
+    Ok(())
+}
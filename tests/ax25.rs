@@ -0,0 +1,134 @@
+use anyhow::Result;
+use fsdr_cli::blocks::{Ax25Format, Ax25Frame, Ax25Print};
+use fsdr_cli::csdr_cmd::CsdrParser;
+use futuresdr::blocks::MessageSource;
+use futuresdr::runtime::{Flowgraph, Pmt, Runtime};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+mod common;
+use common::TestMessageSink;
+
+/// Address field of `callsign` and `ssid`, the extension bit set on the last one
+fn address(callsign: &str, ssid: u8, h_bit: bool, last: bool) -> Vec<u8> {
+    let mut field: Vec<u8> = format!("{callsign:<6}").bytes().map(|c| c << 1).collect();
+    field.push(0x60 | (ssid << 1) | ((h_bit as u8) << 7) | last as u8);
+    field
+}
+
+fn aprs_frame() -> Vec<u8> {
+    let mut frame = address("APRS", 0, false, false);
+    frame.extend(address("F4ABC", 7, false, false));
+    frame.extend(address("WIDE1", 1, true, false));
+    frame.extend(address("WIDE2", 1, false, true));
+    frame.extend([0x03, 0xF0]);
+    frame.extend(b"!4903.50N/07201.75W-Test\r");
+    frame
+}
+
+#[test]
+fn ax25_parse() -> Result<()> {
+    let frame = Ax25Frame::parse(&aprs_frame())?;
+    assert_eq!("APRS", frame.destination.to_string());
+    assert_eq!("F4ABC", frame.source.callsign);
+    assert_eq!(7, frame.source.ssid);
+    assert_eq!(2, frame.digipeaters.len());
+    assert!(frame.digipeaters[0].repeated);
+    assert!(!frame.digipeaters[1].repeated);
+    assert_eq!(0x03, frame.control);
+    assert_eq!("UI", frame.frame_type());
+    assert_eq!(Some(0xF0), frame.pid);
+    assert_eq!(b"!4903.50N/07201.75W-Test\r".to_vec(), frame.info);
+    assert_eq!(
+        "F4ABC-7>APRS,WIDE1-1*,WIDE2-1:!4903.50N/07201.75W-Test<0x0d>",
+        frame.to_tnc2()
+    );
+
+    // From a KISS source, with its command byte
+    let mut kiss = vec![0x00];
+    kiss.extend(aprs_frame());
+    assert_eq!(frame, Ax25Frame::parse(&kiss)?);
+    Ok(())
+}
+
+#[test]
+fn ax25_parse_supervisory_frame() -> Result<()> {
+    let mut frame = address("F4ABC", 0, false, false);
+    frame.extend(address("F5XYZ", 15, false, true));
+    // RR
+    frame.push(0x41);
+    let frame = Ax25Frame::parse(&frame)?;
+    assert_eq!("S", frame.frame_type());
+    assert_eq!(None, frame.pid);
+    assert!(frame.info.is_empty());
+    assert_eq!("F5XYZ-15>F4ABC:", frame.to_tnc2());
+    Ok(())
+}
+
+#[test]
+fn ax25_parse_invalid() {
+    let frame = aprs_frame();
+    // Without the extension bit of the last address
+    let mut no_end = frame[..28].to_vec();
+    no_end[27] &= 0xFE;
+    assert!(Ax25Frame::parse(&no_end).is_err());
+    // A single address
+    let mut single = address("F4ABC", 0, false, true);
+    single.extend([0x03, 0xF0]);
+    assert!(Ax25Frame::parse(&single).is_err());
+    // Without control field
+    assert!(Ax25Frame::parse(&frame[..28]).is_err());
+    // Invalid callsign
+    let mut invalid = frame.clone();
+    invalid[0] = b'-' << 1;
+    assert!(Ax25Frame::parse(&invalid).is_err());
+}
+
+#[test]
+fn ax25_json() -> Result<()> {
+    let frame = Ax25Frame::parse(&aprs_frame())?;
+    let json: serde_json::Value = serde_json::from_str(&frame.to_json()?)?;
+    assert_eq!("F4ABC-7", json["source"]);
+    assert_eq!("APRS", json["destination"]);
+    assert_eq!(serde_json::json!(["WIDE1-1*", "WIDE2-1"]), json["path"]);
+    assert_eq!("UI", json["type"]);
+    assert_eq!(3, json["control"]);
+    assert_eq!(240, json["pid"]);
+    assert_eq!("!4903.50N/07201.75W-Test\r", json["info"]);
+    Ok(())
+}
+
+#[test]
+fn ax25_print_forwards_frames() -> Result<()> {
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(MessageSource::new(
+        Pmt::Blob(aprs_frame()),
+        Duration::from_millis(10),
+        Some(2),
+    ));
+    let print = fg.add_block(Ax25Print::new(Ax25Format::Tnc2));
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let snk = fg.add_block(TestMessageSink::new(messages.clone()));
+    fg.connect_message(&src, "out", &print, "in_port")?;
+    fg.connect_message(&print, "out", &snk, "in_port")?;
+    Runtime::new().run(fg)?;
+    assert_eq!(vec![aprs_frame(), aprs_frame()], *messages.lock().unwrap());
+    Ok(())
+}
+
+#[test]
+fn ax25_print_command() -> Result<()> {
+    let grc = CsdrParser::parse_multiple_commands("csdr load_kiss in.kiss ! ax25_print --json")
+        .expect("")
+        .unwrap();
+    let blk = grc.blocks.last().unwrap();
+    assert_eq!("ax25_print", blk.id);
+    assert_eq!("json", blk.parameters["format"]);
+
+    let grc = CsdrParser::parse_multiple_commands("csdr load_kiss in.kiss ! ax25_print")
+        .expect("")
+        .unwrap();
+    assert_eq!("tnc2", grc.blocks.last().unwrap().parameters["format"]);
+    assert_eq!(Ax25Format::Json, Ax25Format::try_from("json")?);
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anyhow::Result;
+use fsdr_cli::bench_cmd::{prepare_bench, run_bench, BenchCmd, BenchOptions};
+use fsdr_cli::cmd_grammar::{CommandsParser, Rule};
+
+#[test]
+pub fn parse_bench_options() -> Result<()> {
+    let (_options, cmd) = CommandsParser::parse_main_with_options(
+        "bench --samples 1M --seconds (2*5) --json csdr realpart_cf ! gain_ff 2",
+    )?;
+    assert_eq!(Rule::bench_cmd, cmd.as_rule());
+    let options = cmd.bench_options()?;
+    assert_eq!(
+        BenchOptions {
+            samples: Some(1_000_000),
+            seconds: Some(10.0),
+            json: true,
+        },
+        options
+    );
+    Ok(())
+}
+
+#[test]
+pub fn bench_replaces_stdin_and_stdout() -> Result<()> {
+    let (_options, cmd) = CommandsParser::parse_main_with_options("bench realpart_cf ! gain_ff 2")?;
+    let grc = prepare_bench(cmd.bench_grc()?, Some(1000))?;
+
+    let source = &grc.blocks[0];
+    assert_eq!("analog_fastnoise_source_x", source.id);
+    assert_eq!("complex", source.parameters["type"]);
+    let sink = grc.blocks.iter().find(|blk| blk.id == "blocks_null_sink");
+    assert_eq!("float", sink.expect("a null sink").parameters["type"]);
+    assert!(!grc
+        .blocks
+        .iter()
+        .any(|blk| blk.id.starts_with("blocks_file")));
+
+    let head = grc
+        .blocks
+        .iter()
+        .find(|blk| blk.id == "blocks_head")
+        .expect("a head block");
+    assert_eq!("1000", head.parameters["num_items"]);
+    assert!(grc.connections.contains(&[
+        source.name.clone(),
+        "0".into(),
+        head.name.clone(),
+        "0".into()
+    ]));
+    assert!(!grc
+        .connections
+        .iter()
+        .any(|[src, _, tgt, _]| *src == source.name && *tgt != head.name));
+    Ok(())
+}
+
+#[test]
+pub fn bench_counts_samples() -> Result<()> {
+    let (_options, cmd) =
+        CommandsParser::parse_main_with_options("bench --samples 10000 realpart_cf")?;
+    let report = run_bench(cmd.bench_grc()?, &cmd.bench_options()?)?;
+    assert_eq!(10000, report.samples);
+    assert!(report.samples_per_second > 0.0);
+    let json: serde_json::Value = serde_json::from_str(&report.to_json()?)?;
+    assert_eq!(10000, json["samples"]);
+    Ok(())
+}
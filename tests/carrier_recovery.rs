@@ -0,0 +1,162 @@
+use anyhow::Result;
+use fsdr_cli::blocks::modulators::DigitalConstellation;
+use fsdr_cli::blocks::synchronizers::CostasLoop;
+use fsdr_cli::blocks::{NoiseGenerator, NoiseType};
+use fsdr_cli::csdr_cmd::CsdrParser;
+use fsdr_cli::grc::converter::Grc2FutureSdr;
+use fsdr_cli::grc::BlockInstance;
+use futuresdr::blocks::{VectorSink, VectorSource};
+use futuresdr::num_complex::Complex32;
+use futuresdr::prelude::CpuSample;
+use futuresdr::runtime::{Flowgraph, Runtime};
+use std::f32::consts::FRAC_PI_4;
+
+const LEN: usize = 4000;
+const FREQUENCY: f32 = 0.01;
+
+fn run<T: CpuSample>(blk: &BlockInstance, input: Vec<Complex32>) -> Result<Vec<T>> {
+    let mut fg = Flowgraph::new();
+    let len = input.len();
+    let src = fg.add_block(VectorSource::<Complex32>::new(input));
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, blk)?;
+    let (but_in, in_name) = block_under_test.adapt_input_port("0")?;
+    let (but_out, out_name) = block_under_test.adapt_output_port("0")?;
+    let snk = fg.add_block(VectorSink::<T>::new(len));
+    fg.connect_dyn(&src, "output", but_in, in_name)?;
+    fg.connect_dyn(but_out, out_name, &snk, "input")?;
+    Runtime::new().run(fg)?;
+    let snk = snk.get().unwrap();
+    Ok(snk.items().to_vec())
+}
+
+/// Random symbols of the constellation, rotated by a phase and frequency offset
+fn psk(constellation: &DigitalConstellation) -> (Vec<Complex32>, Vec<Complex32>) {
+    let points = constellation.points();
+    let mut random = NoiseGenerator::new(7, NoiseType::Uniform);
+    let symbols: Vec<Complex32> = (0..LEN)
+        .map(|_| {
+            let index = (random.next_f32() + 1.0) / 2.0 * points.len() as f32;
+            points[index as usize % points.len()]
+        })
+        .collect();
+    let received = symbols
+        .iter()
+        .enumerate()
+        .map(|(n, x)| x * Complex32::from_polar(1.0, 0.7 + FREQUENCY * n as f32))
+        .collect();
+    (symbols, received)
+}
+
+/// Once locked, the output is the symbols up to a rotation by a multiple of 2π/order
+fn assert_locked(order: usize, symbols: &[Complex32], output: &[Complex32]) {
+    assert_eq!(symbols.len(), output.len());
+    let step = 8.0 * FRAC_PI_4 / order as f32;
+    let ambiguity = (output[LEN - 1] / symbols[LEN - 1]).arg();
+    let ambiguity = Complex32::from_polar(1.0, (ambiguity / step).round() * step);
+    for (x, y) in symbols.iter().zip(output.iter()).skip(LEN / 2) {
+        assert!((x * ambiguity - y).norm() < 0.1, "order {order}: {x} {y}");
+    }
+}
+
+#[test]
+pub fn costas_loops() -> Result<()> {
+    for (order, constellation) in [
+        (2, DigitalConstellation::bpsk()),
+        (4, DigitalConstellation::qpsk()),
+        (8, DigitalConstellation::psk8()),
+    ] {
+        let (symbols, received) = psk(&constellation);
+        let blk = BlockInstance::new("costas", "digital_costas_loop_cc")
+            .with("w", "2*pi/100")
+            .with("order", &order.to_string())
+            .with("use_snr", "False");
+        let output = run::<Complex32>(&blk, received)?;
+        assert_locked(order, &symbols, &output);
+    }
+    Ok(())
+}
+
+#[test]
+pub fn costas_loop_frequency() -> Result<()> {
+    let (_, received) = psk(&DigitalConstellation::bpsk());
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSource::<Complex32>::new(received));
+    let costas: CostasLoop = CostasLoop::new(2, 0.0628, 0.7071)?;
+    let costas = fg.add_block(costas);
+    let snk = fg.add_block(VectorSink::<Complex32>::new(LEN));
+    let frequency = fg.add_block(VectorSink::<f32>::new(LEN));
+    fg.connect_dyn(&src, "output", &costas, "input")?;
+    fg.connect_dyn(&costas, "output", &snk, "input")?;
+    fg.connect_dyn(&costas, "frequency", &frequency, "input")?;
+    Runtime::new().run(fg)?;
+    let frequency = frequency.get().unwrap();
+    let frequency = frequency.items();
+    assert_eq!(LEN, frequency.len());
+    assert!((frequency[LEN - 1] - FREQUENCY).abs() < 1e-3);
+
+    let blk = BlockInstance::new("costas", "digital_costas_loop_cc").with("order", "3");
+    let mut fg = Flowgraph::new();
+    assert!(Grc2FutureSdr::new().convert_block(&mut fg, &blk).is_err());
+    Ok(())
+}
+
+#[test]
+pub fn pll() -> Result<()> {
+    let tone: Vec<Complex32> = (0..LEN)
+        .map(|n| Complex32::from_polar(0.5, 1.0 + 5.0 * FREQUENCY * n as f32))
+        .collect();
+
+    let blk = BlockInstance::new("pll", "analog_pll_freqdet_cf")
+        .with("w", "2*pi/100")
+        .with("max_freq", "1")
+        .with("min_freq", "-1");
+    let frequency = run::<f32>(&blk, tone.clone())?;
+    assert_eq!(LEN, frequency.len());
+    assert!((frequency[LEN - 1] - 5.0 * FREQUENCY).abs() < 1e-4);
+
+    // The frequency is limited
+    let blk = BlockInstance::new("pll", "analog_pll_freqdet_cf")
+        .with("w", "2*pi/100")
+        .with("max_freq", "0.02")
+        .with("min_freq", "-0.02");
+    let frequency = run::<f32>(&blk, tone.clone())?;
+    assert!(frequency.iter().all(|f| f.abs() <= 0.02));
+
+    let blk = BlockInstance::new("pll", "analog_pll_carriertracking_cc")
+        .with("w", "2*pi/100")
+        .with("max_freq", "1")
+        .with("min_freq", "-1");
+    let baseband = run::<Complex32>(&blk, tone)?;
+    for x in baseband.iter().skip(LEN / 2) {
+        assert!((x - Complex32::new(0.5, 0.0)).norm() < 0.01, "{x}");
+    }
+    Ok(())
+}
+
+#[test]
+pub fn parse_carrier_recovery() {
+    let grc = CsdrParser::parse_command("bpsk_costas_loop_cc 0.01 0.5")
+        .expect("")
+        .unwrap();
+    assert_eq!("digital_costas_loop_cc", grc.blocks[1].id);
+    assert_eq!("2*pi*(0.01)", grc.blocks[1].parameters["w"]);
+    assert_eq!("2", grc.blocks[1].parameters["order"]);
+    assert_eq!("0.5", grc.blocks[1].parameters["damping"]);
+    assert_eq!("complex", grc.blocks[2].parameters["type"]);
+
+    let grc = CsdrParser::parse_command("pll_freqdet_cf (pi/100) 0.1 -0.1")
+        .expect("")
+        .unwrap();
+    assert_eq!("analog_pll_freqdet_cf", grc.blocks[1].id);
+    assert_eq!("pi/100", grc.blocks[1].parameters["w"]);
+    assert_eq!("0.1", grc.blocks[1].parameters["max_freq"]);
+    assert_eq!("-0.1", grc.blocks[1].parameters["min_freq"]);
+    assert_eq!("float", grc.blocks[2].parameters["type"]);
+
+    let grc = CsdrParser::parse_command("pll_carriertracking_cc 0.05")
+        .expect("")
+        .unwrap();
+    assert_eq!("analog_pll_carriertracking_cc", grc.blocks[1].id);
+    assert_eq!("1", grc.blocks[1].parameters["max_freq"]);
+    assert_eq!("complex", grc.blocks[2].parameters["type"]);
+}
@@ -0,0 +1,185 @@
+use anyhow::Result;
+use fsdr_cli::blocks::ChannelParameters;
+use fsdr_cli::csdr_cmd::{CsdrParser, ScriptParser};
+use fsdr_cli::grc::converter::Grc2FutureSdr;
+use fsdr_cli::grc::BlockInstance;
+use futuresdr::blocks::{VectorSink, VectorSource};
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::{Flowgraph, Runtime};
+
+fn run_block(blk: &BlockInstance, input: Vec<Complex32>) -> Result<Vec<Complex32>> {
+    let mut fg = Flowgraph::new();
+    let len = input.len();
+    let src = fg.add_block(VectorSource::<Complex32>::new(input));
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, blk)?;
+    let (but_in, in_name) = block_under_test.adapt_input_port("0")?;
+    let (but_out, out_name) = block_under_test.adapt_output_port("0")?;
+    let snk = fg.add_block(VectorSink::<Complex32>::new(len));
+    fg.connect_dyn(&src, "output", but_in, in_name)?;
+    fg.connect_dyn(but_out, out_name, &snk, "input")?;
+    Runtime::new().run(fg)?;
+    let snk = snk.get().unwrap();
+    Ok(snk.items().to_vec())
+}
+
+fn power(samples: &[Complex32]) -> f32 {
+    samples.iter().map(|v| v.norm_sqr()).sum::<f32>() / samples.len() as f32
+}
+
+#[test]
+pub fn parse_channel_model_cc() {
+    let cmds = "csdr channel_model_cc --snr 20 --freq 0.01 --epsilon 1.0001 --taps 1, 0.5 --seed 3";
+    let grc = CsdrParser::parse_multiple_commands(cmds)
+        .expect("")
+        .unwrap();
+    assert_eq!(3, grc.blocks.len());
+    let blk = &grc.blocks[1];
+    assert_eq!("channels_channel_model", blk.id);
+    assert_eq!("0.1", blk.parameters["noise_voltage"]);
+    assert_eq!("0.01", blk.parameters["freq_offset"]);
+    assert_eq!("1.0001", blk.parameters["epsilon"]);
+    assert_eq!("(1, 0.5)", blk.parameters["taps"]);
+    assert_eq!("3", blk.parameters["seed"]);
+}
+
+#[test]
+pub fn parse_channel_model_cc_with_fading() {
+    let grc = CsdrParser::parse_command("channel_model_cc --noise 0.2 --fading 0.001 3")
+        .expect("")
+        .unwrap();
+    assert_eq!(4, grc.blocks.len());
+    assert_eq!("channels_fading_model", grc.blocks[1].id);
+    assert_eq!("0.001", grc.blocks[1].parameters["fDTs"]);
+    assert_eq!("True", grc.blocks[1].parameters["LOS"]);
+    assert_eq!("3", grc.blocks[1].parameters["K"]);
+    assert_eq!("channels_channel_model", grc.blocks[2].id);
+    assert_eq!("0.2", grc.blocks[2].parameters["noise_voltage"]);
+    assert_eq!("1.0", grc.blocks[2].parameters["taps"]);
+}
+
+#[test]
+pub fn noise_and_snr_are_exclusive() {
+    assert!(CsdrParser::parse_command("channel_model_cc --noise 0.1 --snr 10").is_err());
+}
+
+#[test]
+pub fn snr_can_use_script_variables() -> Result<()> {
+    let grc = ScriptParser::parse("let snr = 20\nload_c in.c32 ! channel_model_cc --snr (snr)\n")?;
+    let blk = grc
+        .blocks
+        .iter()
+        .find(|blk| blk.id == "channels_channel_model")
+        .expect("a channel model");
+    let noise_voltage: f32 = blk.parameters["noise_voltage"].parse()?;
+    assert!((noise_voltage - 0.1).abs() < 1e-6);
+    Ok(())
+}
+
+#[test]
+pub fn invalid_epsilon_and_seed_are_errors() {
+    let blk = BlockInstance::new("channels_channel_model_0", "channels_channel_model");
+    let mut fg = Flowgraph::new();
+    let mut converter = Grc2FutureSdr::new();
+    assert!(converter
+        .convert_block(&mut fg, &blk.clone().with("epsilon", "0"))
+        .is_err());
+    assert!(converter
+        .convert_block(&mut fg, &blk.clone().with("seed", "1.5"))
+        .is_err());
+}
+
+#[test]
+pub fn noise_voltage_for_snr() {
+    assert!((ChannelParameters::noise_voltage_for_snr(0.0) - 1.0).abs() < 1e-6);
+    assert!((ChannelParameters::noise_voltage_for_snr(20.0) - 0.1).abs() < 1e-6);
+}
+
+#[test]
+pub fn identity_channel() -> Result<()> {
+    let blk = BlockInstance::new("channels_channel_model_0", "channels_channel_model");
+    let input: Vec<Complex32> = (0..100)
+        .map(|i| Complex32::new(i as f32, -(i as f32)))
+        .collect();
+    assert_eq!(input, run_block(&blk, input.clone())?);
+    Ok(())
+}
+
+#[test]
+pub fn channel_noise_power() -> Result<()> {
+    let blk = BlockInstance::new("channels_channel_model_0", "channels_channel_model")
+        .with("noise_voltage", "0.5")
+        .with("seed", "7");
+    let output = run_block(&blk, vec![Complex32::new(0.0, 0.0); 100_000])?;
+    let noise = power(&output);
+    assert!((noise - 0.25).abs() < 0.01, "noise power {noise}");
+    assert_eq!(
+        output,
+        run_block(&blk, vec![Complex32::new(0.0, 0.0); 100_000])?
+    );
+    Ok(())
+}
+
+#[test]
+pub fn channel_frequency_offset_and_taps() -> Result<()> {
+    let blk = BlockInstance::new("channels_channel_model_0", "channels_channel_model")
+        .with("freq_offset", "0.25")
+        .with("taps", "(0, 2)");
+    let output = run_block(&blk, vec![Complex32::new(1.0, 0.0); 9])?;
+    // One sample of delay, then rotating by a quarter turn per sample
+    let expected = [(0.0, 0.0), (0.0, 2.0), (-2.0, 0.0), (0.0, -2.0), (2.0, 0.0)];
+    for (i, (v, (re, im))) in output.iter().zip(expected.iter()).enumerate() {
+        assert!(
+            (v.re - re).abs() < 1e-4 && (v.im - im).abs() < 1e-4,
+            "at index {i}, expected: ({re}, {im}), got: {v}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+pub fn channel_timing_offset() -> Result<()> {
+    let blk = BlockInstance::new("channels_channel_model_0", "channels_channel_model")
+        .with("epsilon", "2.0");
+    let input: Vec<Complex32> = (0..100).map(|i| Complex32::new(i as f32, 0.0)).collect();
+    let output = run_block(&blk, input)?;
+    assert_eq!(50, output.len());
+    for (i, v) in output.iter().enumerate().skip(1) {
+        assert!(
+            (v.re - (2 * i - 1) as f32).abs() < 1e-4,
+            "at index {i}, got: {v}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+pub fn channel_tiny_epsilon_outputs_more_samples_than_a_buffer() -> Result<()> {
+    let blk = BlockInstance::new("channels_channel_model_0", "channels_channel_model")
+        .with("epsilon", "1e-5");
+    let input: Vec<Complex32> = (0..3).map(|i| Complex32::new(i as f32, 0.0)).collect();
+    let output = run_block(&blk, input)?;
+    assert!(
+        (299_990..=300_010).contains(&output.len()),
+        "{}",
+        output.len()
+    );
+    assert!(output.iter().all(|v| (0.0..=2.0).contains(&v.re)));
+    assert!(
+        (output[150_000].re - 0.5).abs() < 1e-3,
+        "{}",
+        output[150_000]
+    );
+    Ok(())
+}
+
+#[test]
+pub fn fading_keeps_average_power() -> Result<()> {
+    let blk = BlockInstance::new("channels_fading_model_0", "channels_fading_model")
+        .with("fDTs", "0.05")
+        .with("seed", "1");
+    let output = run_block(&blk, vec![Complex32::new(1.0, 0.0); 200_000])?;
+    let average = power(&output);
+    assert!((average - 1.0).abs() < 0.2, "average power {average}");
+    assert!(output.iter().any(|v| v.norm_sqr() < 0.1), "no deep fade");
+    Ok(())
+}
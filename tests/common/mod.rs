@@ -0,0 +1,39 @@
+//! Blocks shared by the integration tests
+
+use futuresdr::prelude::*;
+use futuresdr::runtime::Pmt;
+use std::sync::{Arc, Mutex};
+
+#[derive(Block)]
+#[message_inputs(in_port)]
+pub struct TestMessageSink {
+    messages: Arc<Mutex<Vec<Vec<u8>>>>,
+}
+
+impl TestMessageSink {
+    pub fn new(messages: Arc<Mutex<Vec<Vec<u8>>>>) -> Self {
+        Self { messages }
+    }
+
+    async fn in_port(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match p {
+            Pmt::Blob(bytes) => {
+                self.messages.lock().unwrap().push(bytes);
+            }
+            Pmt::Finished => {
+                io.finished = true;
+            }
+            _ => {}
+        }
+        Ok(Pmt::Null)
+    }
+}
+
+#[doc(hidden)]
+impl Kernel for TestMessageSink {}
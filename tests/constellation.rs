@@ -0,0 +1,156 @@
+use anyhow::Result;
+use fsdr_cli::blocks::modulators::{Constellation, DigitalConstellation};
+use fsdr_cli::csdr_cmd::CsdrParser;
+use fsdr_cli::grc::converter::Grc2FutureSdr;
+use fsdr_cli::grc::BlockInstance;
+use futuresdr::blocks::{VectorSink, VectorSource};
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::{Flowgraph, Runtime};
+
+fn constellations() -> Vec<DigitalConstellation> {
+    vec![
+        DigitalConstellation::bpsk(),
+        DigitalConstellation::qpsk(),
+        DigitalConstellation::psk8(),
+        DigitalConstellation::qam16(),
+    ]
+}
+
+#[test]
+pub fn constellations_have_unit_power() {
+    for constellation in constellations() {
+        let points = constellation.points();
+        assert_eq!(1 << constellation.bits_per_symbol(), points.len());
+        let power = points.iter().map(|p| p.norm_sqr()).sum::<f32>() / points.len() as f32;
+        assert!((power - 1.0).abs() < 1e-5, "{constellation:?}: {power}");
+    }
+}
+
+#[test]
+pub fn constellations_are_gray_coded() {
+    for constellation in constellations() {
+        let points = constellation.points();
+        for (symbol, point) in points.iter().enumerate() {
+            let nearest = points
+                .iter()
+                .filter(|p| *p != point)
+                .map(|p| (p - point).norm())
+                .fold(f32::INFINITY, f32::min);
+            for (other, p) in points.iter().enumerate() {
+                if other != symbol && ((p - point).norm() - nearest).abs() < 1e-4 {
+                    assert_eq!(
+                        1,
+                        (symbol ^ other).count_ones(),
+                        "{constellation:?}: {symbol} and {other}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+pub fn hard_and_soft_decisions() {
+    let offset = Complex32::new(0.05, -0.05);
+    for constellation in constellations() {
+        let bits = constellation.bits_per_symbol();
+        for (symbol, point) in constellation.points().iter().enumerate() {
+            assert_eq!(symbol, constellation.decision_maker(point + offset));
+            let mut mapped = [Complex32::default()];
+            constellation.map_to_points(symbol, &mut mapped);
+            assert_eq!(*point, mapped[0]);
+            let soft = constellation.soft_decision_maker(point + offset);
+            assert_eq!(bits, soft.len());
+            for (i, value) in soft.iter().enumerate() {
+                let bit = (symbol >> (bits - 1 - i)) & 1;
+                assert_eq!(
+                    bit == 1,
+                    *value > 0.0,
+                    "{constellation:?}: {symbol} {soft:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+pub fn constellation_names() -> Result<()> {
+    assert_eq!(
+        DigitalConstellation::qpsk(),
+        DigitalConstellation::try_from("digital.constellation_qpsk().base()")?
+    );
+    assert_eq!(
+        DigitalConstellation::psk8(),
+        DigitalConstellation::try_from("8psk")?
+    );
+    assert!(DigitalConstellation::try_from("digital.constellation_64qam().base()").is_err());
+
+    let blk = BlockInstance::new("decoder", "digital_constellation_decoder_cb")
+        .with("constellation", "[1, 1j, -1, -1j]");
+    let custom = Grc2FutureSdr::parameter_as_constellation(&blk, "constellation", "bpsk")?;
+    assert_eq!(2, custom.bits_per_symbol());
+    assert_eq!(1, custom.decision_maker(Complex32::new(0.1, 0.9)));
+    assert!(DigitalConstellation::custom(vec![Complex32::default(); 3]).is_err());
+    Ok(())
+}
+
+#[test]
+pub fn constellation_decoder() -> Result<()> {
+    let qpsk = DigitalConstellation::qpsk();
+    let symbols = vec![0, 1, 2, 3, 3, 1];
+    let input: Vec<Complex32> = symbols.iter().map(|s| qpsk.points()[*s] * 0.8).collect();
+
+    let blk = BlockInstance::new("decoder", "digital_constellation_decoder_cb")
+        .with("constellation", "digital.constellation_qpsk().base()");
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSource::<Complex32>::new(input.clone()));
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, &blk)?;
+    let (but_in, in_name) = block_under_test.adapt_input_port("0")?;
+    let (but_out, out_name) = block_under_test.adapt_output_port("0")?;
+    let snk = fg.add_block(VectorSink::<u8>::new(symbols.len()));
+    fg.connect_dyn(&src, "output", but_in, in_name)?;
+    fg.connect_dyn(but_out, out_name, &snk, "input")?;
+    Runtime::new().run(fg)?;
+    let snk = snk.get().unwrap();
+    let expected: Vec<u8> = symbols.iter().map(|s| *s as u8).collect();
+    assert_eq!(expected, snk.items().to_vec());
+
+    let blk = BlockInstance::new("soft_decoder", "digital_constellation_soft_decoder_cf")
+        .with("constellation", "qpsk");
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSource::<Complex32>::new(input));
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, &blk)?;
+    let (but_in, in_name) = block_under_test.adapt_input_port("0")?;
+    let (but_out, out_name) = block_under_test.adapt_output_port("0")?;
+    let snk = fg.add_block(VectorSink::<f32>::new(2 * symbols.len()));
+    fg.connect_dyn(&src, "output", but_in, in_name)?;
+    fg.connect_dyn(but_out, out_name, &snk, "input")?;
+    Runtime::new().run(fg)?;
+    let snk = snk.get().unwrap();
+    let bits: Vec<bool> = snk.items().iter().map(|v| *v > 0.0).collect();
+    let expected: Vec<bool> = symbols
+        .iter()
+        .flat_map(|s| [s & 2 != 0, s & 1 != 0])
+        .collect();
+    assert_eq!(expected, bits);
+    Ok(())
+}
+
+#[test]
+pub fn parse_constellation_decoder() {
+    let grc = CsdrParser::parse_multiple_commands(
+        "csdr symbol_sync_cc 4 --constellation qpsk --ted mm ! constellation_decoder_c_u8 qpsk",
+    )
+    .expect("")
+    .unwrap();
+    assert_eq!("qpsk", grc.blocks[1].parameters["constellation"]);
+    assert_eq!("digital_constellation_decoder_cb", grc.blocks[2].id);
+    assert_eq!("qpsk", grc.blocks[2].parameters["constellation"]);
+    assert_eq!("byte", grc.blocks[3].parameters["type"]);
+
+    let grc = CsdrParser::parse_command("constellation_soft_decoder_c_f 16qam")
+        .expect("")
+        .unwrap();
+    assert_eq!("digital_constellation_soft_decoder_cf", grc.blocks[1].id);
+    assert_eq!("float", grc.blocks[2].parameters["type"]);
+}
@@ -1,11 +1,15 @@
 use anyhow::Result;
 use fsdr_cli::csdr_cmd::CsdrParser;
+use fsdr_cli::grc::converter::band_pass_filter::BandPassFilterConverter;
 use fsdr_cli::grc::converter::Grc2FutureSdr;
+use fsdr_cli::grc::converter_helper::{BlockConverter, ConnectorAdapter};
+use fsdr_cli::grc::BlockInstance;
 use futuresdr::blocks::VectorSink;
 use futuresdr::blocks::VectorSource;
 use futuresdr::num_complex::Complex32;
 use futuresdr::runtime::Flowgraph;
 use futuresdr::runtime::Runtime;
+use std::f32::consts::{PI, TAU};
 
 #[test]
 pub fn parse_load_kiss() {
@@ -184,6 +188,255 @@ pub fn parse_shift_addition_cc_1256() {
     assert_eq!(2, grc.connections.len());
 }
 
+#[test]
+pub fn parse_shift_addition_cc_fifo() {
+    let cmds = "shift_addition_cc --fifo /tmp/shift_pipe";
+    let result = CsdrParser::parse_command(cmds);
+    let grc = result.expect("").unwrap();
+    assert_eq!(4, grc.blocks.len());
+    assert_eq!("blocks_freqshift_cc", grc.blocks[1].id);
+    assert_eq!("fifo_reader", grc.blocks[2].id);
+    assert_eq!("/tmp/shift_pipe", grc.blocks[2].parameters["file"]);
+    assert_eq!("complex", grc.blocks[2].parameters["type"]);
+    assert_eq!(4, grc.connections.len());
+    assert!(grc
+        .connections
+        .iter()
+        .any(|c| c[0] == grc.blocks[2].name && c[2] == grc.blocks[1].name && c[3] == "freq"));
+    // The fifo reader follows the stream of the frequency shifter to finish with it
+    assert!(grc
+        .connections
+        .iter()
+        .any(|c| c[0] == grc.blocks[1].name && c[2] == grc.blocks[2].name && c[3] == "0"));
+    // The chain goes on from the frequency shifter, not from the fifo reader
+    assert_eq!("blocks_file_sink", grc.blocks[3].id);
+    assert!(grc
+        .connections
+        .iter()
+        .any(|c| c[0] == grc.blocks[1].name && c[2] == grc.blocks[3].name));
+}
+
+#[test]
+pub fn parse_bandpass_fir_fft_cc() {
+    let cmds = "bandpass_fir_fft_cc -0.1 0.0 0.05";
+    let result = CsdrParser::parse_command(cmds);
+    let grc = result.expect("").unwrap();
+    assert_eq!(3, grc.blocks.len());
+    assert_eq!("band_pass_filter", grc.blocks[1].id);
+    assert_eq!("-0.1", grc.blocks[1].parameters["low_cutoff_freq"]);
+    assert_eq!("0.0", grc.blocks[1].parameters["high_cutoff_freq"]);
+    assert_eq!("0.05", grc.blocks[1].parameters["width"]);
+    assert_eq!("window.WIN_HAMMING", grc.blocks[1].parameters["win"]);
+}
+
+#[test]
+pub fn parse_bandpass_fir_fft_cc_fifo() {
+    let cmds = "bandpass_fir_fft_cc --fifo /tmp/bpf_pipe 0.02 BLACKMAN";
+    let result = CsdrParser::parse_command(cmds);
+    let grc = result.expect("").unwrap();
+    assert_eq!(4, grc.blocks.len());
+    assert_eq!("band_pass_filter", grc.blocks[1].id);
+    assert_eq!("0.02", grc.blocks[1].parameters["width"]);
+    assert_eq!("window.WIN_BLACKMAN", grc.blocks[1].parameters["win"]);
+    assert_eq!("fifo_reader", grc.blocks[2].id);
+    assert_eq!("/tmp/bpf_pipe", grc.blocks[2].parameters["file"]);
+    assert!(grc
+        .connections
+        .iter()
+        .any(|c| c[0] == grc.blocks[2].name && c[2] == grc.blocks[1].name && c[3] == "cutoff"));
+}
+
+/// Run a complex tone of `freq` (relative to the sample rate) through `blk`
+fn run_tone(blk: &BlockInstance, freq: f32, len: usize) -> Result<Vec<Complex32>> {
+    run_tone_through(|fg| Grc2FutureSdr::new().convert_block(fg, blk), freq, len)
+}
+
+/// Run a complex tone of `freq` (relative to the sample rate) through the block added by `convert`
+fn run_tone_through(
+    convert: impl FnOnce(&mut Flowgraph) -> Result<Box<dyn ConnectorAdapter>>,
+    freq: f32,
+    len: usize,
+) -> Result<Vec<Complex32>> {
+    let mut fg = Flowgraph::new();
+    let tone: Vec<Complex32> = (0..len)
+        .map(|n| Complex32::from_polar(1.0, TAU * freq * n as f32))
+        .collect();
+    let src = fg.add_block(VectorSource::<Complex32>::new(tone));
+    let snk = fg.add_block(VectorSink::<Complex32>::new(len));
+    let block_under_test = convert(&mut fg)?;
+    let (but_in, in_name) = block_under_test.adapt_input_port("0")?;
+    let (but_out, out_name) = block_under_test.adapt_output_port("0")?;
+    fg.connect_dyn(&src, "output", but_in, in_name)?;
+    fg.connect_dyn(but_out, out_name, &snk, "input")?;
+    Runtime::new().run(fg)?;
+    let snk = snk.get().unwrap();
+    Ok(snk.items().to_vec())
+}
+
+/// Average amplitude of a tone of `freq` after `blk`, once the filter is settled
+fn tone_amplitude(blk: &BlockInstance, freq: f32) -> Result<f32> {
+    Ok(settled_amplitude(&run_tone(blk, freq, 4096)?))
+}
+
+fn settled_amplitude(output: &[Complex32]) -> f32 {
+    let settled = &output[output.len() - 1024..];
+    settled.iter().map(|v| v.norm()).sum::<f32>() / settled.len() as f32
+}
+
+#[test]
+pub fn convert_bandpass_fir_fft_cc() -> Result<()> {
+    let grc = CsdrParser::parse_command("bandpass_fir_fft_cc 0.0 0.1 0.05")
+        .expect("")
+        .unwrap();
+    let blk = &grc.blocks[1];
+    let mut fg = Flowgraph::new();
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, blk)?;
+    assert!(block_under_test.adapt_input_port("cutoff").is_err());
+
+    let passed = tone_amplitude(blk, 0.05)?;
+    assert!((0.8..1.2).contains(&passed), "{passed}");
+    let stopped = tone_amplitude(blk, 0.3)?;
+    assert!(stopped < 0.02, "{stopped}");
+    Ok(())
+}
+
+#[test]
+pub fn convert_bandpass_fir_fft_cc_exposes_cutoff() -> Result<()> {
+    let cmds = "bandpass_fir_fft_cc --fifo /tmp/bpf_pipe 0.05";
+    let grc = CsdrParser::parse_command(cmds).expect("").unwrap();
+    // No parameter that GNU Radio does not know
+    assert!(!grc.blocks[1].parameters.contains_key("tunable"));
+    let blk = grc.blocks[1]
+        .clone()
+        .with("low_cutoff_freq", "0.0")
+        .with("high_cutoff_freq", "0.1");
+
+    let mut fg = Flowgraph::new();
+    let block_under_test = BandPassFilterConverter { tunable: true }.convert(&blk, &mut fg)?;
+    let (_, cutoff_name) = block_under_test.adapt_input_port("cutoff")?;
+    assert_eq!("cutoff", cutoff_name);
+
+    // Complex taps: only the positive frequencies of the band pass
+    let tunable_amplitude = |freq| -> Result<f32> {
+        let output = run_tone_through(
+            |fg| BandPassFilterConverter { tunable: true }.convert(&blk, fg),
+            freq,
+            4096,
+        )?;
+        Ok(settled_amplitude(&output))
+    };
+    let passed = tunable_amplitude(0.05)?;
+    assert!((0.8..1.2).contains(&passed), "{passed}");
+    let mirrored = tunable_amplitude(-0.05)?;
+    assert!(mirrored < 0.02, "{mirrored}");
+    let stopped = tunable_amplitude(0.3)?;
+    assert!(stopped < 0.02, "{stopped}");
+    Ok(())
+}
+
+#[test]
+pub fn convert_fifo_controlled_flowgraph() -> Result<()> {
+    let controls = std::env::temp_dir().join("fsdr_cli_bpf_controls");
+    std::fs::write(&controls, "0.0 0.1\n")?;
+    // The cutoff of the band pass filter is connected, so it is tunable
+    let cmds = format!("bandpass_fir_fft_cc --fifo {} 0.05", controls.display());
+    let grc = CsdrParser::parse_command(&cmds).expect("").unwrap();
+    assert!(Grc2FutureSdr::new().convert_grc(grc).is_ok());
+    std::fs::remove_file(&controls)?;
+
+    // Like csdr, a missing fifo is an error
+    let grc = CsdrParser::parse_command("shift_addition_cc --fifo /nonexistent/shift_pipe")
+        .expect("")
+        .unwrap();
+    assert!(Grc2FutureSdr::new().convert_grc(grc).is_err());
+    Ok(())
+}
+
+/// The flowgraph finishes with its input, while nobody writes into the named pipe
+#[cfg(unix)]
+#[test]
+pub fn fifo_without_writer_does_not_block() -> Result<()> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+    use std::time::{Duration, Instant};
+
+    let fifo = std::env::temp_dir().join("fsdr_cli_shift_pipe");
+    if fifo.exists() {
+        std::fs::remove_file(&fifo)?;
+    }
+    assert!(Command::new("mkfifo").arg(&fifo).status()?.success());
+    let mut child = Command::new(env!("CARGO_BIN_EXE_fsdr-cli"))
+        .args(["csdr", "shift_addition_cc", "--fifo"])
+        .arg(&fifo)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    let input = vec![0u8; 8 * 1024];
+    child.stdin.take().unwrap().write_all(&input)?;
+    let start = Instant::now();
+    while child.try_wait()?.is_none() {
+        if start.elapsed() > Duration::from_secs(10) {
+            child.kill()?;
+            std::fs::remove_file(&fifo)?;
+            panic!("shift_addition_cc --fifo did not finish with its input");
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    let output = child.wait_with_output()?;
+    std::fs::remove_file(&fifo)?;
+    assert!(output.status.success());
+    assert_eq!(input.len(), output.stdout.len());
+    Ok(())
+}
+
+/// csdr's `shift_addition_cc`: the rotation is updated with the trigonometric addition
+/// formulas, its phase being recomputed at the start of each buffer.
+fn csdr_shift_addition(input: &[Complex32], rate: f32, buffer_size: usize) -> Vec<Complex32> {
+    let rate = rate * 2.0;
+    let (sin_delta, cos_delta) = (rate * PI).sin_cos();
+    let mut starting_phase = 0.0f32;
+    let mut output = Vec::with_capacity(input.len());
+    for buffer in input.chunks(buffer_size) {
+        let (mut sin_phi, mut cos_phi) = starting_phase.sin_cos();
+        for v in buffer {
+            output.push(v * Complex32::new(cos_phi, sin_phi));
+            (cos_phi, sin_phi) = (
+                cos_phi * cos_delta - sin_phi * sin_delta,
+                sin_phi * cos_delta + cos_phi * sin_delta,
+            );
+        }
+        starting_phase += rate * PI * buffer.len() as f32;
+        while starting_phase > PI {
+            starting_phase -= 2.0 * PI;
+        }
+        while starting_phase < -PI {
+            starting_phase += 2.0 * PI;
+        }
+    }
+    output
+}
+
+#[test]
+pub fn convert_shift_addition_cc() -> Result<()> {
+    let grc = CsdrParser::parse_command("shift_addition_cc 0.1")
+        .expect("")
+        .unwrap();
+    let output = run_tone(&grc.blocks[1], 0.0, 4096)?;
+    assert_eq!(4096, output.len());
+    for (n, v) in output.iter().enumerate() {
+        let phase = (std::f64::consts::TAU * 0.1f32 as f64 * n as f64) % std::f64::consts::TAU;
+        let expected = Complex32::from_polar(1.0, phase as f32);
+        assert!((v - expected).norm() < 1e-5, "{n}: {v} != {expected}");
+    }
+    // csdr accumulates rounding errors along each buffer, so it is not bit exact
+    let ones = vec![Complex32::new(1.0, 0.0); 4096];
+    let csdr = csdr_shift_addition(&ones, 0.1, 1024);
+    for (n, (v, expected)) in output.iter().zip(csdr.iter()).enumerate() {
+        assert!((v - expected).norm() < 1e-3, "{n}: {v} != {expected}");
+    }
+    Ok(())
+}
+
 #[test]
 pub fn parse_limit_ff() {
     let cmds = "limit_ff";
@@ -958,3 +1211,122 @@ pub fn parse_tcp_kiss_client() {
     assert_eq!("\"myhost.com\"", grc.blocks[0].parameters["address"]);
     assert_eq!("8045", grc.blocks[0].parameters["port"]);
 }
+
+#[test]
+pub fn parse_ctrlport_option() -> Result<()> {
+    use fsdr_cli::cmd_grammar::{CommandsParser, Rule};
+
+    let (options, cmd) =
+        CommandsParser::parse_main_with_options("--ctrlport=0.0.0.0:1338 csdr realpart_cf")?;
+    assert_eq!(1, options.len());
+    assert_eq!(Rule::ctrlport_opt, options[0].as_rule());
+    let bind = options[0].clone().into_inner().next().map(|p| p.as_str());
+    assert_eq!(Some("0.0.0.0:1338"), bind);
+    assert_eq!(Rule::csdr_cmd, cmd.as_rule());
+
+    let (options, _cmd) = CommandsParser::parse_main_with_options("--ctrlport realpart_cf")?;
+    assert_eq!(1, options.len());
+    assert_eq!(None, options[0].clone().into_inner().next());
+    Ok(())
+}
+
+#[test]
+pub fn convert_timing_recovery_exposes_message_inputs() -> Result<()> {
+    let cmds = "timing_recovery_cc GARDNER 20 0.5 2";
+    let grc = CsdrParser::parse_command(cmds)?.expect("");
+
+    let mut fg = Flowgraph::new();
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, &grc.blocks[1])?;
+    let (_, mu) = block_under_test.adapt_input_port("mu")?;
+    assert_eq!("mu", mu);
+    let (_, max_error) = block_under_test.adapt_input_port("max_error")?;
+    assert_eq!("max_error", max_error);
+    assert!(block_under_test.adapt_input_port("unknown").is_err());
+    Ok(())
+}
+
+#[test]
+pub fn parse_through_and_pv() -> Result<()> {
+    let cmds = "csdr load_c tests/test.c32 ! through ! realpart_cf ! pv_f";
+    let grc = CsdrParser::parse_multiple_commands(cmds)?.expect("");
+    let ids: Vec<&str> = grc.blocks.iter().map(|blk| blk.id.as_str()).collect();
+    assert_eq!(
+        vec![
+            "blocks_file_source",
+            "through",
+            "blocks_complex_to_real",
+            "through",
+            "blocks_file_sink"
+        ],
+        ids
+    );
+    assert_eq!("complex", grc.blocks[1].parameters["type"]);
+    assert_eq!("float", grc.blocks[3].parameters["type"]);
+    Ok(())
+}
+
+#[test]
+pub fn file_source_with_progress_passes_data_through() -> Result<()> {
+    let dir = std::env::temp_dir();
+    let input = dir.join("fsdr_cli_progress_input.f32");
+    let output = dir.join("fsdr_cli_progress_output.f32");
+    let samples: Vec<u8> = (0..1000).flat_map(|i| (i as f32).to_le_bytes()).collect();
+    std::fs::write(&input, &samples)?;
+
+    let cmds = format!("csdr load_f {} ! pv_f", input.display());
+    let mut grc = CsdrParser::parse_multiple_commands(&cmds)?.expect("");
+    let sink = grc.blocks.last_mut().expect("a sink");
+    sink.parameters
+        .insert("file".to_string(), output.display().to_string());
+
+    let mut converter = Grc2FutureSdr::new();
+    converter.with_progress();
+    let fg = converter.convert_grc(grc)?;
+    Runtime::new().run(fg)?;
+    assert_eq!(samples, std::fs::read(&output)?);
+    Ok(())
+}
+
+#[test]
+pub fn parse_pwr_squelch() -> Result<()> {
+    let grc = CsdrParser::parse_command("pwr_squelch_ff -50 --gate")?.expect("");
+    let blk = &grc.blocks[1];
+    assert_eq!("analog_pwr_squelch_xx", blk.id);
+    assert_eq!("float", blk.parameters["type"]);
+    assert_eq!("-50", blk.parameters["threshold"]);
+    assert_eq!("True", blk.parameters["gate"]);
+
+    let grc = CsdrParser::parse_command("pwr_squelch_cc --fifo /tmp/squelch_pipe")?.expect("");
+    assert_eq!("complex", grc.blocks[1].parameters["type"]);
+    assert_eq!("False", grc.blocks[1].parameters["gate"]);
+    assert_eq!("fifo_reader", grc.blocks[2].id);
+    assert!(grc
+        .connections
+        .iter()
+        .any(|c| c[0] == grc.blocks[2].name && c[2] == grc.blocks[1].name && c[3] == "threshold"));
+    Ok(())
+}
+
+#[test]
+pub fn convert_pwr_squelch_mutes_below_threshold() -> Result<()> {
+    let grc = CsdrParser::parse_command("pwr_squelch_cc -10")?.expect("");
+    let mut fg = Flowgraph::new();
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, &grc.blocks[1])?;
+    let (_, threshold) = block_under_test.adapt_input_port("threshold")?;
+    assert_eq!("threshold", threshold);
+
+    // The average power reaches -10 dB after about 1054 samples
+    let output = run_tone(&grc.blocks[1], 0.0, 4096)?;
+    assert_eq!(4096, output.len());
+    assert!(output[..1000].iter().all(|v| v.norm() == 0.0));
+    assert!(output[1100..].iter().all(|v| (v.norm() - 1.0).abs() < 1e-6));
+
+    // Muted samples are dropped with the gate
+    let gated = grc.blocks[1].clone().with("gate", "True");
+    let output = run_tone(&gated, 0.0, 4096)?;
+    assert!((2996..3096).contains(&output.len()), "{}", output.len());
+
+    let ramp = grc.blocks[1].clone().with("ramp", "10");
+    assert!(run_tone(&ramp, 0.0, 16).is_err());
+    Ok(())
+}
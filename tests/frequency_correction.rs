@@ -0,0 +1,142 @@
+use anyhow::Result;
+use fsdr_cli::blocks::modulators::{root_raised_cosine, DigitalConstellation};
+use fsdr_cli::blocks::synchronizers::FllBandEdge;
+use fsdr_cli::blocks::{NoiseGenerator, NoiseType};
+use fsdr_cli::csdr_cmd::CsdrParser;
+use fsdr_cli::grc::converter::Grc2FutureSdr;
+use fsdr_cli::grc::BlockInstance;
+use futuresdr::blocks::{VectorSink, VectorSource};
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::{Flowgraph, Runtime};
+
+const LEN: usize = 20000;
+const SPS: usize = 4;
+
+/// Shaped QPSK of 4 samples per symbol, shifted by `frequency` radians per sample
+fn qpsk(frequency: f32) -> Vec<Complex32> {
+    let points = DigitalConstellation::qpsk().points().to_vec();
+    let mut random = NoiseGenerator::new(3, NoiseType::Uniform);
+    let mut upsampled = vec![Complex32::default(); LEN];
+    for sample in upsampled.iter_mut().step_by(SPS) {
+        *sample = points[((random.next_f32() + 1.0) * 2.0) as usize % 4];
+    }
+    let taps = root_raised_cosine(1.0, SPS as f64, 0.35, 11 * SPS);
+    (0..LEN)
+        .map(|n| {
+            let shaped: Complex32 = taps
+                .iter()
+                .enumerate()
+                .filter(|(k, _)| *k <= n)
+                .map(|(k, tap)| upsampled[n - k] * tap)
+                .sum();
+            shaped * Complex32::from_polar(1.0, frequency * n as f32)
+        })
+        .collect()
+}
+
+/// Mean instantaneous frequency, weighted by the power
+fn mean_frequency(samples: &[Complex32]) -> f32 {
+    samples
+        .windows(2)
+        .map(|w| w[1] * w[0].conj())
+        .sum::<Complex32>()
+        .arg()
+}
+
+#[test]
+pub fn fll_band_edge() -> Result<()> {
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSource::<Complex32>::new(qpsk(0.02)));
+    let fll: FllBandEdge = FllBandEdge::new(SPS as f32, 0.35, 45, 0.0628)?;
+    let fll = fg.add_block(fll);
+    let snk = fg.add_block(VectorSink::<Complex32>::new(LEN));
+    let frequency = fg.add_block(VectorSink::<f32>::new(LEN));
+    fg.connect_dyn(&src, "output", &fll, "input")?;
+    fg.connect_dyn(&fll, "output", &snk, "input")?;
+    fg.connect_dyn(&fll, "frequency", &frequency, "input")?;
+    Runtime::new().run(fg)?;
+    let frequency = frequency.get().unwrap();
+    let frequency = frequency.items();
+    assert_eq!(LEN, frequency.len());
+    let tail = &frequency[LEN / 2..];
+    let correction = tail.iter().sum::<f32>() / tail.len() as f32;
+    assert!((correction + 0.02).abs() < 2e-3, "{correction}");
+
+    let blk = BlockInstance::new("fll", "digital_fll_band_edge_cc")
+        .with("type", "cc")
+        .with("samps_per_sym", "4")
+        .with("rolloff", "0.35")
+        .with("filter_size", "45")
+        .with("w", "2*pi/100");
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSource::<Complex32>::new(qpsk(-0.03)));
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, &blk)?;
+    let (but_in, in_name) = block_under_test.adapt_input_port("0")?;
+    let (but_out, out_name) = block_under_test.adapt_output_port("0")?;
+    let snk = fg.add_block(VectorSink::<Complex32>::new(LEN));
+    fg.connect_dyn(&src, "output", but_in, in_name)?;
+    fg.connect_dyn(but_out, out_name, &snk, "input")?;
+    Runtime::new().run(fg)?;
+    let snk = snk.get().unwrap();
+    let corrected = snk.items();
+    assert_eq!(LEN, corrected.len());
+    // The symbols are not exactly balanced
+    let expected = mean_frequency(&qpsk(0.0)[LEN / 2..]);
+    assert!((mean_frequency(&corrected[LEN / 2..]) - expected).abs() < 2e-3);
+    Ok(())
+}
+
+#[test]
+pub fn afc() -> Result<()> {
+    // 2FSK of 8 samples per symbol, deviating by ±0.2 rad/sample around 0.05
+    let mut random = NoiseGenerator::new(5, NoiseType::Uniform);
+    let mut phase = 0.0f32;
+    let mut input = Vec::with_capacity(LEN);
+    for _ in 0..LEN / 16 {
+        // Balanced symbols: each pair has a symbol of each value
+        let deviation = if random.next_f32() > 0.0 { 0.2 } else { -0.2 };
+        for deviation in [deviation, -deviation] {
+            for _ in 0..8 {
+                phase += 0.05 + deviation;
+                input.push(Complex32::from_polar(1.0, phase));
+            }
+        }
+    }
+
+    let blk = BlockInstance::new("afc", "afc_cc")
+        .with("averaging", "2000")
+        .with("report", "False");
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSource::<Complex32>::new(input));
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, &blk)?;
+    let (but_in, in_name) = block_under_test.adapt_input_port("0")?;
+    let (but_out, out_name) = block_under_test.adapt_output_port("0")?;
+    let snk = fg.add_block(VectorSink::<Complex32>::new(LEN));
+    fg.connect_dyn(&src, "output", but_in, in_name)?;
+    fg.connect_dyn(but_out, out_name, &snk, "input")?;
+    Runtime::new().run(fg)?;
+    let snk = snk.get().unwrap();
+    let corrected = snk.items();
+    assert_eq!(LEN, corrected.len());
+    assert!(mean_frequency(&corrected[LEN / 2..]).abs() < 5e-3);
+    Ok(())
+}
+
+#[test]
+pub fn parse_afc() {
+    let grc = CsdrParser::parse_command("afc_cc 1000 --rate 48000")
+        .expect("")
+        .unwrap();
+    assert_eq!("afc_cc", grc.blocks[1].id);
+    assert_eq!("1000", grc.blocks[1].parameters["averaging"]);
+    assert_eq!("48000", grc.blocks[1].parameters["samp_rate"]);
+    assert_eq!("True", grc.blocks[1].parameters["report"]);
+    assert_eq!("complex", grc.blocks[2].parameters["type"]);
+
+    let grc = CsdrParser::parse_command("afc_cc --quiet")
+        .expect("")
+        .unwrap();
+    assert_eq!("4096", grc.blocks[1].parameters["averaging"]);
+    assert_eq!("False", grc.blocks[1].parameters["report"]);
+    assert!(!grc.blocks[1].parameters.contains_key("samp_rate"));
+}
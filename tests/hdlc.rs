@@ -0,0 +1,232 @@
+use anyhow::Result;
+use fsdr_cli::blocks::{crc16_x25, HdlcDeframer, HdlcFramer, KissFileSource};
+use fsdr_cli::csdr_cmd::CsdrParser;
+use fsdr_cli::grc::converter::Grc2FutureSdr;
+use fsdr_cli::grc::BlockInstance;
+use futuresdr::blocks::{VectorSink, VectorSource};
+use futuresdr::runtime::{Flowgraph, Runtime};
+use std::sync::{Arc, Mutex};
+
+mod common;
+use common::TestMessageSink;
+
+const FLAG: [u8; 8] = [0, 1, 1, 1, 1, 1, 1, 0];
+
+/// Bits of an HDLC frame, least significant first, stuffed, between flags
+fn hdlc_bits(data: &[u8]) -> Vec<u8> {
+    let mut bytes = data.to_vec();
+    bytes.extend(crc16_x25(data).to_le_bytes());
+    let mut bits = FLAG.to_vec();
+    let mut ones = 0;
+    for byte in bytes {
+        for i in 0..8 {
+            let bit = (byte >> i) & 1;
+            bits.push(bit);
+            ones = if bit == 1 { ones + 1 } else { 0 };
+            if ones == 5 {
+                bits.push(0);
+                ones = 0;
+            }
+        }
+    }
+    bits.extend(FLAG);
+    bits
+}
+
+fn deframe(block: HdlcDeframer, input: Vec<u8>) -> Result<Vec<Vec<u8>>> {
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSource::<u8>::new(input));
+    let block_under_test = fg.add_block(block);
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let snk = fg.add_block(TestMessageSink::new(messages.clone()));
+    fg.connect_dyn(&src, "output", &block_under_test, "input")?;
+    fg.connect_message(&block_under_test, "pdus", &snk, "in_port")?;
+    Runtime::new().run(fg)?;
+    let messages = messages.lock().unwrap().clone();
+    Ok(messages)
+}
+
+#[test]
+fn crc16_x25_check_value() {
+    assert_eq!(0x906E, crc16_x25(b"123456789"));
+    assert_eq!(0x0000, crc16_x25(b""));
+}
+
+#[test]
+fn hdlc_deframer() -> Result<()> {
+    let frame = vec![0x7E, 0xFF, 0x3E, 0x00, 0xF8];
+    let mut input = vec![1, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1];
+    input.extend(hdlc_bits(&frame));
+    input.extend(FLAG);
+    input.extend(hdlc_bits(b"hello, world"));
+    let received = deframe(HdlcDeframer::new(3, 500, true, false), input.clone())?;
+    assert_eq!(vec![frame.clone(), b"hello, world".to_vec()], received);
+
+    // The first frame is too short, with its FCS
+    let received = deframe(HdlcDeframer::new(8, 500, true, false), input.clone())?;
+    assert_eq!(vec![b"hello, world".to_vec()], received);
+    // The second one is too long
+    let received = deframe(HdlcDeframer::new(3, 10, true, false), input.clone())?;
+    assert_eq!(vec![frame.clone()], received);
+
+    // A bit error in the 0x00 byte of the first frame
+    let mut corrupted = input;
+    corrupted[50] ^= 1;
+    let received = deframe(HdlcDeframer::new(3, 500, true, false), corrupted.clone())?;
+    assert_eq!(vec![b"hello, world".to_vec()], received);
+    let received = deframe(HdlcDeframer::new(3, 500, false, false), corrupted)?;
+    assert_eq!(
+        vec![vec![0x7E, 0xFF, 0x3E, 0x04, 0xF8], b"hello, world".to_vec()],
+        received
+    );
+    Ok(())
+}
+
+#[test]
+fn hdlc_deframer_abort() -> Result<()> {
+    let mut input = hdlc_bits(b"hello");
+    // Seven ones within the frame
+    input.splice(20..20, [1, 1, 1, 1, 1, 1, 1]);
+    input.extend(hdlc_bits(b"world"));
+    let received = deframe(HdlcDeframer::new(3, 500, false, false), input)?;
+    assert_eq!(vec![b"world".to_vec()], received);
+    Ok(())
+}
+
+#[test]
+fn hdlc_framer() -> Result<()> {
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(KissFileSource::new("tests/test.kiss")?);
+    let framer: HdlcFramer = HdlcFramer::new(2, false);
+    let framer = fg.add_block(framer);
+    let snk = fg.add_block(VectorSink::<u8>::new(128));
+    fg.connect_message(&src, "output", &framer, "in_port")?;
+    fg.connect_dyn(&framer, "output", &snk, "input")?;
+    Runtime::new().run(fg)?;
+
+    // The frames of test.kiss are 00 AA BB and 00 CC C0 DD
+    let mut expected = FLAG.to_vec();
+    expected.extend(hdlc_bits(&[0x00, 0xAA, 0xBB]));
+    expected.extend(FLAG);
+    expected.extend(hdlc_bits(&[0x00, 0xCC, 0xC0, 0xDD]));
+    let snk = snk.get()?;
+    assert_eq!(expected, snk.items().to_vec());
+    Ok(())
+}
+
+#[test]
+fn hdlc_framer_stuffing() -> Result<()> {
+    let bits = hdlc_bits(&[0xFF]);
+    assert_eq!(
+        [0, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 0, 1, 1, 1],
+        bits[..17]
+    );
+    Ok(())
+}
+
+#[test]
+fn hdlc_nrzi_round_trip() -> Result<()> {
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(KissFileSource::new("tests/test.kiss")?);
+    let framer: HdlcFramer = HdlcFramer::new(4, true);
+    let framer = fg.add_block(framer);
+    let deframer: HdlcDeframer = HdlcDeframer::new(3, 500, true, true);
+    let deframer = fg.add_block(deframer);
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let snk = fg.add_block(TestMessageSink::new(messages.clone()));
+    fg.connect_message(&src, "output", &framer, "in_port")?;
+    fg.connect_dyn(&framer, "output", &deframer, "input")?;
+    fg.connect_message(&deframer, "pdus", &snk, "in_port")?;
+    Runtime::new().run(fg)?;
+    assert_eq!(
+        vec![vec![0x00, 0xAA, 0xBB], vec![0x00, 0xCC, 0xC0, 0xDD]],
+        *messages.lock().unwrap()
+    );
+    Ok(())
+}
+
+#[test]
+fn hdlc_commands() -> Result<()> {
+    let grc = CsdrParser::parse_command("hdlc_deframer_bp --nrzi --min 17 --max 330")
+        .expect("")
+        .unwrap();
+    let blk = &grc.blocks[1];
+    assert_eq!("digital_hdlc_deframer_bp", blk.id);
+    assert_eq!("17", blk.parameters["length_min"]);
+    assert_eq!("330", blk.parameters["length_max"]);
+    assert_eq!("True", blk.parameters["crc_check"]);
+    assert_eq!("True", blk.parameters["nrzi"]);
+
+    let mut input = hdlc_bits(b"hello");
+    input.extend(hdlc_bits(b"hello world, hello world"));
+    // NRZI encoding
+    let mut level = 0;
+    let input = input
+        .iter()
+        .map(|bit| {
+            level ^= 1 - bit;
+            level
+        })
+        .collect();
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSource::<u8>::new(input));
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, blk)?;
+    let (but_in, in_name) = block_under_test.adapt_input_port("0")?;
+    let (but_out, out_name) = block_under_test.adapt_output_port("0")?;
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let snk = fg.add_block(TestMessageSink::new(messages.clone()));
+    fg.connect_dyn(&src, "output", but_in, in_name)?;
+    fg.connect_message(but_out, out_name, &snk, "in_port")?;
+    Runtime::new().run(fg)?;
+    assert_eq!(
+        vec![b"hello world, hello world".to_vec()],
+        *messages.lock().unwrap()
+    );
+
+    let grc = CsdrParser::parse_command("hdlc_deframer_bp --no_crc")
+        .expect("")
+        .unwrap();
+    assert_eq!("32", grc.blocks[1].parameters["length_min"]);
+    assert_eq!("False", grc.blocks[1].parameters["crc_check"]);
+    assert_eq!("False", grc.blocks[1].parameters["nrzi"]);
+
+    let grc = CsdrParser::parse_multiple_commands(
+        "csdr load_kiss in.kiss ! hdlc_framer_pb --flags 8 --nrzi",
+    )
+    .expect("")
+    .unwrap();
+    let blk = grc
+        .blocks
+        .iter()
+        .find(|blk| blk.id == "digital_hdlc_framer_pb")
+        .unwrap();
+    assert_eq!("8", blk.parameters["flags"]);
+    assert_eq!("True", blk.parameters["nrzi"]);
+    Ok(())
+}
+
+#[test]
+fn satellites_hdlc_deframer_check_fcs() -> Result<()> {
+    // 'h' becomes 'i', the FCS is wrong
+    let mut input = hdlc_bits(b"hello world, hello world");
+    input[FLAG.len()] ^= 1;
+    for (check_fcs, expected) in [
+        ("True", vec![]),
+        ("False", vec![b"iello world, hello world".to_vec()]),
+    ] {
+        let blk =
+            BlockInstance::new("deframer", "satellites_hdlc_deframer").with("check_fcs", check_fcs);
+        let mut fg = Flowgraph::new();
+        let src = fg.add_block(VectorSource::<u8>::new(input.clone()));
+        let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, &blk)?;
+        let (but_in, in_name) = block_under_test.adapt_input_port("0")?;
+        let (but_out, out_name) = block_under_test.adapt_output_port("0")?;
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        let snk = fg.add_block(TestMessageSink::new(messages.clone()));
+        fg.connect_dyn(&src, "output", but_in, in_name)?;
+        fg.connect_message(but_out, out_name, &snk, "in_port")?;
+        Runtime::new().run(fg)?;
+        assert_eq!(expected, *messages.lock().unwrap(), "check_fcs {check_fcs}");
+    }
+    Ok(())
+}
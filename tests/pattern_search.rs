@@ -1,5 +1,9 @@
 use anyhow::Result;
+use fsdr_cli::blocks::correlate_access_code::{ACCESS_CODE_FLAG, INVERTED_ACCESS_CODE_FLAG};
 use fsdr_cli::blocks::pattern_search::PatternSearch;
+use fsdr_cli::blocks::{AccessCode, CorrelateAccessCode, CorrelationOutput, Correlator};
+use fsdr_cli::csdr_cmd::CsdrParser;
+use fsdr_cli::grc::converter::Grc2FutureSdr;
 use futuresdr::blocks::VectorSink;
 use futuresdr::blocks::VectorSource;
 use futuresdr::prelude::connect;
@@ -76,3 +80,141 @@ fn pattern_search_three_bytes_found() -> Result<()> {
 
     Ok(())
 }
+
+fn correlate(correlator: Correlator, mode: CorrelationOutput, input: Vec<u8>) -> Result<Vec<u8>> {
+    let mut fg = Flowgraph::new();
+    let block_under_test: CorrelateAccessCode = CorrelateAccessCode::new(correlator, mode);
+    let src = VectorSource::<u8>::new(input);
+    let vect_sink = VectorSink::<u8>::new(64);
+
+    connect!(fg,
+        src > block_under_test > vect_sink;
+    );
+    Runtime::new().run(fg)?;
+
+    let snk = vect_sink.get()?;
+    Ok(snk.items().to_vec())
+}
+
+fn bits(value: u64, len: usize) -> Vec<u8> {
+    (0..len).rev().map(|i| ((value >> i) & 1) as u8).collect()
+}
+
+#[test]
+fn access_codes() -> Result<()> {
+    assert_eq!(
+        AccessCode::from_bits(&bits(0x1ACF, 16))?,
+        AccessCode::try_from("0x1ACF")?
+    );
+    assert_eq!(4, AccessCode::try_from("0110")?.len());
+    assert_eq!(64, AccessCode::try_from("0x0123456789ABCDEF")?.len());
+    assert!(AccessCode::try_from("0x1G").is_err());
+    assert!(AccessCode::try_from("0120").is_err());
+    assert!(AccessCode::from_bits(&[0; 65]).is_err());
+    Ok(())
+}
+
+#[test]
+fn correlate_access_code_with_bit_errors() -> Result<()> {
+    let access_code = AccessCode::try_from("0x1ACFFC1D")?;
+    // Two bit errors in the access code, then a frame of 8 bits
+    let mut input = bits(0b1011, 4);
+    input.extend(bits(0x1ACFFC1D ^ 0x00100100, 32));
+    input.extend(bits(0xA5, 8));
+    input.extend(bits(0x3C, 8));
+
+    let output = correlate(
+        Correlator::new(access_code, 2, false),
+        CorrelationOutput::Frame(8),
+        input.clone(),
+    )?;
+    assert_eq!(bits(0xA5, 8), output);
+
+    let output = correlate(
+        Correlator::new(access_code, 1, false),
+        CorrelationOutput::Frame(8),
+        input,
+    )?;
+    assert!(output.is_empty());
+    Ok(())
+}
+
+#[test]
+fn correlate_inverted_access_code() -> Result<()> {
+    let access_code = AccessCode::try_from("0x1ACFFC1D")?;
+    let mut input = bits(!0x1ACFFC1Du64 ^ 0x80, 32);
+    input.extend(bits(!0xA5u64, 8));
+
+    let output = correlate(
+        Correlator::new(access_code, 1, true),
+        CorrelationOutput::Frame(8),
+        input.clone(),
+    )?;
+    assert_eq!(bits(0xA5, 8), output);
+
+    let output = correlate(
+        Correlator::new(access_code, 1, false),
+        CorrelationOutput::Frame(8),
+        input,
+    )?;
+    assert!(output.is_empty());
+    Ok(())
+}
+
+#[test]
+fn correlate_access_code_flags() -> Result<()> {
+    let access_code = AccessCode::try_from("0110")?;
+    let input = vec![1, 0, 1, 1, 0, 1, 1, 0, 0, 1, 1, 0, 1];
+    let output = correlate(
+        Correlator::new(access_code, 0, true),
+        CorrelationOutput::Flag,
+        input.clone(),
+    )?;
+    assert_eq!(input.len(), output.len());
+    let data: Vec<u8> = output.iter().map(|b| b & 1).collect();
+    assert_eq!(input, data);
+    let flags: Vec<u8> = output.iter().map(|b| b & !1).collect();
+    let (found, inverted) = (
+        ACCESS_CODE_FLAG,
+        ACCESS_CODE_FLAG | INVERTED_ACCESS_CODE_FLAG,
+    );
+    assert_eq!(
+        vec![0, 0, 0, 0, 0, found, 0, 0, found, 0, inverted, 0, found],
+        flags
+    );
+    Ok(())
+}
+
+#[test]
+fn pattern_search_with_threshold() -> Result<()> {
+    let grc = CsdrParser::parse_command("pattern_search_u8_u8 8 1 0 1 1 0 1 --threshold 1")
+        .expect("")
+        .unwrap();
+    let blk = &grc.blocks[1];
+    assert_eq!("pattern_search", blk.id);
+    assert_eq!("1,0,1,1,0,1", blk.parameters["pattern_values"]);
+    assert_eq!("1", blk.parameters["threshold"]);
+    assert!(!blk.parameters.contains_key("invert"));
+
+    let mut input = bits(0b101100, 6);
+    input.extend(bits(0xF0, 8));
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSource::<u8>::new(input));
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, blk)?;
+    let (but_in, in_name) = block_under_test.adapt_input_port("0")?;
+    let (but_out, out_name) = block_under_test.adapt_output_port("0")?;
+    let snk = fg.add_block(VectorSink::<u8>::new(8));
+    fg.connect_dyn(&src, "output", but_in, in_name)?;
+    fg.connect_dyn(but_out, out_name, &snk, "input")?;
+    Runtime::new().run(fg)?;
+    let snk = snk.get().unwrap();
+    assert_eq!(bits(0xF0, 8), snk.items().to_vec());
+
+    let grc = CsdrParser::parse_command("pattern_search_u8_u8 8 1 0 1 --invert")
+        .expect("")
+        .unwrap();
+    assert_eq!("1,0,1", grc.blocks[1].parameters["pattern_values"]);
+    assert_eq!("0", grc.blocks[1].parameters["threshold"]);
+    assert_eq!("True", grc.blocks[1].parameters["invert"]);
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use anyhow::Result;
+use fsdr_cli::blocks::modulators::{
+    root_raised_cosine, Constellation, ConstellationModulator, DigitalConstellation,
+};
+use fsdr_cli::csdr_cmd::CsdrParser;
+use fsdr_cli::grc::converter::Grc2FutureSdr;
+use fsdr_cli::grc::BlockInstance;
+use futuresdr::blocks::{VectorSink, VectorSource};
+use futuresdr::num_complex::Complex32;
+use futuresdr::prelude::{DefaultCpuReader, DefaultCpuWriter};
+use futuresdr::runtime::{Flowgraph, Runtime};
+
+const SPS: usize = 4;
+
+fn modulate(blk: &BlockInstance, input: Vec<u8>) -> Result<Vec<Complex32>> {
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSource::<u8>::new(input));
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, blk)?;
+    let (but_in, in_name) = block_under_test.adapt_input_port("0")?;
+    let (but_out, out_name) = block_under_test.adapt_output_port("0")?;
+    let snk = fg.add_block(VectorSink::<Complex32>::new(1024));
+    fg.connect_dyn(&src, "output", but_in, in_name)?;
+    fg.connect_dyn(but_out, out_name, &snk, "input")?;
+    Runtime::new().run(fg)?;
+    let snk = snk.get().unwrap();
+    Ok(snk.items().to_vec())
+}
+
+/// Matched filter the samples and decide on the symbol at each symbol instant
+fn demodulate(
+    constellation: &DigitalConstellation,
+    samples: &[Complex32],
+    symbols: usize,
+) -> Vec<usize> {
+    let taps = root_raised_cosine(SPS as f64, SPS as f64, 0.35, 11 * SPS);
+    let peak: f32 = taps.iter().map(|tap| tap * tap).sum();
+    (0..symbols)
+        .map(|n| {
+            let instant = n * SPS + taps.len() - 1;
+            let y: Complex32 = taps
+                .iter()
+                .enumerate()
+                .filter_map(|(k, tap)| samples.get(instant - k).map(|x| x * tap))
+                .sum();
+            constellation.decision_maker(y / peak)
+        })
+        .collect()
+}
+
+/// Symbols of the bytes, most significant bits first
+fn symbols(bytes: &[u8], bits_per_symbol: usize) -> Vec<usize> {
+    let bits: Vec<usize> = bytes
+        .iter()
+        .flat_map(|b| (0..8).rev().map(move |i| ((b >> i) & 1) as usize))
+        .collect();
+    bits.chunks_exact(bits_per_symbol)
+        .map(|chunk| chunk.iter().fold(0, |symbol, bit| (symbol << 1) | bit))
+        .collect()
+}
+
+#[test]
+pub fn root_raised_cosine_taps() {
+    let taps = root_raised_cosine(2.0, 4.0, 0.35, 44);
+    assert_eq!(45, taps.len());
+    assert!((taps.iter().sum::<f32>() - 2.0).abs() < 1e-5);
+    for (a, b) in taps.iter().zip(taps.iter().rev()) {
+        assert!((a - b).abs() < 1e-6);
+    }
+    let center = taps[22];
+    assert!(taps.iter().all(|tap| *tap <= center));
+}
+
+#[test]
+pub fn modulate_constellations() -> Result<()> {
+    let bytes = vec![0x1b, 0xe4, 0x5a, 0xc3, 0x0f, 0x96];
+    for name in ["bpsk", "qpsk", "8psk", "16qam"] {
+        let constellation = DigitalConstellation::try_from(name)?;
+        let bits_per_symbol = constellation.bits_per_symbol();
+        let blk = BlockInstance::new("modulator", "digital_constellation_modulator")
+            .with("constellation", name)
+            .with("differential", "False")
+            .with("samples_per_symbol", "4")
+            .with("excess_bw", "0.35");
+        let samples = modulate(&blk, bytes.clone())?;
+        let expected = symbols(&bytes, bits_per_symbol);
+        // The 11 symbols of the filter span are flushed at the end
+        assert_eq!((expected.len() + 11) * SPS, samples.len(), "{name}");
+        assert_eq!(
+            expected,
+            demodulate(&constellation, &samples, expected.len()),
+            "{name}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+pub fn differential_bpsk() -> Result<()> {
+    let bpsk = DigitalConstellation::bpsk();
+    let blk = BlockInstance::new("modulator", "digital_constellation_modulator")
+        .with("constellation", "digital.constellation_bpsk().base()")
+        .with("differential", "True")
+        .with("samples_per_symbol", "4");
+    let samples = modulate(&blk, vec![0b1011_0000])?;
+    // The phase flips on each 1, starting from the point of 0
+    assert_eq!(vec![1, 1, 0, 1, 1, 1, 1, 1], demodulate(&bpsk, &samples, 8));
+    Ok(())
+}
+
+#[test]
+pub fn invalid_modulators() {
+    assert!(
+        ConstellationModulator::<DefaultCpuReader<u8>, DefaultCpuWriter<Complex32>>::new(
+            DigitalConstellation::qam16(),
+            true,
+            4,
+            0.35
+        )
+        .is_err()
+    );
+    let blk = BlockInstance::new("modulator", "digital_constellation_modulator")
+        .with("constellation", "qpsk")
+        .with("samples_per_symbol", "1");
+    let mut fg = Flowgraph::new();
+    assert!(Grc2FutureSdr::new().convert_block(&mut fg, &blk).is_err());
+}
+
+#[test]
+pub fn parse_psk_modulator() {
+    let grc = CsdrParser::parse_command("psk_modulator_u8_c qpsk 8 0.5 --differential")
+        .expect("")
+        .unwrap();
+    assert_eq!("digital_constellation_modulator", grc.blocks[1].id);
+    assert_eq!("qpsk", grc.blocks[1].parameters["constellation"]);
+    assert_eq!("8", grc.blocks[1].parameters["samples_per_symbol"]);
+    assert_eq!("0.5", grc.blocks[1].parameters["excess_bw"]);
+    assert_eq!("True", grc.blocks[1].parameters["differential"]);
+    assert_eq!("complex", grc.blocks[2].parameters["type"]);
+
+    let grc = CsdrParser::parse_command("psk_modulator_u8_c bpsk 4")
+        .expect("")
+        .unwrap();
+    assert_eq!("0.35", grc.blocks[1].parameters["excess_bw"]);
+    assert_eq!("False", grc.blocks[1].parameters["differential"]);
+}
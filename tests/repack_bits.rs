@@ -0,0 +1,158 @@
+use anyhow::Result;
+use fsdr_cli::blocks::Endianness;
+use fsdr_cli::csdr_cmd::CsdrParser;
+use fsdr_cli::grc::converter::Grc2FutureSdr;
+use fsdr_cli::grc::BlockInstance;
+use futuresdr::blocks::{VectorSink, VectorSource};
+use futuresdr::runtime::{Flowgraph, Runtime};
+
+fn run(blk: &BlockInstance, input: Vec<u8>) -> Result<Vec<u8>> {
+    let mut fg = Flowgraph::new();
+    let len = input.len();
+    let src = fg.add_block(VectorSource::<u8>::new(input));
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, blk)?;
+    let (but_in, in_name) = block_under_test.adapt_input_port("0")?;
+    let (but_out, out_name) = block_under_test.adapt_output_port("0")?;
+    let snk = fg.add_block(VectorSink::<u8>::new(len));
+    fg.connect_dyn(&src, "output", but_in, in_name)?;
+    fg.connect_dyn(but_out, out_name, &snk, "input")?;
+    Runtime::new().run(fg)?;
+    let snk = snk.get().unwrap();
+    Ok(snk.items().to_vec())
+}
+
+fn command(cmd: &str) -> BlockInstance {
+    let grc = CsdrParser::parse_command(cmd).expect("").unwrap();
+    grc.blocks[1].clone()
+}
+
+#[test]
+fn unpack_and_pack_k_bits() -> Result<()> {
+    let unpack = command("unpack_k_bits_bb 8");
+    assert_eq!("blocks_unpack_k_bits_bb", unpack.id);
+    assert_eq!("8", unpack.parameters["k"]);
+    let bits = run(&unpack, vec![0xA5, 0x0F])?;
+    assert_eq!(vec![1, 0, 1, 0, 0, 1, 0, 1, 0, 0, 0, 0, 1, 1, 1, 1], bits);
+    assert_eq!(vec![0xA5, 0x0F], run(&command("pack_k_bits_bb 8"), bits)?);
+
+    // Any k, most significant bit first
+    let bits = run(&command("unpack_k_bits_bb 3"), vec![5, 2])?;
+    assert_eq!(vec![1, 0, 1, 0, 1, 0], bits);
+    let pack = command("pack_k_bits_bb 3");
+    assert_eq!("blocks_pack_k_bits_bb", pack.id);
+    assert_eq!(vec![5, 2], run(&pack, bits)?);
+    Ok(())
+}
+
+#[test]
+fn pack_bits_csdr() -> Result<()> {
+    let bits = vec![1, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 1, 0];
+    let pack = command("pack_bits_8to1_u8_u8");
+    assert_eq!("blocks_pack_k_bits_bb", pack.id);
+    assert_eq!(vec![0x81, 0x02], run(&pack, bits)?);
+
+    // Least significant bit first
+    let unpack = command("pack_bits_1to8_u8_u8");
+    assert_eq!("blocks_packed_to_unpacked_xx", unpack.id);
+    assert_eq!(
+        vec![1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+        run(&unpack, vec![0x01, 0x80])?
+    );
+    Ok(())
+}
+
+#[test]
+fn packed_to_unpacked_endianness() -> Result<()> {
+    let packed = vec![0xB3, 0xC7, 0x5E];
+    let msb = command("packed_to_unpacked_bb 3");
+    assert_eq!("blocks_packed_to_unpacked_xx", msb.id);
+    assert_eq!("3", msb.parameters["bits_per_chunk"]);
+    assert_eq!("gr.GR_MSB_FIRST", msb.parameters["endianness"]);
+    let chunks = run(&msb, packed.clone())?;
+    assert_eq!(vec![5, 4, 7, 4, 3, 5, 3, 6], chunks);
+    assert_eq!(packed, run(&command("unpacked_to_packed_bb 3"), chunks)?);
+
+    let lsb = command("packed_to_unpacked_bb 3 --lsb");
+    assert_eq!("gr.GR_LSB_FIRST", lsb.parameters["endianness"]);
+    let chunks = run(&lsb, packed.clone())?;
+    assert_eq!(vec![3, 6, 6, 3, 4, 5, 7, 2], chunks);
+    let unpacked_to_packed = command("unpacked_to_packed_bb 3 --lsb");
+    assert_eq!("blocks_unpacked_to_packed_xx", unpacked_to_packed.id);
+    assert_eq!(packed, run(&unpacked_to_packed, chunks)?);
+
+    // Trailing bits not filling a byte are dropped
+    assert_eq!(
+        vec![0xB3],
+        run(&command("unpacked_to_packed_bb 3"), vec![5, 4, 7])?
+    );
+    assert_eq!(
+        Endianness::LsbFirst,
+        Endianness::try_from("gr.GR_LSB_FIRST")?
+    );
+    Ok(())
+}
+
+#[test]
+fn repack_bits_grc() -> Result<()> {
+    let blk = BlockInstance::new("blocks_repack_bits_bb", "repack")
+        .with("k", "2")
+        .with("l", "4")
+        .with("endianness", "gr.GR_LSB_FIRST");
+    // Bits 0 1, 1 1, 1 0 and 0 0 in order, least significant first
+    assert_eq!(
+        vec![0b1110, 0b0001],
+        run(&blk, vec![0b10, 0b11, 0b01, 0b00])?
+    );
+
+    let invalid = BlockInstance::new("blocks_packed_to_unpacked_xx", "unpack")
+        .with("type", "int")
+        .with("bits_per_chunk", "1");
+    assert!(run(&invalid, vec![0]).is_err());
+    let invalid = BlockInstance::new("blocks_unpack_k_bits_bb", "unpack").with("k", "9");
+    assert!(run(&invalid, vec![0]).is_err());
+    Ok(())
+}
+
+#[test]
+fn differential_coding() -> Result<()> {
+    let encoder = command("diff_encoder_bb 4");
+    assert_eq!("digital_diff_encoder_bb", encoder.id);
+    assert_eq!("4", encoder.parameters["modulus"]);
+    assert_eq!("digital.DIFF_DIFFERENTIAL", encoder.parameters["coding"]);
+    let encoded = run(&encoder, vec![1, 2, 3, 0, 1])?;
+    assert_eq!(vec![1, 3, 2, 2, 3], encoded);
+    let decoder = command("diff_decoder_bb 4");
+    assert_eq!("digital_diff_decoder_bb", decoder.id);
+    assert_eq!(vec![1, 2, 3, 0, 1], run(&decoder, encoded)?);
+
+    // The modulus is 2 by default
+    assert_eq!(
+        vec![1, 1, 0, 1],
+        run(&command("diff_encoder_bb"), vec![1, 0, 1, 1])?
+    );
+
+    // NRZI: a 0 is a transition
+    let encoder = command("diff_encoder_bb --nrzi");
+    assert_eq!("2", encoder.parameters["modulus"]);
+    assert_eq!("digital.DIFF_NRZI", encoder.parameters["coding"]);
+    let bits = vec![0, 0, 1, 0, 1, 1];
+    let encoded = run(&encoder, bits.clone())?;
+    assert_eq!(vec![1, 0, 0, 1, 1, 1], encoded);
+    assert_eq!(bits, run(&command("diff_decoder_bb --nrzi"), encoded)?);
+
+    assert!(run(&command("diff_encoder_bb 4 --nrzi"), vec![0]).is_err());
+    Ok(())
+}
+
+#[test]
+fn differential_coding_csdr() -> Result<()> {
+    let encoder = command("differential_encoder_u8_u8");
+    assert_eq!("digital_diff_encoder_bb", encoder.id);
+    assert_eq!("digital.DIFF_NRZI", encoder.parameters["coding"]);
+    let decoder = command("differential_decoder_u8_u8");
+    assert_eq!("digital_diff_decoder_bb", decoder.id);
+    assert_eq!("digital.DIFF_NRZI", decoder.parameters["coding"]);
+    let bits = vec![1, 0, 1, 1, 0, 0, 0, 1];
+    assert_eq!(bits, run(&decoder, run(&encoder, bits.clone())?)?);
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anyhow::Result;
+use fsdr_cli::repl::Repl;
+
+#[test]
+pub fn repl_appends_lines_and_undoes_them() -> Result<()> {
+    let mut repl = Repl::new();
+    repl.execute("load_c tests/test.c32")?;
+    repl.execute("let rate = 48k")?;
+    repl.execute("fmdemod_quadri_cf ! gain_ff 0.5")?;
+    repl.execute("= rate / 2")?;
+    let grc = repl.current_grc()?;
+    let ids: Vec<&str> = grc.blocks.iter().map(|blk| blk.id.as_str()).collect();
+    assert_eq!(
+        vec![
+            "blocks_file_source",
+            "variable",
+            "analog_quadrature_demod_cf",
+            "blocks_multiply_const_vxx",
+            "blocks_file_sink",
+        ],
+        ids
+    );
+
+    repl.execute("undo")?;
+    let grc = repl.current_grc()?;
+    assert_eq!(3, grc.blocks.len());
+    assert!(repl.describe()?.contains("rate (variable) value=48k"));
+
+    repl.execute("undo")?;
+    repl.execute("undo")?;
+    assert!(repl.execute("undo").is_err());
+    Ok(())
+}
+
+#[test]
+pub fn repl_keeps_pipeline_on_error() -> Result<()> {
+    let mut repl = Repl::new();
+    repl.execute("load_f tests/test.f32")?;
+    assert!(repl.execute("unknown_cmd 12").is_err());
+    assert!(repl.execute("stop").is_err());
+    assert_eq!(2, repl.current_grc()?.blocks.len());
+    Ok(())
+}
+
+#[test]
+pub fn repl_completes_commands_and_variables() -> Result<()> {
+    let mut repl = Repl::new();
+    repl.execute("let audio_rate = 48k")?;
+    let words = repl.words();
+    assert!(words.contains("fmdemod_quadri_cf"));
+    assert!(words.contains("csdr"));
+    assert!(words.contains("undo"));
+    assert!(words.contains("audio_rate"));
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use anyhow::Result;
+use fsdr_cli::blocks::{crc16_x25, Lfsr};
+use fsdr_cli::csdr_cmd::CsdrParser;
+use fsdr_cli::grc::converter::Grc2FutureSdr;
+use fsdr_cli::grc::BlockInstance;
+use futuresdr::blocks::{VectorSink, VectorSource};
+use futuresdr::prelude::CpuSample;
+use futuresdr::runtime::{Flowgraph, Runtime};
+use std::sync::{Arc, Mutex};
+
+mod common;
+use common::TestMessageSink;
+
+fn run<T: CpuSample>(blk: &BlockInstance, input: Vec<T>) -> Result<Vec<T>> {
+    let mut fg = Flowgraph::new();
+    let len = input.len();
+    let src = fg.add_block(VectorSource::<T>::new(input));
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, blk)?;
+    let (but_in, in_name) = block_under_test.adapt_input_port("0")?;
+    let (but_out, out_name) = block_under_test.adapt_output_port("0")?;
+    let snk = fg.add_block(VectorSink::<T>::new(len));
+    fg.connect_dyn(&src, "output", but_in, in_name)?;
+    fg.connect_dyn(but_out, out_name, &snk, "input")?;
+    Runtime::new().run(fg)?;
+    let snk = snk.get().unwrap();
+    Ok(snk.items().to_vec())
+}
+
+fn command(cmd: &str) -> BlockInstance {
+    let grc = CsdrParser::parse_command(cmd).expect("").unwrap();
+    grc.blocks[1].clone()
+}
+
+/// Pseudo-random bits
+fn bits(len: usize) -> Vec<u8> {
+    let mut lfsr = Lfsr::new(0x3, 0x5A5A, 15).unwrap();
+    (0..len).map(|_| lfsr.next_bit()).collect()
+}
+
+/// The first bits of the CCSDS pseudo-randomizer sequence
+const CCSDS: u32 = 0xFF48_0EC0;
+
+#[test]
+fn lfsr_ccsds_sequence() -> Result<()> {
+    let (mask, seed, len) = Lfsr::CCSDS;
+    let mut lfsr = Lfsr::new(mask, seed, len)?;
+    let sequence: Vec<u8> = (0..32).map(|_| lfsr.next_bit()).collect();
+    let expected: Vec<u8> = (0..32).rev().map(|i| ((CCSDS >> i) & 1) as u8).collect();
+    assert_eq!(expected, sequence);
+    lfsr.reset();
+    assert_eq!(1, lfsr.next_bit());
+    assert!(Lfsr::new(0x1, 0x1, 64).is_err());
+    Ok(())
+}
+
+#[test]
+fn g3ruh_scrambler_round_trip() -> Result<()> {
+    let scrambler = command("scrambler_bb --g3ruh");
+    assert_eq!("digital_scrambler_bb", scrambler.id);
+    assert_eq!("0x21", scrambler.parameters["mask"]);
+    assert_eq!("0x0", scrambler.parameters["seed"]);
+    assert_eq!("16", scrambler.parameters["len"]);
+    let descrambler = command("descrambler_bb 0x21 0 16");
+    assert_eq!("digital_descrambler_bb", descrambler.id);
+    assert_eq!("0x21", descrambler.parameters["mask"]);
+    assert_eq!("0", descrambler.parameters["seed"]);
+
+    let input = bits(1000);
+    let scrambled = run(&scrambler, input.clone())?;
+    assert_ne!(input, scrambled);
+    let output = run(&descrambler, scrambled)?;
+    // Delayed by the length of the register, plus one, as in GNU Radio
+    assert_eq!(input[..1000 - 17], output[17..]);
+    Ok(())
+}
+
+#[test]
+fn descrambler_self_synchronizes() -> Result<()> {
+    let input = bits(500);
+    let (mask, _, len) = Lfsr::G3RUH;
+    let mut scrambler = Lfsr::new(mask, 0x1234, len)?;
+    let scrambled: Vec<u8> = input
+        .iter()
+        .map(|bit| scrambler.next_bit_scramble(*bit))
+        .collect();
+    // The seed of the descrambler does not matter after 17 bits
+    let output = run(&command("descrambler_bb --g3ruh"), scrambled)?;
+    assert_eq!(input[17..500 - 17], output[34..]);
+    Ok(())
+}
+
+#[test]
+fn additive_scrambler() -> Result<()> {
+    let blk = command("additive_scrambler_bb --ccsds");
+    assert_eq!("digital_additive_scrambler_bb", blk.id);
+    assert_eq!("0xA9", blk.parameters["mask"]);
+    assert_eq!("0xFF", blk.parameters["seed"]);
+    assert_eq!("7", blk.parameters["len"]);
+    assert_eq!("0", blk.parameters["count"]);
+    assert_eq!("1", blk.parameters["bits_per_byte"]);
+    let output = run(&blk, vec![0u8; 32])?;
+    let expected: Vec<u8> = (0..32).rev().map(|i| ((CCSDS >> i) & 1) as u8).collect();
+    assert_eq!(expected, output);
+
+    // Additive scrambling is its own inverse
+    let blk = command("additive_scrambler_bb 0xA9 0xFF 7 --count 100");
+    let input = bits(1000);
+    let scrambled = run(&blk, input.clone())?;
+    assert_ne!(input, scrambled);
+    assert_eq!(input, run(&blk, scrambled)?);
+
+    // Least significant bit first, reset every 2 bytes
+    let blk = command("additive_scrambler_bb --ccsds --bits_per_byte 8 --count 2");
+    assert_eq!("8", blk.parameters["bits_per_byte"]);
+    assert_eq!(vec![0xFF, 0x12, 0xFF, 0x12], run(&blk, vec![0u8; 4])?);
+    Ok(())
+}
+
+/// Bits of an AX.25 frame between flags, stuffed and NRZI encoded
+fn nrzi_hdlc_bits(data: &[u8]) -> Vec<u8> {
+    let flag = [0, 1, 1, 1, 1, 1, 1, 0];
+    let mut bytes = data.to_vec();
+    bytes.extend(crc16_x25(data).to_le_bytes());
+    let mut bits: Vec<u8> = flag.repeat(16);
+    let mut ones = 0;
+    for byte in bytes {
+        for i in 0..8 {
+            let bit = (byte >> i) & 1;
+            bits.push(bit);
+            ones = if bit == 1 { ones + 1 } else { 0 };
+            if ones == 5 {
+                bits.push(0);
+                ones = 0;
+            }
+        }
+    }
+    // The descrambler delays the bits by 17
+    bits.extend(flag.repeat(4));
+    let mut level = 0;
+    bits.iter()
+        .map(|bit| {
+            level ^= 1 - bit;
+            level
+        })
+        .collect()
+}
+
+#[test]
+fn ax25_deframer_g3ruh() -> Result<()> {
+    let mut frame: Vec<u8> = b"CQ    ".iter().map(|c| c << 1).collect();
+    frame.push(0x60);
+    frame.extend(b"F4ABC ".iter().map(|c| c << 1));
+    frame.extend([0x61, 0x03, 0xF0]);
+    frame.extend(b"Hello from a 9600 baud modem");
+    let (mask, seed, len) = Lfsr::G3RUH;
+    let mut scrambler = Lfsr::new(mask, seed, len)?;
+    let symbols: Vec<f32> = nrzi_hdlc_bits(&frame)
+        .iter()
+        .map(|bit| 2.0 * scrambler.next_bit_scramble(*bit) as f32 - 1.0)
+        .collect();
+
+    let blk =
+        BlockInstance::new("deframer", "satellites_ax25_deframer").with("g3ruh_scrambler", "True");
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSource::<f32>::new(symbols));
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, &blk)?;
+    let (but_in, in_name) = block_under_test.adapt_input_port("0")?;
+    let (but_out, out_name) = block_under_test.adapt_output_port("0")?;
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let snk = fg.add_block(TestMessageSink::new(messages.clone()));
+    fg.connect_dyn(&src, "output", but_in, in_name)?;
+    fg.connect_message(but_out, out_name, &snk, "in_port")?;
+    Runtime::new().run(fg)?;
+    assert_eq!(vec![frame], *messages.lock().unwrap());
+    Ok(())
+}
@@ -0,0 +1,258 @@
+use anyhow::Result;
+use fsdr_cli::csdr_cmd::macro_cmd::{Macro, Macros};
+use fsdr_cli::csdr_cmd::ScriptParser;
+use fsdr_cli::grc::converter::Grc2FutureSdr;
+use fsdr_cli::grc::{BlockInstance, GrcParser};
+use std::process::Command;
+
+#[test]
+pub fn load_script_with_include_and_variables() -> Result<()> {
+    let grc = ScriptParser::load("tests/scripts/nfm_rx.fsdr")?;
+    let ids: Vec<&str> = grc.blocks.iter().map(|blk| blk.id.as_str()).collect();
+    assert_eq!(
+        vec![
+            "variable",
+            "variable",
+            "variable",
+            "variable",
+            "blocks_file_source",
+            "blocks_freqshift_cc",
+            "fir_filter_xxx",
+            "analog_quadrature_demod_cf",
+            "analog_nfm_deemph",
+            "analog_agc_xx",
+            "blocks_file_sink",
+        ],
+        ids
+    );
+    assert_eq!("samp_rate", grc.blocks[0].name);
+    assert_eq!("2.4M", grc.blocks[0].parameters["value"]);
+    assert_eq!("offset", grc.blocks[3].name);
+    assert_eq!("(offset/samp_rate)", grc.blocks[5].parameters["freq"]);
+    // Only the stream blocks are chained
+    assert_eq!(6, grc.connections.len());
+
+    let variables = Grc2FutureSdr::evaluate_variables(&grc)?;
+    assert_eq!(Some(&2_400_000.0), variables.get("samp_rate"));
+    assert_eq!(Some(&48_000.0), variables.get("audio_rate"));
+    assert_eq!(Some(&500_000.0), variables.get("offset"));
+    Ok(())
+}
+
+#[test]
+pub fn variables_are_doubles_evaluated_when_used() -> Result<()> {
+    let script = "let freq = 435166900\nshift_addition_cc (1000/freq)\n";
+    let grc = ScriptParser::parse(script)?;
+    let variables = Grc2FutureSdr::evaluate_variables(&grc)?;
+    assert_eq!(Some(&435_166_900.0), variables.get("freq"));
+
+    // A variable that is not a number only matters to the blocks using it
+    let with_label = |shift_freq: Option<&str>| -> Result<_> {
+        let mut grc = ScriptParser::parse(script)?;
+        grc.blocks
+            .push(BlockInstance::new("label", "variable").with("value", "'ISS'"));
+        if let Some(shift_freq) = shift_freq {
+            let shift = grc
+                .blocks
+                .iter_mut()
+                .find(|blk| blk.id == "blocks_freqshift_cc")
+                .unwrap();
+            shift
+                .parameters
+                .insert("freq".to_string(), shift_freq.to_string());
+        }
+        Ok(grc)
+    };
+    assert!(Grc2FutureSdr::new().convert_grc(with_label(None)?).is_ok());
+    let err = Grc2FutureSdr::new()
+        .convert_grc(with_label(Some("label"))?)
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("variable label cannot be evaluated"));
+    Ok(())
+}
+
+#[test]
+pub fn parse_script_with_continuation_and_comments() -> Result<()> {
+    let script = "# a comment\n\
+                  let rate = (1500/48000) # another one\n\
+                  \n\
+                  realpart_cf ! \\\n\
+                  \tgain_ff (rate*2)\n";
+    let grc = ScriptParser::parse(script)?;
+    assert_eq!(5, grc.blocks.len());
+    assert_eq!("variable", grc.blocks[0].id);
+    assert_eq!("rate", grc.blocks[0].name);
+    assert_eq!("blocks_complex_to_real", grc.blocks[2].id);
+    assert_eq!("blocks_multiply_const_vxx", grc.blocks[3].id);
+    Ok(())
+}
+
+#[test]
+pub fn script_errors_report_line_number() {
+    let script = "realpart_cf\nunknown_cmd 12\n";
+    let err = ScriptParser::parse(script).expect_err("unknown command must fail");
+    assert!(format!("{err:#}").contains("line 2"));
+}
+
+#[test]
+pub fn script_variable_must_be_defined() {
+    let script = "let a = (b*2)\n";
+    assert!(ScriptParser::parse(script).is_err());
+}
+
+#[test]
+pub fn script_include_cycle_is_detected() {
+    let err = ScriptParser::load("tests/scripts/cycle.fsdr").expect_err("cycle must fail");
+    assert!(format!("{err:#}").contains("include cycle"));
+}
+
+#[test]
+pub fn script_macro_expands_into_commands() -> Result<()> {
+    let script = "macro nfm_rx samp_rate audio_rate = fir_decimate_cc ($samp_rate/$audio_rate) ! fmdemod_quadri_cf ! deemphasis_nfm_ff $audio_rate ! agc_ff\n\
+                  load_c input.cf32 ! nfm_rx 2.4M 48k\n";
+    let grc = ScriptParser::parse(script)?;
+    let ids: Vec<&str> = grc.blocks.iter().map(|blk| blk.id.as_str()).collect();
+    assert_eq!(
+        vec![
+            "blocks_file_source",
+            "fir_filter_xxx",
+            "analog_quadrature_demod_cf",
+            "analog_nfm_deemph",
+            "analog_agc_xx",
+            "blocks_file_sink",
+        ],
+        ids
+    );
+    assert_eq!("48000", grc.blocks[3].parameters["samp_rate"]);
+    Ok(())
+}
+
+#[test]
+pub fn script_macro_checks_arguments() {
+    let script = "macro half = fractional_decimator_ff 2\n\
+                  load_f input.f32 ! half 3\n";
+    let err = ScriptParser::parse(script).expect_err("too many arguments must fail");
+    assert!(format!("{err:#}").contains("expecting 0 arguments but got 1"));
+}
+
+#[test]
+pub fn script_macro_recursion_is_bounded() {
+    let script = "macro again = again\nload_f input.f32 ! again\n";
+    assert!(ScriptParser::parse(script).is_err());
+}
+
+#[test]
+pub fn script_macro_forwards_raw_arguments() -> Result<()> {
+    let script =
+        "macro demod file algorithm = load_c $file ! timing_recovery_cc $algorithm 20 0.5 2\n\
+                  macro slow_agc rate = realpart_cf ! agc_ff --rate $rate\n\
+                  demod rec.c32 GARDNER ! slow_agc 0.01\n";
+    let grc = ScriptParser::parse(script)?;
+    assert_eq!("blocks_file_source", grc.blocks[0].id);
+    assert_eq!("rec.c32", grc.blocks[0].parameters["file"]);
+    assert_eq!("timing_recovery", grc.blocks[1].id);
+    assert_eq!("GARDNER", grc.blocks[1].parameters["algorithm"]);
+    // Only `$rate` is replaced, not the option name
+    assert_eq!("analog_agc_xx", grc.blocks[3].id);
+    assert_eq!("0.01", grc.blocks[3].parameters["rate"]);
+    Ok(())
+}
+
+#[test]
+pub fn script_macro_unknown_placeholder_fails() {
+    let script = "macro half = fractional_decimator_ff $factor\n\
+                  load_f input.f32 ! half\n";
+    let err = ScriptParser::parse(script).expect_err("unknown placeholder must fail");
+    assert!(format!("{err:#}").contains("unknown parameter $factor"));
+}
+
+#[test]
+pub fn builtin_command_errors_are_not_taken_for_macros() {
+    let script = "load_c input.c32 ! fir_decimate_cc\n";
+    let err = ScriptParser::parse(script).expect_err("missing argument must fail");
+    let err = format!("{err:#}");
+    assert!(err.contains("syntax error"));
+    assert!(!err.contains("unknown command or macro"));
+
+    let script = "macro gain_ff = agc_ff\nload_f input.f32 ! gain_ff\n";
+    let err = ScriptParser::parse(script).expect_err("built-in commands cannot be replaced");
+    assert!(format!("{err:#}").contains("syntax error in gain_ff"));
+}
+
+#[test]
+pub fn macro_arguments_stop_at_builtin_commands() -> Result<()> {
+    let script = "macro convert_twice factor = fractional_decimator_ff $factor ! fractional_decimator_ff $factor\n\
+                  load_f input.f32 convert_twice 2 gain_ff 0.5\n";
+    let grc = ScriptParser::parse(script)?;
+    let ids: Vec<&str> = grc.blocks.iter().map(|blk| blk.id.as_str()).collect();
+    assert_eq!(
+        vec![
+            "blocks_file_source",
+            "rational_resampler_xxx",
+            "rational_resampler_xxx",
+            "blocks_multiply_const_vxx",
+            "blocks_file_sink",
+        ],
+        ids
+    );
+    Ok(())
+}
+
+#[test]
+pub fn macros_are_loaded_from_config() -> Result<()> {
+    let macros = Macro::load("tests/config/futuresdr/config.toml")?;
+    assert_eq!(
+        vec!["demod_file", "slow_agc"],
+        macros.keys().collect::<Vec<_>>()
+    );
+    assert_eq!(vec!["file", "algorithm"], macros["demod_file"].params);
+    assert_eq!(
+        "Macros:\n\
+         \tdemod_file <file> <algorithm>\ttiming recovery of a recording\n\
+         \tslow_agc <rate>\n\
+         \n",
+        Macro::help(&macros)
+    );
+    assert_eq!("", Macro::help(&Macros::new()));
+    Ok(())
+}
+
+#[test]
+pub fn config_macros_are_listed_by_help_and_usable() -> Result<()> {
+    let help = Command::new(env!("CARGO_BIN_EXE_fsdr-cli"))
+        .arg("--help")
+        .env("XDG_CONFIG_HOME", "tests/config")
+        .output()?;
+    let stderr = String::from_utf8_lossy(&help.stderr);
+    assert!(stderr.contains("\tdemod_file <file> <algorithm>\ttiming recovery of a recording\n"));
+    assert!(stderr.contains("\tslow_agc <rate>\n"));
+
+    let output = std::env::temp_dir().join("fsdr_cli_config_macro.grc");
+    let run = Command::new(env!("CARGO_BIN_EXE_fsdr-cli"))
+        .args(["csdr", "-o"])
+        .arg(&output)
+        .args(["demod_file", "rec.c32", "GARDNER", "!", "realpart_cf", "!"])
+        .args(["slow_agc", "0.01"])
+        .env("XDG_CONFIG_HOME", "tests/config")
+        .output()?;
+    assert!(
+        run.status.success(),
+        "{}",
+        String::from_utf8_lossy(&run.stderr)
+    );
+    let grc = GrcParser::load(&output)?;
+    std::fs::remove_file(&output)?;
+    let ids: Vec<&str> = grc.blocks.iter().map(|blk| blk.id.as_str()).collect();
+    assert_eq!(
+        vec![
+            "blocks_file_source",
+            "timing_recovery",
+            "blocks_complex_to_real",
+            "analog_agc_xx",
+            "blocks_file_sink",
+        ],
+        ids
+    );
+    assert_eq!("0.01", grc.blocks[3].parameters["rate"]);
+    Ok(())
+}
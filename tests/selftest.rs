@@ -0,0 +1,66 @@
+use anyhow::Result;
+use fsdr_cli::cmd_grammar::{CommandsParser, Rule};
+use fsdr_cli::selftest::{compare, decode, Comparison, SELFTEST_CASES};
+
+#[test]
+pub fn parse_selftest() -> Result<()> {
+    let cmd = CommandsParser::parse_main("selftest convert_u8_f")?;
+    assert_eq!(Rule::selftest_cmd, cmd.as_rule());
+    let filter = cmd.into_inner().next().map(|name| name.as_str());
+    assert_eq!(Some("convert_u8_f"), filter);
+    Ok(())
+}
+
+#[test]
+pub fn compare_with_tolerance() -> Result<()> {
+    let samples: Vec<u8> = [1i16, -1].iter().flat_map(|v| v.to_ne_bytes()).collect();
+    let reference = decode("short", &samples)?;
+    assert_eq!(vec![1.0, -1.0], reference);
+    assert_eq!(
+        Comparison::Identical,
+        compare(&reference, &[1.0, -1.0], 0.0)
+    );
+    assert_eq!(
+        Comparison::WithinTolerance(1.0),
+        compare(&reference, &[2.0, -1.0], 1.0)
+    );
+    assert_eq!(
+        Comparison::Diverge {
+            index: 1,
+            expected: -1.0,
+            actual: 1.0
+        },
+        compare(&reference, &[1.0, 1.0], 1.0)
+    );
+    assert!(!compare(&reference, &[1.0], 1.0).passed());
+    Ok(())
+}
+
+#[test]
+pub fn csdr_commands_match_reference_vectors() -> Result<()> {
+    let mut failures = Vec::new();
+    for case in SELFTEST_CASES.iter() {
+        let comparison = case.run()?;
+        if !case.as_expected(&comparison) {
+            failures.push(format!("{}: {comparison}", case.command));
+        }
+    }
+    assert!(failures.is_empty(), "{failures:#?}");
+    Ok(())
+}
+
+#[test]
+pub fn known_divergences_are_expected_failures() {
+    let case = SELFTEST_CASES
+        .iter()
+        .find(|case| case.name == "fmdemod_quadri_cf")
+        .expect("fmdemod_quadri_cf case");
+    assert!(case.known_divergence.is_some());
+    assert_eq!(1e-5, case.tolerance);
+    assert!(!case.as_expected(&Comparison::Identical));
+    assert!(case.as_expected(&Comparison::Diverge {
+        index: 0,
+        expected: 0.0,
+        actual: 1.0
+    }));
+}
@@ -0,0 +1,69 @@
+#![cfg(unix)]
+use anyhow::{bail, Result};
+use std::process::{Command, Stdio};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+/// Exit code of `fsdr-cli` when interrupted, like shells do for SIGINT
+const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+#[test]
+pub fn sigint_terminates_flowgraph_and_flushes_sink() -> Result<()> {
+    let output = std::env::temp_dir().join("fsdr_cli_sigint.kiss");
+    if output.exists() {
+        std::fs::remove_file(&output)?;
+    }
+    let mut child = Command::new(env!("CARGO_BIN_EXE_fsdr-cli"))
+        .args(["csdr", "tone_f", "1000", "48000", "!", "throttle_ff", "!"])
+        .args([
+            "binary_slicer_f_u8",
+            "!",
+            "fixedlen_to_pdu",
+            "8",
+            "!",
+            "save_kiss",
+        ])
+        .arg(&output)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Wait for the flowgraph to run before interrupting it
+    let start = Instant::now();
+    while std::fs::metadata(&output).map_or(0, |m| m.len()) == 0 {
+        if start.elapsed() > Duration::from_secs(10) {
+            child.kill()?;
+            bail!("nothing written into {}", output.display());
+        }
+        sleep(Duration::from_millis(50));
+    }
+    sleep(Duration::from_millis(200));
+    let kill = Command::new("kill")
+        .args(["-INT", &child.id().to_string()])
+        .status()?;
+    assert!(kill.success());
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if start.elapsed() > Duration::from_secs(10) {
+            child.kill()?;
+            bail!("fsdr-cli did not stop on SIGINT");
+        }
+        sleep(Duration::from_millis(50));
+    };
+    assert_eq!(Some(INTERRUPTED_EXIT_CODE), status.code());
+
+    // Only whole KISS frames are written
+    let written = std::fs::read(&output)?;
+    std::fs::remove_file(&output)?;
+    assert_eq!(Some(&0xC0), written.first());
+    assert_eq!(Some(&0xC0), written.last());
+    let fends = written.iter().filter(|&&byte| byte == 0xC0).count();
+    assert_eq!(0, fends % 2);
+    // FEND, 8 bits from the slicer, FEND
+    assert_eq!(0, written.len() % 10);
+    Ok(())
+}
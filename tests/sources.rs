@@ -0,0 +1,162 @@
+use anyhow::Result;
+use fsdr_cli::blocks::{NoiseGenerator, NoiseType, RepeatSource, Waveform};
+use fsdr_cli::csdr_cmd::CsdrParser;
+use fsdr_cli::grc::converter::Grc2FutureSdr;
+use fsdr_cli::grc::BlockInstance;
+use futuresdr::blocks::{Head, VectorSink};
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::{Flowgraph, Runtime};
+
+#[test]
+pub fn parse_tone_c() {
+    let cmds = "csdr tone_c 1000 48000 ! realpart_cf";
+    let grc = CsdrParser::parse_multiple_commands(cmds)
+        .expect("")
+        .unwrap();
+    assert_eq!(3, grc.blocks.len());
+    assert_eq!("analog_sig_source_x", grc.blocks[0].id);
+    assert_eq!("complex", grc.blocks[0].parameters["type"]);
+    assert_eq!("1000", grc.blocks[0].parameters["freq"]);
+    assert_eq!("48000", grc.blocks[0].parameters["samp_rate"]);
+    assert_eq!("1.0", grc.blocks[0].parameters["amp"]);
+    assert_eq!("blocks_complex_to_real", grc.blocks[1].id);
+}
+
+#[test]
+pub fn parse_noise_c() {
+    let grc = CsdrParser::parse_command("noise_c 0.1").expect("").unwrap();
+    assert_eq!(2, grc.blocks.len());
+    assert_eq!("analog_noise_source_x", grc.blocks[0].id);
+    assert_eq!("complex", grc.blocks[0].parameters["type"]);
+    assert_eq!("0.1", grc.blocks[0].parameters["amp"]);
+    assert_eq!("0", grc.blocks[0].parameters["seed"]);
+    assert_eq!("blocks_file_sink", grc.blocks[1].id);
+}
+
+#[test]
+pub fn source_must_start_pipeline() {
+    assert!(CsdrParser::parse_multiple_commands("csdr convert_u8_f ! tone_f 1000 48000").is_err());
+}
+
+#[test]
+pub fn waveforms() {
+    use std::f64::consts::PI;
+    // GNU Radio: amplitude while the phase in [-π, π) is negative, so it starts low
+    assert_eq!(0.0, Waveform::Square.value(0.0, 2.0));
+    assert_eq!(0.0, Waveform::Square.value(0.5 * PI, 2.0));
+    assert_eq!(2.0, Waveform::Square.value(PI, 2.0));
+    assert_eq!(2.0, Waveform::Square.value(1.5 * PI, 2.0));
+    // GNU Radio: amplitude * |phase| / π, the phase being in [-π, π)
+    assert_eq!(0.0, Waveform::Triangle.value(0.0, 2.0));
+    assert_eq!(1.0, Waveform::Triangle.value(0.5 * PI, 2.0));
+    assert_eq!(2.0, Waveform::Triangle.value(PI, 2.0));
+    assert_eq!(1.0, Waveform::Triangle.value(1.5 * PI, 2.0));
+    assert_eq!(1.0, Waveform::Sawtooth.value(0.0, 2.0));
+    assert_eq!(0.0, Waveform::Sawtooth.value(PI, 2.0));
+    assert_eq!(2.0, Waveform::Constant.value(1.0, 2.0));
+    assert_eq!(
+        Waveform::Cosine,
+        Waveform::try_from("analog.GR_COS_WAVE").unwrap()
+    );
+}
+
+#[test]
+pub fn noise_is_reproducible() {
+    let mut a = NoiseGenerator::new(42, NoiseType::Gaussian);
+    let mut b = NoiseGenerator::new(42, NoiseType::Gaussian);
+    let a: Vec<f32> = (0..100_000).map(|_| a.next_f32()).collect();
+    let b: Vec<f32> = (0..100_000).map(|_| b.next_f32()).collect();
+    assert_eq!(a, b);
+    let mean = a.iter().sum::<f32>() / a.len() as f32;
+    let variance = a.iter().map(|v| (v - mean) * (v - mean)).sum::<f32>() / a.len() as f32;
+    assert!(mean.abs() < 0.02, "mean {mean}");
+    assert!((variance - 1.0).abs() < 0.02, "variance {variance}");
+
+    let mut uniform = NoiseGenerator::new(1, NoiseType::Uniform);
+    assert!((0..10_000)
+        .map(|_| uniform.next_f32())
+        .all(|v| (-1.0..=1.0).contains(&v)));
+}
+
+#[test]
+pub fn convert_sig_source_cos() -> Result<()> {
+    let blk = BlockInstance::new("analog_sig_source_x_0", "analog_sig_source_x")
+        .with("type", "complex")
+        .with("waveform", "analog.GR_COS_WAVE")
+        .with("freq", "12000")
+        .with("samp_rate", "48000")
+        .with("amp", "2.0");
+
+    let mut fg = Flowgraph::new();
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, &blk)?;
+    let (but_out, out_name) = block_under_test.adapt_output_port("0")?;
+    let (_, freq_name) = block_under_test.adapt_input_port("freq")?;
+    assert_eq!("freq", freq_name);
+    let head = fg.add_block(Head::<Complex32>::new(8));
+    let snk = fg.add_block(VectorSink::<Complex32>::new(8));
+    fg.connect_dyn(but_out, out_name, &head, "input")?;
+    fg.connect_dyn(&head, "output", &snk, "input")?;
+    Runtime::new().run(fg)?;
+
+    let snk = snk.get().unwrap();
+    let items = snk.items();
+    assert_eq!(8, items.len());
+    let expected = [(2.0, 0.0), (0.0, 2.0), (-2.0, 0.0), (0.0, -2.0)];
+    for (i, (v, (re, im))) in items.iter().zip(expected.iter().cycle()).enumerate() {
+        assert!(
+            (v.re - re).abs() < 1e-4 && (v.im - im).abs() < 1e-4,
+            "at index {i}, expected: ({re}, {im}), got: {v}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+pub fn parse_vector() -> Result<()> {
+    let vector = Grc2FutureSdr::parse_vector("(1, 0.5, -1j, 2+3j, 1e-3-2j)")?;
+    assert_eq!(
+        vec![
+            Complex32::new(1.0, 0.0),
+            Complex32::new(0.5, 0.0),
+            Complex32::new(0.0, -1.0),
+            Complex32::new(2.0, 3.0),
+            Complex32::new(1e-3, -2.0),
+        ],
+        vector
+    );
+    assert_eq!(
+        vec![Complex32::new(1.0, 0.0), Complex32::new(2.0, 0.0)],
+        Grc2FutureSdr::parse_vector("[1, 2]")?
+    );
+    Ok(())
+}
+
+#[test]
+pub fn convert_vector_source() -> Result<()> {
+    let blk = BlockInstance::new("blocks_vector_source_x_0", "blocks_vector_source_x")
+        .with("type", "float")
+        .with("vector", "(1, 2, 3)")
+        .with("repeat", "True");
+
+    let mut fg = Flowgraph::new();
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, &blk)?;
+    let (but_out, out_name) = block_under_test.adapt_output_port("0")?;
+    let head = fg.add_block(Head::<f32>::new(7));
+    let snk = fg.add_block(VectorSink::<f32>::new(7));
+    fg.connect_dyn(but_out, out_name, &head, "input")?;
+    fg.connect_dyn(&head, "output", &snk, "input")?;
+    Runtime::new().run(fg)?;
+
+    let snk = snk.get().unwrap();
+    assert_eq!(
+        vec![1.0, 2.0, 3.0, 1.0, 2.0, 3.0, 1.0],
+        snk.items().to_vec()
+    );
+    Ok(())
+}
+
+#[test]
+pub fn repeat_source_needs_items() {
+    assert!(RepeatSource::<f32>::new(Vec::new()).is_err());
+    assert!(RepeatSource::<f32>::new(vec![1.0]).is_ok());
+}
@@ -0,0 +1,63 @@
+use anyhow::Result;
+use fsdr_cli::cmd_grammar::{CommandsParser, Rule};
+use fsdr_cli::csdr_cmd::ScriptParser;
+use fsdr_cli::grc::converter::Grc2FutureSdr;
+use fsdr_cli::stats::StatsFormat;
+use futuresdr::runtime::Runtime;
+use std::time::Duration;
+
+#[test]
+pub fn parse_stats_option() -> Result<()> {
+    let (options, cmd) =
+        CommandsParser::parse_main_with_options("--stats=json --ctrlport csdr realpart_cf")?;
+    assert_eq!(2, options.len());
+    assert_eq!(Rule::stats_opt, options[0].as_rule());
+    let format = options[0].clone().into_inner().next().map(|p| p.as_str());
+    assert_eq!(Some("json"), format);
+    assert_eq!(Rule::csdr_cmd, cmd.as_rule());
+
+    let (options, _cmd) = CommandsParser::parse_main_with_options("--stats realpart_cf")?;
+    assert_eq!(None, options[0].clone().into_inner().next());
+    Ok(())
+}
+
+#[test]
+pub fn stats_count_items_of_each_block() -> Result<()> {
+    let dir = std::env::temp_dir();
+    let input = dir.join("fsdr_cli_stats_input.f32");
+    let output = dir.join("fsdr_cli_stats_output.f32");
+    let samples: Vec<u8> = (0..1000).flat_map(|i| (i as f32).to_le_bytes()).collect();
+    std::fs::write(&input, samples)?;
+
+    let mut grc = ScriptParser::parse(&format!("load_f {}\ngain_ff 2\n", input.display()))?;
+    let sink = grc.blocks.last_mut().expect("a sink");
+    sink.parameters
+        .insert("file".to_string(), output.display().to_string());
+
+    let mut converter = Grc2FutureSdr::new();
+    converter.with_stats(StatsFormat::Json);
+    let fg = converter.convert_grc(grc)?;
+    let stats = converter.take_stats().expect("stats are enabled");
+    Runtime::new().run(fg)?;
+
+    let blocks = stats.blocks(Duration::from_secs(1));
+    let ids: Vec<&str> = blocks.iter().map(|blk| blk.block_type.as_str()).collect();
+    assert_eq!(
+        vec![
+            "blocks_file_source",
+            "blocks_multiply_const_vxx",
+            "blocks_file_sink"
+        ],
+        ids
+    );
+    assert_eq!(1000, blocks[0].produced);
+    assert_eq!(1000, blocks[1].consumed);
+    assert_eq!(1000, blocks[1].produced);
+    assert_eq!(1000, blocks[2].consumed);
+    assert_eq!(1000.0, blocks[2].throughput);
+    assert!(blocks[0].input_fill.is_none());
+
+    let report = stats.report(Duration::from_secs(1))?;
+    assert!(report.starts_with("{\"elapsed\":1.0,\"blocks\":[{\"name\":"));
+    Ok(())
+}
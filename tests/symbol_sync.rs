@@ -0,0 +1,229 @@
+use anyhow::Result;
+use fsdr_cli::blocks::modulators::DigitalConstellation;
+use fsdr_cli::blocks::synchronizers::interpolating_resampler::InterpolatingResampler;
+use fsdr_cli::blocks::synchronizers::{SymbolSync, SymbolSyncSample, TedType};
+use fsdr_cli::csdr_cmd::CsdrParser;
+use futuresdr::blocks::{VectorSink, VectorSource};
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::{Flowgraph, Runtime};
+use std::f32::consts::PI;
+
+const SPS: f32 = 4.0;
+
+/// Raised cosine pulse, `t` in symbols
+fn raised_cosine(t: f32, beta: f32) -> f32 {
+    let sinc = |t: f32| {
+        if t == 0.0 {
+            1.0
+        } else {
+            (PI * t).sin() / (PI * t)
+        }
+    };
+    let denominator = 1.0 - (2.0 * beta * t).powi(2);
+    if denominator.abs() < 1e-6 {
+        PI / 4.0 * sinc(1.0 / (2.0 * beta))
+    } else {
+        sinc(t) * (PI * beta * t).cos() / denominator
+    }
+}
+
+/// Pseudo random BPSK symbols
+fn symbols(count: usize) -> Vec<f32> {
+    let mut state = 0x1234_5678u32;
+    (0..count)
+        .map(|_| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            if state & 0x4000_0000 == 0 {
+                1.0
+            } else {
+                -1.0
+            }
+        })
+        .collect()
+}
+
+/// Shaped BPSK, whose first symbol is at `offset` samples
+fn shaped(symbols: &[f32], offset: f32) -> Vec<f32> {
+    let len = (symbols.len() as f32 * SPS) as usize;
+    (0..len)
+        .map(|n| {
+            let t = (n as f32 - offset) / SPS;
+            let first = (t - 6.0).ceil().max(0.0) as usize;
+            let last = ((t + 6.0).floor().max(0.0) as usize).min(symbols.len() - 1);
+            (first..=last)
+                .map(|k| symbols[k] * raised_cosine(t - k as f32, 0.5))
+                .sum()
+        })
+        .collect()
+}
+
+fn synchronize<T: SymbolSyncSample>(ted_type: TedType, input: Vec<T>) -> Result<Vec<T>> {
+    let mut fg = Flowgraph::new();
+    let len = input.len();
+    let src = fg.add_block(VectorSource::<T>::new(input));
+    let sync = fg.add_block(SymbolSync::<T>::new(
+        ted_type,
+        DigitalConstellation::bpsk(),
+        SPS,
+        0.045,
+        1.0,
+        1.0,
+        1.5,
+    )?);
+    let snk = fg.add_block(VectorSink::<T>::new(len));
+    fg.connect_dyn(&src, "output", &sync, "input")?;
+    fg.connect_dyn(&sync, "output", &snk, "input")?;
+    Runtime::new().run(fg)?;
+    let snk = snk.get().unwrap();
+    Ok(snk.items().to_vec())
+}
+
+/// Fraction of the symbols recovered once the loop has converged
+fn recovered(symbols: &[f32], output: &[f32]) -> f32 {
+    let range = 1000..1900;
+    assert!(output.len() > range.end + 10, "{} symbols", output.len());
+    (-10isize..=10)
+        .map(|delay| {
+            range
+                .clone()
+                .filter(|j| {
+                    let k = (*j as isize + delay) as usize;
+                    output[*j].signum() == symbols[k]
+                })
+                .count() as f32
+                / range.len() as f32
+        })
+        .fold(0.0, f32::max)
+}
+
+fn check_ted(ted_type: TedType) -> Result<()> {
+    let symbols = symbols(2000);
+    let output = synchronize(ted_type, shaped(&symbols, 1.3))?;
+    let expected = symbols.len() as f32;
+    assert!(
+        (output.len() as f32 - expected).abs() < 0.01 * expected,
+        "{ted_type:?}: {} symbols",
+        output.len()
+    );
+    let ratio = recovered(&symbols, &output);
+    assert!(
+        ratio > 0.99,
+        "{ted_type:?}: {ratio} of the symbols recovered"
+    );
+    // Once converged, the samples are close to the symbols
+    let mean_error = output[1000..1900]
+        .iter()
+        .map(|v| (v.abs() - 1.0).abs())
+        .sum::<f32>()
+        / 900.0;
+    assert!(mean_error < 0.1, "{ted_type:?}: mean error {mean_error}");
+    Ok(())
+}
+
+#[test]
+pub fn symbol_sync_gardner() -> Result<()> {
+    check_ted(TedType::Gardner)
+}
+
+#[test]
+pub fn symbol_sync_mueller_and_muller() -> Result<()> {
+    check_ted(TedType::MuellerAndMuller)
+}
+
+#[test]
+pub fn symbol_sync_zero_crossing() -> Result<()> {
+    check_ted(TedType::ZeroCrossing)
+}
+
+#[test]
+pub fn symbol_sync_early_late() -> Result<()> {
+    check_ted(TedType::EarlyLate)
+}
+
+#[test]
+pub fn symbol_sync_signal_times_slope() -> Result<()> {
+    check_ted(TedType::SignalTimesSlope)
+}
+
+#[test]
+pub fn symbol_sync_complex() -> Result<()> {
+    let symbols = symbols(2000);
+    let input: Vec<Complex32> = shaped(&symbols, 2.6)
+        .iter()
+        .map(|v| Complex32::new(0.0, *v))
+        .collect();
+    let output = synchronize(TedType::Gardner, input)?;
+    let output: Vec<f32> = output.iter().map(|v| v.im).collect();
+    assert!(recovered(&symbols, &output) > 0.99);
+    Ok(())
+}
+
+#[test]
+pub fn cubic_interpolation() {
+    let f = |t: f32| 0.5 * t * t * t - t * t + 2.0;
+    let df = |t: f32| 1.5 * t * t - 2.0 * t;
+    let resampler =
+        InterpolatingResampler::new([-1.0, 0.0, 1.0, 2.0].map(|t| Complex32::new(f(t), -f(t))));
+    for mu in [0.0, 0.25, 0.5, 0.9] {
+        let v = resampler.interpolate(mu);
+        assert!(
+            (v.re - f(mu)).abs() < 1e-5 && (v.im + f(mu)).abs() < 1e-5,
+            "{mu}: {v}"
+        );
+        let d = resampler.differentiate(mu);
+        assert!((d.re - df(mu)).abs() < 1e-5, "{mu}: {d}");
+    }
+}
+
+#[test]
+pub fn invalid_parameters_are_errors() {
+    let build = |sps, max_deviation| {
+        SymbolSync::<f32>::new(
+            TedType::Gardner,
+            DigitalConstellation::bpsk(),
+            sps,
+            0.045,
+            1.0,
+            1.0,
+            max_deviation,
+        )
+    };
+    assert!(build(1.0, 0.0).is_err());
+    assert!(build(SPS, -0.5).is_err());
+    assert!(build(SPS, SPS - 1.0).is_err());
+    assert!(build(SPS, 1.5).is_ok());
+}
+
+#[test]
+pub fn ted_names() {
+    assert_eq!(
+        TedType::Gardner,
+        TedType::try_from("digital.TED_GARDNER").unwrap()
+    );
+    assert_eq!(TedType::MuellerAndMuller, TedType::try_from("mm").unwrap());
+    assert!(TedType::try_from("digital.TED_UNKNOWN").is_err());
+}
+
+#[test]
+pub fn parse_symbol_sync() {
+    let grc = CsdrParser::parse_multiple_commands(
+        "csdr symbol_sync_cc 8 --ted mm --loop_bw 0.01 --ted_gain 2 ! realpart_cf",
+    )
+    .expect("")
+    .unwrap();
+    let blk = &grc.blocks[1];
+    assert_eq!("digital_symbol_sync_xx", blk.id);
+    assert_eq!("cc", blk.parameters["type"]);
+    assert_eq!("8", blk.parameters["sps"]);
+    assert_eq!("mm", blk.parameters["ted_type"]);
+    assert_eq!("0.01", blk.parameters["loop_bw"]);
+    assert_eq!("2", blk.parameters["ted_gain"]);
+    assert_eq!("1.5", blk.parameters["max_dev"]);
+
+    let grc = CsdrParser::parse_command("symbol_sync_ff 4")
+        .expect("")
+        .unwrap();
+    assert_eq!("ff", grc.blocks[1].parameters["type"]);
+    assert_eq!("gardner", grc.blocks[1].parameters["ted_type"]);
+    assert_eq!("float", grc.blocks[0].parameters["type"]);
+}
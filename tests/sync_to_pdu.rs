@@ -0,0 +1,143 @@
+use anyhow::Result;
+use fsdr_cli::blocks::{AccessCode, Correlator, SyncToPdu, SyncToPduInput};
+use fsdr_cli::csdr_cmd::CsdrParser;
+use fsdr_cli::grc::converter::Grc2FutureSdr;
+use futuresdr::blocks::VectorSource;
+use futuresdr::runtime::{Flowgraph, Runtime};
+use std::sync::{Arc, Mutex};
+
+mod common;
+use common::TestMessageSink;
+
+const SYNC: u64 = 0x1ACFFC1D;
+
+fn bits(value: u64, len: usize) -> Vec<u8> {
+    (0..len).rev().map(|i| ((value >> i) & 1) as u8).collect()
+}
+
+/// Two frames of 16 bits, the sync word of the first one having a bit error
+fn frames() -> Vec<u8> {
+    let mut input = bits(0b0110_1001, 8);
+    input.extend(bits(SYNC ^ 0x0400_0000, 32));
+    input.extend(bits(0xCAFE, 16));
+    input.extend(bits(0x55, 8));
+    input.extend(bits(SYNC, 32));
+    input.extend(bits(0xBEEF, 16));
+    input.extend(bits(0, 8));
+    input
+}
+
+fn pdus<T: SyncToPduInput>(block: SyncToPdu<T>, input: Vec<T>) -> Result<Vec<Vec<u8>>> {
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSource::<T>::new(input));
+    let block_under_test = fg.add_block(block);
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let snk = fg.add_block(TestMessageSink::new(messages.clone()));
+    fg.connect_dyn(&src, "output", &block_under_test, "input")?;
+    fg.connect_message(&block_under_test, "pdus", &snk, "in_port")?;
+    Runtime::new().run(fg)?;
+    let messages = messages.lock().unwrap().clone();
+    Ok(messages)
+}
+
+fn correlator(threshold: u32) -> Result<Correlator> {
+    Ok(Correlator::new(
+        AccessCode::try_from("0x1ACFFC1D")?,
+        threshold,
+        false,
+    ))
+}
+
+#[test]
+fn sync_to_pdu_unpacked() -> Result<()> {
+    let received = pdus(SyncToPdu::new(correlator(1)?, 16), frames())?;
+    assert_eq!(vec![vec![0xCA, 0xFE], vec![0xBE, 0xEF]], received);
+
+    let received = pdus(SyncToPdu::new(correlator(0)?, 16), frames())?;
+    assert_eq!(vec![vec![0xBE, 0xEF]], received);
+    Ok(())
+}
+
+#[test]
+fn sync_to_pdu_packed() -> Result<()> {
+    let input: Vec<u8> = frames()
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0, |acc, bit| (acc << 1) | bit))
+        .collect();
+    let received = pdus(SyncToPdu::packed(correlator(1)?, 16), input)?;
+    assert_eq!(vec![vec![0xCA, 0xFE], vec![0xBE, 0xEF]], received);
+    Ok(())
+}
+
+#[test]
+fn sync_to_pdu_soft() -> Result<()> {
+    let input: Vec<f32> = frames()
+        .iter()
+        .map(|bit| if *bit == 1 { 0.8 } else { -1.2 })
+        .collect();
+    let received = pdus(SyncToPdu::<f32>::new(correlator(1)?, 16), input)?;
+    assert_eq!(vec![vec![0xCA, 0xFE], vec![0xBE, 0xEF]], received);
+    Ok(())
+}
+
+#[test]
+fn sync_to_pdu_pads_last_byte() -> Result<()> {
+    let received = pdus(SyncToPdu::new(correlator(1)?, 12), frames())?;
+    assert_eq!(vec![vec![0xCA, 0xF0], vec![0xBE, 0xE0]], received);
+    Ok(())
+}
+
+#[test]
+fn sync_to_pdu_overlapping_frames() -> Result<()> {
+    // The second sync word is within the frame following the first one
+    let mut input = bits(SYNC, 32);
+    input.extend(bits(SYNC, 32));
+    input.extend(bits(0xBEEF_BEEF, 32));
+    let received = pdus(SyncToPdu::new(correlator(0)?, 32), input)?;
+    assert_eq!(
+        vec![vec![0x1A, 0xCF, 0xFC, 0x1D], vec![0xBE, 0xEF, 0xBE, 0xEF]],
+        received
+    );
+    Ok(())
+}
+
+#[test]
+fn sync_to_pdu_command() -> Result<()> {
+    let grc = CsdrParser::parse_command("sync_to_pdu_u8 16 0x1ACFFC1D 1")
+        .expect("")
+        .unwrap();
+    let blk = &grc.blocks[1];
+    assert_eq!("satellites_sync_to_pdu", blk.id);
+    assert_eq!("16", blk.parameters["packlen"]);
+    assert_eq!("0x1ACFFC1D", blk.parameters["sync"]);
+    assert_eq!("1", blk.parameters["threshold"]);
+
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSource::<u8>::new(frames()));
+    let block_under_test = Grc2FutureSdr::new().convert_block(&mut fg, blk)?;
+    let (but_in, in_name) = block_under_test.adapt_input_port("0")?;
+    let (but_out, out_name) = block_under_test.adapt_output_port("0")?;
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let snk = fg.add_block(TestMessageSink::new(messages.clone()));
+    fg.connect_dyn(&src, "output", but_in, in_name)?;
+    fg.connect_message(but_out, out_name, &snk, "in_port")?;
+    Runtime::new().run(fg)?;
+    assert_eq!(
+        vec![vec![0xCA, 0xFE], vec![0xBE, 0xEF]],
+        *messages.lock().unwrap()
+    );
+
+    let grc = CsdrParser::parse_command("sync_to_pdu_packed_u8 (8*223) 0001_1010_1100_1111")
+        .expect("")
+        .unwrap();
+    assert_eq!("satellites_sync_to_pdu_packed", grc.blocks[1].id);
+    assert_eq!("8*223", grc.blocks[1].parameters["packlen"]);
+    assert_eq!("0001_1010_1100_1111", grc.blocks[1].parameters["sync"]);
+    assert_eq!("0", grc.blocks[1].parameters["threshold"]);
+
+    let grc = CsdrParser::parse_command("sync_to_pdu_f 16 0x1ACF")
+        .expect("")
+        .unwrap();
+    assert_eq!("satellites_sync_to_pdu_soft", grc.blocks[1].id);
+    Ok(())
+}
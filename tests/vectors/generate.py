@@ -0,0 +1,166 @@
+#!/usr/bin/env python3
+"""Generate the inputs and reference outputs of `fsdr-cli selftest`.
+
+Without argument, references are computed from the formulas of libcsdr.
+With --csdr, they are produced by piping each input into a real `csdr` binary.
+"""
+import math
+import os
+import struct
+import subprocess
+import sys
+
+N = 256
+HERE = os.path.dirname(os.path.abspath(__file__))
+
+
+def f32(value):
+    return struct.unpack("<f", struct.pack("<f", value))[0]
+
+
+def trunc(value, lo, hi):
+    # C conversion from float to an integer type, saturated like the FPU does
+    return max(lo, min(hi, int(value)))
+
+
+INPUTS = {
+    "u8": ("B", list(range(N))),
+    "s8": ("b", [i - 128 for i in range(N)]),
+    "s16": ("h", [-32768 + 256 * i for i in range(N)]),
+    "f": ("f", [f32(0.9 * math.sin(2 * math.pi * i / 32)) for i in range(N)]),
+    "f_dc": ("f", [f32(0.25 + 0.5 * math.sin(2 * math.pi * i / 32)) for i in range(N)]),
+    "c": (
+        "f",
+        [
+            f32(v)
+            for i in range(N)
+            for v in (
+                (0.2 + 0.6 * i / N) * math.cos(2 * math.pi * 3 * i / 64),
+                (0.2 + 0.6 * i / N) * math.sin(2 * math.pi * 3 * i / 64),
+            )
+        ],
+    ),
+}
+
+
+def pairs(values):
+    return zip(values[0::2], values[1::2])
+
+
+def interleave(samples):
+    return [f32(v) for s in samples for v in (s.real, s.imag)]
+
+
+def shift_addition(x, rate):
+    # Rotation updated with the trigonometric addition formulas, like libcsdr
+    sindelta, cosdelta = f32(math.sin(rate * 2 * math.pi)), f32(math.cos(rate * 2 * math.pi))
+    cosphi, sinphi = 1.0, 0.0
+    output = []
+    for i, q in pairs(x):
+        output.append(complex(f32(cosphi * i - sinphi * q), f32(sinphi * i + cosphi * q)))
+        cosphi, sinphi = (
+            f32(cosphi * cosdelta - sinphi * sindelta),
+            f32(sinphi * cosdelta + cosphi * sindelta),
+        )
+    return interleave(output)
+
+
+def fmdemod_quadri(x):
+    output = []
+    last_i, last_q = 0.0, 0.0
+    for i, q in pairs(x):
+        output.append((i * (q - last_q) - q * (i - last_i)) / (i * i + q * q))
+        last_i, last_q = i, q
+    return output
+
+
+def firdes_lowpass(length, cutoff_rate):
+    # firdes_lowpass_f with a Hamming window, normalized to a unity gain
+    middle = length // 2
+    hamming = lambda rate: 0.54 - 0.46 * math.cos(2 * math.pi * (0.5 + rate / 2))
+    taps = [0.0] * length
+    taps[middle] = 2 * math.pi * cutoff_rate * hamming(0)
+    for i in range(1, middle + 1):
+        taps[middle - i] = taps[middle + i] = math.sin(2 * math.pi * cutoff_rate * i) / i * hamming(i / middle)
+    total = sum(taps)
+    return [t / total for t in taps]
+
+
+def fir_decimate(x, factor, transition_bw):
+    # firdes_filter_len, the transition bandwidth being a float
+    length = int(4.0 / f32(transition_bw))
+    length += 1 if length % 2 == 0 else 0
+    taps = firdes_lowpass(length, 0.5 / factor)
+    samples = [complex(i, q) for i, q in pairs(x)]
+    output = [
+        sum(t * v for t, v in zip(taps, samples[start : start + length]))
+        for start in range(0, len(samples) - length + 1, factor)
+    ]
+    return interleave(output)
+
+
+def deemphasis_wfm(x, sample_rate, tau):
+    dt = 1.0 / sample_rate
+    alpha = dt / (tau + dt)
+    output, last = [], 0.0
+    for v in x:
+        last = alpha * v + (1 - alpha) * last
+        output.append(last)
+    return output
+
+
+def fastdcblock(x, last_dc_level=0.0):
+    # The DC level, averaged on the whole buffer, is linearly removed
+    avg = sum(x) / len(x)
+    diff = avg - last_dc_level
+    return [v - (last_dc_level + diff * i / len(x)) for i, v in enumerate(x)]
+
+
+# name: (command, input, output format, reference from libcsdr formulas)
+CASES = {
+    "convert_u8_f": ("convert_u8_f", "u8", "f", lambda x: [v / 127.5 - 1.0 for v in x]),
+    "convert_s8_f": ("convert_s8_f", "s8", "f", lambda x: [v / 127.0 for v in x]),
+    "convert_s16_f": ("convert_s16_f", "s16", "f", lambda x: [v / 32767.0 for v in x]),
+    "convert_f_u8": ("convert_f_u8", "f", "B", lambda x: [trunc(v * 255 * 0.5 + 128, 0, 255) for v in x]),
+    "convert_f_s8": ("convert_f_s8", "f", "b", lambda x: [trunc(v * 127, -128, 127) for v in x]),
+    "convert_f_s16": ("convert_f_s16", "f", "h", lambda x: [trunc(v * 32767, -32768, 32767) for v in x]),
+    "realpart_cf": ("realpart_cf", "c", "f", lambda x: [i for i, _ in pairs(x)]),
+    "amdemod_cf": ("amdemod_cf", "c", "f", lambda x: [math.sqrt(i * i + q * q) for i, q in pairs(x)]),
+    "dsb_fc": ("dsb_fc", "f", "f", lambda x: [v for s in x for v in (s, 0.0)]),
+    "gain_ff": ("gain_ff 0.5", "f", "f", lambda x: [v * 0.5 for v in x]),
+    "limit_ff": ("limit_ff 0.5", "f", "f", lambda x: [max(-0.5, min(0.5, v)) for v in x]),
+    "clipdetect_ff": ("clipdetect_ff", "f", "f", lambda x: list(x)),
+    "shift_addition_cc": ("shift_addition_cc 0.1", "c", "f", lambda x: shift_addition(x, 0.1)),
+    "fir_decimate_cc": ("fir_decimate_cc 2 0.05", "c", "f", lambda x: fir_decimate(x, 2, 0.05)),
+    "fmdemod_quadri_cf": ("fmdemod_quadri_cf", "c", "f", fmdemod_quadri),
+    "deemphasis_wfm_ff": ("deemphasis_wfm_ff 48000 50e-6", "f", "f", lambda x: deemphasis_wfm(x, 48000, 50e-6)),
+    "fastdcblock_ff": ("fastdcblock_ff", "f_dc", "f", fastdcblock),
+}
+# Not covered, their output depending on how csdr splits the input into buffers:
+# agc_ff, rational_resampler_ff, deemphasis_nfm_ff and bandpass_fir_fft_cc.
+
+
+def pack(fmt, values):
+    # Native byte order, like csdr
+    return struct.pack("=%d%s" % (len(values), fmt), *values)
+
+
+def main():
+    use_csdr = "--csdr" in sys.argv[1:]
+    for name, (command, input_name, output_fmt, reference) in CASES.items():
+        input_fmt, values = INPUTS[input_name]
+        data = pack(input_fmt, values)
+        with open(os.path.join(HERE, name + ".in"), "wb") as f:
+            f.write(data)
+        if use_csdr:
+            output = subprocess.run(
+                ["csdr"] + command.split(), input=data, stdout=subprocess.PIPE, check=True
+            ).stdout
+        else:
+            output = pack(output_fmt, reference(values))
+        with open(os.path.join(HERE, name + ".ref"), "wb") as f:
+            f.write(output)
+
+
+if __name__ == "__main__":
+    main()
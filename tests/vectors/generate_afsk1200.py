@@ -0,0 +1,103 @@
+#!/usr/bin/env python3
+"""Generate `afsk1200.wav`, the reference recording of `tests/afsk1200.rs`.
+
+Three AX.25 UI frames, HDLC framed and NRZI encoded, are sent in Bell 202 AFSK
+(1200 Hz mark, 2200 Hz space, 1200 baud) as a receiver would output them: the
+space tone 4.4 dB below the mark one (de-emphasis), a clock 0.2% fast and
+white gaussian noise. Keep the frames in sync with the test.
+"""
+import math
+import os
+import random
+import struct
+import wave
+
+HERE = os.path.dirname(os.path.abspath(__file__))
+RATE = 22050
+BAUD = 1200
+MARK = 1200
+SPACE = 2200
+CLOCK_ERROR = 1.002
+SPACE_GAIN = 0.6
+NOISE = 0.1
+PREAMBLE_FLAGS = 32
+
+
+def crc16_x25(data):
+    crc = 0xFFFF
+    for byte in data:
+        crc ^= byte
+        for _ in range(8):
+            crc = (crc >> 1) ^ 0x8408 if crc & 1 else crc >> 1
+    return crc ^ 0xFFFF
+
+
+def address(call, last):
+    callsign, _, ssid = call.partition("-")
+    field = bytes(ord(c) << 1 for c in f"{callsign:<6}")
+    return field + bytes([0x60 | (int(ssid or 0) << 1) | int(last)])
+
+
+def ui_frame(source, destination, path, info):
+    calls = [destination, source] + path
+    addresses = b"".join(address(c, i == len(calls) - 1) for i, c in enumerate(calls))
+    return addresses + bytes([0x03, 0xF0]) + info
+
+
+FRAMES = [
+    ui_frame("F4ABC-7", "APRS", ["WIDE1-1"], b"!4903.50N/07201.75W-fsdr-cli AFSK1200 test"),
+    ui_frame("F5XYZ", "APZ001", ["WIDE1-1", "WIDE2-2"], b">Testing the Bell 202 demodulator"),
+    ui_frame("F4ABC-7", "APRS", [], b":F5XYZ    :Hello world{1"),
+]
+
+
+def hdlc_bits(data):
+    flag = [0, 1, 1, 1, 1, 1, 1, 0]
+    bits = flag * PREAMBLE_FLAGS
+    ones = 0
+    for byte in data + crc16_x25(data).to_bytes(2, "little"):
+        for i in range(8):
+            bit = (byte >> i) & 1
+            bits.append(bit)
+            ones = ones + 1 if bit else 0
+            if ones == 5:
+                bits.append(0)
+                ones = 0
+    return bits + flag * 3
+
+
+def main():
+    rnd = random.Random(1)
+    silence = RATE // 5
+    samples = [rnd.gauss(0, NOISE) for _ in range(silence)]
+    level = 0
+    phase = 0.0
+    for frame in FRAMES:
+        # NRZI: a 0 is a change of tone
+        levels = []
+        for bit in hdlc_bits(frame):
+            level ^= 1 - bit
+            levels.append(level)
+        n = 0
+        while True:
+            k = int(n * BAUD * CLOCK_ERROR / RATE)
+            if k >= len(levels):
+                break
+            mark = levels[k] == 1
+            phase = (phase + 2 * math.pi * (MARK if mark else SPACE) / RATE) % (2 * math.pi)
+            amplitude = 0.5 if mark else 0.5 * SPACE_GAIN
+            samples.append(amplitude * math.sin(phase) + rnd.gauss(0, NOISE))
+            n += 1
+        samples += [rnd.gauss(0, NOISE) for _ in range(silence)]
+
+    with wave.open(os.path.join(HERE, "afsk1200.wav"), "wb") as wav:
+        wav.setnchannels(1)
+        wav.setsampwidth(2)
+        wav.setframerate(RATE)
+        wav.writeframes(
+            b"".join(struct.pack("<h", max(-32768, min(32767, round(s * 32767)))) for s in samples)
+        )
+
+
+if __name__ == "__main__":
+    main()